@@ -201,6 +201,8 @@ export class GlobalErrorService {
         return 'Not Found';
       case ErrorCode.DbAlreadyExists:
         return 'Already Exists';
+      case ErrorCode.Timeout:
+        return 'Timed Out';
       case ErrorCode.InternalError:
       case ErrorCode.LockPoisoned:
         return 'System Error';
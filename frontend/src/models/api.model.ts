@@ -1,4 +1,10 @@
 // User model exports
 // Re-export user types for convenience - the single source of truth is user.model.ts
 
-export type { User, UserCreatePayload, UserUpdatePayload } from './user.model';
+export type {
+  User,
+  UserChangeResponse,
+  UserCreatePayload,
+  UserCreateResponse,
+  UserUpdatePayload,
+} from './user.model';
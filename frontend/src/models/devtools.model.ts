@@ -82,6 +82,27 @@ export interface ConfigInfo {
   port: number;
   debug_mode: boolean;
   features: string[];
+  /** The config file that was merged, if any */
+  config_file?: string | null;
+  /** The `[profile.<name>]` merged over the file, from --profile or APP_PROFILE */
+  profile?: string | null;
+  /** Profiles the config file defines */
+  profiles?: string[];
+  /** Every effective setting and the layer that set it */
+  values?: ConfigValue[];
+  validation_policy?: 'fail' | 'degraded';
+  /** Invalid settings replaced by defaults under the degraded policy */
+  problems?: { code: string; message: string; field?: string; cause?: string }[];
+  /** Settings changed by a hot reload that take effect on the next start */
+  pending_restart?: string[];
+}
+
+export type ConfigLayer = 'default' | 'file' | 'profile' | 'env' | 'cli';
+
+export interface ConfigValue {
+  key: string;
+  value: unknown;
+  source: { layer: ConfigLayer; origin?: string | null };
 }
 
 // ==================== Performance Metrics ====================
@@ -96,6 +117,75 @@ export interface PerformanceMetrics {
   pending_requests: number;
 }
 
+// ==================== Event Catalogue ====================
+export interface EventSchemaInfo {
+  event_type: string;
+  kind: 'json_schema' | 'rust_type';
+  description?: string | null;
+  /** JSON schema, for `json_schema` entries */
+  schema?: unknown;
+  /** Fully qualified Rust type, for `rust_type` entries */
+  rust_type?: string | null;
+}
+
+// ==================== DI Container ====================
+export interface DiServiceInfo {
+  type_name: string;
+  name?: string | null;
+  lifetime: 'instance' | 'lazy' | 'transient' | 'scoped';
+  instantiated: boolean;
+  dependencies: string[];
+  /** 0 for the window's scope, 1 for the root container */
+  depth: number;
+}
+
+export interface DiValidation {
+  valid: boolean;
+  error?: { message: string; details?: string; context?: Record<string, string> } | null;
+}
+
+// ==================== Serialization ====================
+export interface SerializationFormatStats {
+  format: string;
+  total_serializations: number;
+  total_deserializations: number;
+  total_bytes_sent: number;
+  total_bytes_received: number;
+  avg_serialization_time_us: number;
+  avg_deserialization_time_us: number;
+  compression_ratio: number;
+}
+
+export interface SerializationStats {
+  selected: string;
+  formats: SerializationFormatStats[];
+}
+
+export interface SerializationBenchmarkResult {
+  payload: string;
+  format: string;
+  bytes: number;
+  avg_serialization_time_us: number;
+  avg_deserialization_time_us: number;
+  size_ratio: number;
+  speed_ratio: number;
+}
+
+export interface SerializationFormatComparison {
+  format: string;
+  size_ratio: string;
+  speed_ratio: string;
+  readability: string;
+  browser_support: string;
+  use_case: string;
+}
+
+export interface SerializationBenchmark {
+  iterations: number;
+  results: SerializationBenchmarkResult[];
+  comparison: SerializationFormatComparison[];
+}
+
 // ==================== Event Log Entry ====================
 export interface EventLogEntry {
   id: number;
@@ -139,6 +229,7 @@ export type DevToolsTabId =
   | 'database'
   | 'config'
   | 'performance'
+  | 'serialization'
   | 'events'
   | 'bindings'
   | 'windows'
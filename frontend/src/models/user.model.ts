@@ -26,3 +26,14 @@ export interface UserUpdatePayload {
   role?: string;
   status?: string;
 }
+
+export interface UserCreateResponse {
+  id: number;
+  message: string;
+}
+
+export interface UserChangeResponse {
+  id: number;
+  rows_affected: number;
+  message: string;
+}
@@ -36,6 +36,7 @@ export enum ErrorCode {
 
   // System errors (6000-6999)
   LockPoisoned = 'LOCK_POISONED',
+  Timeout = 'TIMEOUT',
   InternalError = 'INTERNAL_ERROR',
 
   // Custom/unknown
@@ -66,6 +67,8 @@ export interface ErrorValue {
  * Matches the Rust to_response() output
  */
 export interface ErrorResponse {
+  /** Correlation id echoed from the request */
+  request_id?: string | null;
   success: false;
   data: null;
   error: ErrorValue;
@@ -75,6 +78,8 @@ export interface ErrorResponse {
  * API response envelope for success
  */
 export interface SuccessResponse<T> {
+  /** Correlation id echoed from the request */
+  request_id?: string | null;
   success: true;
   data: T;
   error: null;
@@ -304,6 +309,10 @@ export function toUserMessage(error: ErrorValue): string {
     return error.message || 'A field contains an invalid value.';
   }
 
+  if (error.code === ErrorCode.Timeout) {
+    return error.message || 'The operation timed out. Please try again.';
+  }
+
   // For system errors - provide more helpful messages
   if (error.code === ErrorCode.InternalError || error.code === ErrorCode.LockPoisoned) {
     // If we have a specific message, show it (without technical details)
@@ -6,62 +6,109 @@
 // 2. Convert API responses to Result types
 // 3. Propagate errors as values through the application
 
-import type { User, UserCreatePayload, UserUpdatePayload } from '../models/user.model';
+import type {
+  User,
+  UserChangeResponse,
+  UserCreatePayload,
+  UserCreateResponse,
+  UserUpdatePayload,
+} from '../models/user.model';
 import { ApiResponse, ErrorCode, ErrorValue, Result } from '../types/error.types';
 import { getLogger } from './logger';
+import {
+  SUPPORTED_FORMATS,
+  SerializationFormat,
+  decode,
+  encode,
+  formatFromMimeType,
+  isBinaryFormat,
+  mimeType,
+  parseFormat,
+  toBase64,
+} from './serialization-codec';
+import { callOverWebSocket, getWebSocketUrl } from './websocket-client';
 
 const logger = getLogger('api-client');
 
+/** Format announced by the backend (`window.__WEBUI_SERIALIZATION`) */
+let announcedFormat: SerializationFormat = 'json';
+/** Format agreed through `serialization:negotiate` for WebUI binding calls */
+let negotiatedFormat: SerializationFormat = 'json';
+
+let requestCounter = 0;
+
+/**
+ * Generate a correlation id that is echoed back in the response envelope
+ */
+export function nextRequestId(): string {
+  requestCounter += 1;
+  return `${Date.now().toString(36)}-${requestCounter}`;
+}
+
 /**
  * Call a backend function and return a typed Result
+ *
+ * The payload is the first argument, as a JSON string, and a fresh request id
+ * is the second. Once a binary format is negotiated, the payload is base64 in
+ * that format and the format name is the third argument. Only the response
+ * carrying the same request id settles this call, so concurrent calls to one
+ * function never cross.
  */
-export async function callBackend<T>(functionName: string, ...args: unknown[]): Promise<Result<T>> {
+export async function callBackend<T>(functionName: string, payload?: unknown, timeoutMs = 30000): Promise<Result<T>> {
+  const apiBaseUrl = getApiBaseUrl();
+  if (apiBaseUrl) {
+    return callRest<T>(apiBaseUrl, functionName, payload, timeoutMs);
+  }
+
+  const wsUrl = getWebSocketUrl();
+  if (wsUrl) {
+    const requestId = nextRequestId();
+    const sent = await callOverWebSocket<T>(wsUrl, functionName, requestId, payload, timeoutMs);
+    return sent.ok ? envelopeToResult<T>(functionName, requestId, sent.value) : sent;
+  }
+
   return new Promise<Result<T>>((resolve) => {
     const responseEventName = getResponseEventName(functionName);
+    const requestId = nextRequestId();
 
-    const handler = (event: Event) => {
+    const cleanup = () => {
       clearTimeout(timeoutId);
       window.removeEventListener(responseEventName, handler as EventListener);
+    };
 
+    const handler = (event: Event) => {
       const customEvent = event as CustomEvent;
-      const response = customEvent.detail?.response as Record<string, unknown> | undefined;
+      const response = customEvent.detail as ApiResponse<T> | undefined;
 
-      if (response && 'data' in response && response.data !== undefined) {
-        logger.debug(`Backend call success: ${functionName}`);
-        resolve({ ok: true, value: response.data as T });
-      } else {
-        const respError = response?.error as { code?: ErrorCode; message?: string; details?: string } | undefined;
-        const errorValue: ErrorValue = {
-          code: (respError?.code as ErrorCode) || ErrorCode.InternalError,
-          message: respError?.message || `Backend call failed: ${functionName}`,
-          details: respError?.details,
-        };
-        logger.error(`Backend call failed: ${functionName}`, {}, new Error(errorValue.message));
-        resolve({ ok: false, error: errorValue });
+      if (response?.request_id !== requestId) {
+        return;
       }
+      cleanup();
+
+      resolve(envelopeToResult<T>(functionName, requestId, response));
     };
 
     const timeoutId = setTimeout(() => {
       window.removeEventListener(responseEventName, handler as EventListener);
-      logger.error(`Backend call timeout: ${functionName}`);
+      logger.error(`Backend call timeout: ${functionName}`, { requestId });
       resolve({
         ok: false,
         error: {
           code: ErrorCode.InternalError,
           message: `Backend call timeout: ${functionName}`,
           details: 'No response received within timeout period',
+          context: { request_id: requestId },
         },
       });
-    }, 30000);
+    }, timeoutMs);
 
-    window.addEventListener(responseEventName, handler as EventListener, { once: true });
+    window.addEventListener(responseEventName, handler as EventListener);
 
     try {
       const backendFn = (window as unknown as Record<string, unknown>)[functionName];
 
       if (typeof backendFn !== 'function') {
-        clearTimeout(timeoutId);
-        window.removeEventListener(responseEventName, handler as EventListener);
+        cleanup();
         logger.error(`Backend function not found: ${functionName}`);
         resolve({
           ok: false,
@@ -74,58 +121,193 @@ export async function callBackend<T>(functionName: string, ...args: unknown[]):
         return;
       }
 
-      backendFn(...args);
-      logger.debug(`Backend call initiated: ${functionName}`);
+      if (isBinaryFormat(negotiatedFormat)) {
+        const body = payload === undefined ? '' : toBase64(encode(negotiatedFormat, payload));
+        backendFn(body, requestId, negotiatedFormat);
+      } else {
+        const body = payload === undefined ? '' : JSON.stringify(payload);
+        backendFn(body, requestId);
+      }
+      logger.debug(`Backend call initiated: ${functionName}`, { requestId });
     } catch (error) {
-      clearTimeout(timeoutId);
-      window.removeEventListener(responseEventName, handler as EventListener);
-      logger.error(`Backend call failed: ${functionName}`, { error: String(error) });
+      cleanup();
+      logger.error(`Backend call failed: ${functionName}`, { requestId, error: String(error) });
       resolve({
         ok: false,
         error: {
           code: ErrorCode.InternalError,
           message: `Failed to call backend: ${functionName}`,
           cause: error instanceof Error ? error.message : String(error),
+          context: { request_id: requestId },
         },
       });
     }
   });
 }
 
-function getResponseEventName(functionName: string): string {
-  const eventMap: Record<string, string> = {
-    get_users: 'db_response',
-    create_user: 'user_create_response',
-    update_user: 'user_update_response',
-    delete_user: 'user_delete_response',
-    get_system_info: 'sysinfo_response',
+/**
+ * Call a backend function over the http_rest transport
+ *
+ * Uses the generic `POST /api/rpc/{method}` route; the request id travels in
+ * the X-Request-Id header and comes back in the response envelope.
+ */
+async function callRest<T>(baseUrl: string, functionName: string, payload: unknown, timeoutMs: number): Promise<Result<T>> {
+  const requestId = nextRequestId();
+  const controller = new AbortController();
+  const timeoutId = setTimeout(() => controller.abort(), timeoutMs);
+
+  try {
+    const format = announcedFormat;
+    const response = await fetch(`${baseUrl}/api/rpc/${encodeURIComponent(functionName)}`, {
+      method: 'POST',
+      headers: { 'Content-Type': mimeType(format), Accept: mimeType(format), 'X-Request-Id': requestId },
+      body: payload === undefined ? '' : encode(format, payload),
+      signal: controller.signal,
+    });
+    const bytes = new Uint8Array(await response.arrayBuffer());
+    const envelope = decode(formatFromMimeType(response.headers.get('Content-Type')), bytes) as ApiResponse<T>;
+    return envelopeToResult<T>(functionName, requestId, envelope);
+  } catch (error) {
+    const timedOut = controller.signal.aborted;
+    logger.error(`Backend call failed: ${functionName}`, { requestId, error: String(error) });
+    return {
+      ok: false,
+      error: {
+        code: ErrorCode.InternalError,
+        message: timedOut ? `Backend call timeout: ${functionName}` : `Failed to call backend: ${functionName}`,
+        cause: error instanceof Error ? error.message : String(error),
+        context: { request_id: requestId },
+      },
+    };
+  } finally {
+    clearTimeout(timeoutId);
+  }
+}
+
+function envelopeToResult<T>(functionName: string, requestId: string, response: ApiResponse<T>): Result<T> {
+  if (response.success) {
+    logger.debug(`Backend call success: ${functionName}`, { requestId });
+    return { ok: true, value: response.data as T };
+  }
+
+  const respError = response.error ?? undefined;
+  const errorValue: ErrorValue = {
+    code: respError?.code || ErrorCode.InternalError,
+    message: respError?.message || `Backend call failed: ${functionName}`,
+    details: respError?.details,
+    field: respError?.field,
+    context: { ...respError?.context, request_id: requestId },
   };
+  logger.error(`Backend call failed: ${functionName}`, { requestId }, new Error(errorValue.message));
+  return { ok: false, error: errorValue };
+}
+
+/**
+ * Agree on the serialization format announced by the backend
+ *
+ * The negotiation call itself travels as JSON; once it succeeds, binding calls
+ * send base64 payloads in the agreed format and responses arrive as raw bytes
+ * through `window.__webuiRpcResponse`.
+ */
+async function negotiateSerialization(announced: unknown): Promise<void> {
+  const format = parseFormat(announced);
+  if (!format) {
+    logger.warn('Backend announced an unknown serialization format', { announced: String(announced) });
+    return;
+  }
+  announcedFormat = format;
+  if (!isBinaryFormat(format) || getApiBaseUrl() || getWebSocketUrl()) {
+    return;
+  }
 
-  return eventMap[functionName] || `${functionName}_response`;
+  const result = await callBackend<{ format: string }>('serialization:negotiate', { formats: SUPPORTED_FORMATS });
+  if (result.ok) {
+    negotiatedFormat = parseFormat(result.value.format) ?? 'json';
+    logger.info(`Serialization negotiated: ${negotiatedFormat}`);
+  }
+}
+
+/**
+ * Receive raw response envelopes and pushes sent with `webui_send_raw`
+ *
+ * Raw responses only exist for binary formats, so they are decoded with the
+ * announced format even while the negotiation reply is still in flight.
+ */
+function handleRawResponse(bytes: Uint8Array): void {
+  try {
+    const envelope = decode(announcedFormat, bytes) as ApiResponse<unknown> & {
+      method?: string;
+      push?: string;
+      data?: unknown;
+    };
+    if (envelope.push) {
+      // Backend-initiated push (`rpc::push_to_window`), not a call response
+      window.dispatchEvent(new CustomEvent(envelope.push, { detail: envelope.data }));
+      return;
+    }
+    if (!envelope.method) {
+      logger.error('Raw response without method');
+      return;
+    }
+    window.dispatchEvent(new CustomEvent(getResponseEventName(envelope.method), { detail: envelope }));
+  } catch (error) {
+    logger.error('Failed to decode raw response', { format: announcedFormat, error: String(error) });
+  }
+}
+
+function installSerializationNegotiation(): void {
+  if (typeof window === 'undefined') {
+    return;
+  }
+  const globals = window as unknown as {
+    __WEBUI_SERIALIZATION?: unknown;
+    __webuiRpcResponse?: (bytes: Uint8Array) => void;
+  };
+  globals.__webuiRpcResponse = handleRawResponse;
+  window.addEventListener('webui:serialization', (event) => {
+    void negotiateSerialization((event as CustomEvent<{ format?: string }>).detail?.format);
+  });
+  if (globals.__WEBUI_SERIALIZATION !== undefined) {
+    void negotiateSerialization(globals.__WEBUI_SERIALIZATION);
+  }
+}
+
+installSerializationNegotiation();
+
+function getApiBaseUrl(): string | undefined {
+  const baseUrl = (window as unknown as { __API_BASE_URL?: unknown }).__API_BASE_URL;
+  return typeof baseUrl === 'string' && baseUrl.length > 0 ? baseUrl : undefined;
+}
+
+function getResponseEventName(functionName: string): string {
+  return `${functionName}_response`;
 }
 
 /**
  * User API functions
  */
-export type { User, UserCreatePayload, UserUpdatePayload } from '../models/user.model';
+export type {
+  User,
+  UserChangeResponse,
+  UserCreatePayload,
+  UserCreateResponse,
+  UserUpdatePayload,
+} from '../models/user.model';
 
 export async function getUsers(): Promise<Result<User[]>> {
   return callBackend<User[]>('get_users');
 }
 
-export async function createUser(payload: UserCreatePayload): Promise<Result<number>> {
-  const elementName = `create_user:${payload.name}:${payload.email}:${payload.role ?? 'User'}:${payload.status ?? 'Active'}`;
-  return callBackend<number>('create_user', elementName);
+export async function createUser(payload: UserCreatePayload): Promise<Result<UserCreateResponse>> {
+  return callBackend<UserCreateResponse>('create_user', payload);
 }
 
-export async function updateUser(payload: UserUpdatePayload): Promise<Result<number>> {
-  const elementName = `update_user:${payload.id}:${payload.name ?? ''}:${payload.email ?? ''}:${payload.role ?? ''}:${payload.status ?? ''}`;
-  return callBackend<number>('update_user', elementName);
+export async function updateUser(payload: UserUpdatePayload): Promise<Result<UserChangeResponse>> {
+  return callBackend<UserChangeResponse>('update_user', payload);
 }
 
-export async function deleteUser(id: number): Promise<Result<number>> {
-  const elementName = `delete_user:${id}`;
-  return callBackend<number>('delete_user', elementName);
+export async function deleteUser(id: number): Promise<Result<UserChangeResponse>> {
+  return callBackend<UserChangeResponse>('delete_user', { id });
 }
 
 export async function getSystemInfo(): Promise<Result<Record<string, unknown>>> {
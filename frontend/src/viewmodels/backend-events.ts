@@ -0,0 +1,154 @@
+// frontend/src/viewmodels/backend-events.ts
+// Backend event bus subscriptions: matching events are pushed as they are published
+//
+// In a WebUI window the backend keeps a subscription table per window
+// (`event:subscribe` / `event:unsubscribe`) and pushes `event:message` window
+// events. Over the WebSocket transport the `events` push channel is filtered
+// locally instead.
+//
+// Requests carry a correlation id and an optional target endpoint (`window:3`,
+// a backend service name); exactly one reply settles them.
+
+import { Result } from '../types/error.types';
+import { callBackend } from './api-client';
+import { getLogger } from './logger';
+import { getWebSocketUrl, onPush } from './websocket-client';
+
+const logger = getLogger('backend-events');
+
+/** Window event the backend dispatches for every pushed bus event */
+export const BACKEND_EVENT_PUSH = 'event:message';
+
+export interface BackendEvent<P = unknown> {
+  event_type: string;
+  payload: P;
+  timestamp: number;
+  source?: string | null;
+  target?: string | null;
+  /** Present on requests and their replies */
+  correlation_id?: string;
+}
+
+export interface BackendRequestOptions {
+  /** Endpoint to address; every matching listener may answer when omitted */
+  target?: string;
+  timeoutMs?: number;
+}
+
+interface BackendEventPush {
+  subscription: string;
+  event: BackendEvent;
+}
+
+type BackendEventHandler<P = unknown> = (event: BackendEvent<P>) => void;
+
+const handlers = new Map<string, Set<BackendEventHandler>>();
+let listening = false;
+
+/**
+ * Receive backend events of an exact type or topic pattern (`user.*`, `user.#`, `#`)
+ *
+ * Returns an unsubscribe function; the backend subscription is dropped with the
+ * last handler of a pattern.
+ */
+export function onBackendEvent<P = unknown>(eventType: string, handler: BackendEventHandler<P>): () => void {
+  if (getWebSocketUrl()) {
+    return onPush('events', (data) => {
+      const event = data as BackendEvent<P>;
+      if (topicMatches(eventType, event.event_type)) {
+        handler(event);
+      }
+    });
+  }
+
+  listen();
+  const bucket = handlers.get(eventType) ?? new Set<BackendEventHandler>();
+  const isFirst = bucket.size === 0;
+  bucket.add(handler as BackendEventHandler);
+  handlers.set(eventType, bucket);
+  if (isFirst) {
+    void callBackend('event:subscribe', { event_type: eventType }).then((result) => {
+      if (!result.ok) {
+        logger.error(`Failed to subscribe to backend events: ${eventType}`, { error: result.error.message });
+      }
+    });
+  }
+
+  return () => {
+    const current = handlers.get(eventType);
+    current?.delete(handler as BackendEventHandler);
+    if (current && current.size === 0) {
+      handlers.delete(eventType);
+      void callBackend('event:unsubscribe', { event_type: eventType });
+    }
+  };
+}
+
+/**
+ * Publish a request on the backend bus and wait for the first reply
+ */
+export async function requestBackendEvent<R = unknown>(
+  eventType: string,
+  data: unknown,
+  options: BackendRequestOptions = {}
+): Promise<Result<BackendEvent<R>>> {
+  const timeoutMs = options.timeoutMs ?? 5000;
+  return callBackend<BackendEvent<R>>(
+    'event:request',
+    { event_type: eventType, data, target: options.target, timeout_ms: timeoutMs },
+    // Leave the backend time to report its own timeout error
+    timeoutMs + 1000
+  );
+}
+
+/**
+ * Answer a request received through `onBackendEvent`; false if it was already answered
+ */
+export async function replyToBackendEvent(request: BackendEvent, data: unknown): Promise<Result<boolean>> {
+  if (!request.correlation_id) {
+    logger.warn(`Cannot reply to ${request.event_type}: not a request`);
+    return { ok: true, value: false };
+  }
+  const result = await callBackend<{ delivered: boolean }>('event:reply', {
+    correlation_id: request.correlation_id,
+    data,
+  });
+  return result.ok ? { ok: true, value: result.value.delivered } : result;
+}
+
+function listen(): void {
+  if (listening || typeof window === 'undefined') {
+    return;
+  }
+  listening = true;
+  window.addEventListener(BACKEND_EVENT_PUSH, (event) => {
+    const push = (event as CustomEvent<BackendEventPush | undefined>).detail;
+    if (!push) {
+      return;
+    }
+    for (const handler of handlers.get(push.subscription) ?? []) {
+      try {
+        handler(push.event);
+      } catch (error) {
+        logger.error(`Backend event handler failed: ${push.event.event_type}`, { error: String(error) });
+      }
+    }
+  });
+}
+
+/**
+ * Same rules as the backend: `*` matches one segment, `#` any number
+ */
+export function topicMatches(pattern: string, topic: string): boolean {
+  const matchSegments = (p: string[], t: string[]): boolean => {
+    if (p.length === 0) {
+      return t.length === 0;
+    }
+    const [head, ...rest] = p;
+    if (head === '#') {
+      return t.some((_, skip) => matchSegments(rest, t.slice(skip))) || matchSegments(rest, []);
+    }
+    return t.length > 0 && (head === '*' || head === t[0]) && matchSegments(rest, t.slice(1));
+  };
+  return matchSegments(pattern.split('.'), topic.split('.'));
+}
@@ -5,6 +5,8 @@
 
 import { Injectable, signal, computed, inject } from '@angular/core';
 import { EventBusViewModel } from './event-bus.viewmodel';
+import { callBackend } from './api-client';
+import { onBackendEvent } from './backend-events';
 import { getLogger } from './logger';
 import type {
   SystemInfo,
@@ -13,7 +15,13 @@ import type {
   NetworkInfo,
   DatabaseInfo,
   ConfigInfo,
+  ConfigValue,
   PerformanceMetrics,
+  SerializationStats,
+  SerializationBenchmark,
+  EventSchemaInfo,
+  DiServiceInfo,
+  DiValidation,
   EnvironmentInfo,
   EventLogEntry,
   BackendBinding,
@@ -40,7 +48,12 @@ export class DevToolsService {
   private readonly configInfoSignal = signal<ConfigInfo | null>(null);
   private readonly performanceSignal = signal<PerformanceMetrics | null>(null);
   private readonly environmentSignal = signal<EnvironmentInfo | null>(null);
+  private readonly serializationStatsSignal = signal<SerializationStats | null>(null);
+  private readonly serializationBenchmarkSignal = signal<SerializationBenchmark | null>(null);
   private readonly eventsSignal = signal<EventLogEntry[]>([]);
+  private readonly eventCatalogueSignal = signal<EventSchemaInfo[]>([]);
+  private readonly diServicesSignal = signal<DiServiceInfo[]>([]);
+  private readonly diValidationSignal = signal<DiValidation | null>(null);
   private readonly bindingsSignal = signal<BackendBinding[]>([]);
   private readonly windowsSignal = signal<WindowState[]>([]);
   private readonly logsSignal = signal<LogEntry[]>([]);
@@ -54,13 +67,19 @@ export class DevToolsService {
   readonly configInfo = computed(() => this.configInfoSignal());
   readonly performance = computed(() => this.performanceSignal());
   readonly environment = computed(() => this.environmentSignal());
+  readonly serializationStats = computed(() => this.serializationStatsSignal());
+  readonly serializationBenchmark = computed(() => this.serializationBenchmarkSignal());
   readonly events = computed(() => this.eventsSignal());
+  readonly eventCatalogue = computed(() => this.eventCatalogueSignal());
+  readonly diServices = computed(() => this.diServicesSignal());
+  readonly diValidation = computed(() => this.diValidationSignal());
   readonly bindings = computed(() => this.bindingsSignal());
   readonly windows = computed(() => this.windowsSignal());
   readonly logs = computed(() => this.logsSignal());
 
   private refreshTimer: number | null = null;
   private eventCounter = 0;
+  private configChangesUnsubscribe: (() => void) | null = null;
 
   constructor() {
     this.setupEventListeners();
@@ -73,6 +92,23 @@ export class DevToolsService {
     logger.info('DevTools initialized');
     this.gatherEnvironmentInfo();
     this.gatherAllData();
+    this.watchConfigChanges();
+  }
+
+  /**
+   * Refresh the config panel when the backend reloads its config file
+   */
+  private watchConfigChanges(): void {
+    if (this.configChangesUnsubscribe) {
+      return;
+    }
+    this.configChangesUnsubscribe = onBackendEvent<{ changes: unknown[]; restart_required: string[] }>(
+      'config.changed',
+      (event) => {
+        this.addEvent('system', 'config.changed', `${event.payload.changes.length} setting(s) changed`, event.payload);
+        void this.gatherConfigInfo();
+      }
+    );
   }
 
   /**
@@ -119,6 +155,7 @@ export class DevToolsService {
       this.gatherNetworkInfo(),
       this.gatherDatabaseInfo(),
       this.gatherPerformanceMetrics(),
+      this.gatherSerializationStats(),
       this.gatherBindingsInfo(),
       this.gatherWindowsInfo(),
     ]);
@@ -260,6 +297,13 @@ export class DevToolsService {
           port: Number(result.port || 0),
           debug_mode: Boolean(result.debug_mode),
           features: (result.features as string[]) || [],
+          config_file: (result.config_file as string | null) ?? null,
+          profile: (result.profile as string | null) ?? null,
+          profiles: (result.profiles as string[]) || [],
+          values: (result.values as ConfigValue[]) || [],
+          validation_policy: result.validation_policy as ConfigInfo['validation_policy'],
+          problems: (result.problems as ConfigInfo['problems']) || [],
+          pending_restart: (result.pending_restart as string[]) || [],
         });
       }
     } catch (error) {
@@ -302,6 +346,65 @@ export class DevToolsService {
     }
   }
 
+  /**
+   * Gather live serialization counters from backend
+   */
+  private async gatherSerializationStats(): Promise<void> {
+    const result = await this.callBackend<SerializationStats>('serialization:stats');
+    if (result) {
+      this.serializationStatsSignal.set(result);
+    }
+  }
+
+  /**
+   * Benchmark every format against real payloads on the backend
+   */
+  async runSerializationBenchmark(iterations: number = 100): Promise<void> {
+    const result = await this.callBackend<SerializationBenchmark>('serialization:benchmark', { iterations }, 30000);
+    if (result) {
+      this.serializationBenchmarkSignal.set(result);
+      this.addEvent('info', 'devtools', `Serialization benchmark finished (${iterations} iterations)`);
+      // The size ratios in the live stats come from the benchmark
+      await this.gatherSerializationStats();
+    }
+  }
+
+  /**
+   * Reset the live serialization counters
+   */
+  async resetSerializationStats(): Promise<void> {
+    const result = await this.callBackend<SerializationStats>('serialization:reset_stats');
+    if (result) {
+      this.serializationStatsSignal.set(result);
+    }
+  }
+
+  /**
+   * Load the documented event types and their payload schemas
+   */
+  async loadEventCatalogue(): Promise<void> {
+    const result = await this.callBackend<EventSchemaInfo[]>('event:schemas');
+    if (result) {
+      this.eventCatalogueSignal.set(result);
+    }
+  }
+
+  /**
+   * Load the DI container's registrations and check its dependency graph
+   */
+  async loadDiServices(): Promise<void> {
+    const [services, validation] = await Promise.all([
+      this.callBackend<DiServiceInfo[]>('di:services'),
+      this.callBackend<DiValidation>('di:validate'),
+    ]);
+    if (services) {
+      this.diServicesSignal.set(services);
+    }
+    if (validation) {
+      this.diValidationSignal.set(validation);
+    }
+  }
+
   /**
    * Calculate approximate FPS
    */
@@ -379,6 +482,13 @@ export class DevToolsService {
       'get_database_info',
       'get_config_info',
       'get_logs',
+      'serialization:stats',
+      'serialization:benchmark',
+      'event:schemas',
+      'di:services',
+      'di:validate',
+      'settings:get',
+      'settings:update',
     ].map((name) => {
       const fn = (window as unknown as Record<string, unknown>)[name];
       return {
@@ -421,50 +531,13 @@ export class DevToolsService {
   /**
    * Call backend function with error handling
    */
-  private async callBackend<T>(functionName: string, ...args: unknown[]): Promise<T | null> {
-    return new Promise((resolve) => {
-      const responseEventName = `${functionName}_response`;
-      const timeoutMs = 5000;
-
-      const handler = (event: Event) => {
-        clearTimeout(timeoutId);
-        window.removeEventListener(responseEventName, handler as EventListener);
-
-        const customEvent = event as CustomEvent;
-        const response = customEvent.detail?.response as { data?: T; error?: { message?: string } } | undefined;
-
-        if (response?.data !== undefined) {
-          resolve(response.data);
-        } else {
-          logger.debug(`Backend call returned no data: ${functionName}`);
-          resolve(null);
-        }
-      };
-
-      const timeoutId = setTimeout(() => {
-        window.removeEventListener(responseEventName, handler as EventListener);
-        logger.debug(`Backend call timeout: ${functionName}`);
-        resolve(null);
-      }, timeoutMs);
-
-      window.addEventListener(responseEventName, handler as EventListener, { once: true });
-
-      try {
-        const backendFn = (window as unknown as Record<string, unknown>)[functionName];
-        if (typeof backendFn === 'function') {
-          backendFn(...args);
-        } else {
-          clearTimeout(timeoutId);
-          window.removeEventListener(responseEventName, handler as EventListener);
-          resolve(null);
-        }
-      } catch (error) {
-        clearTimeout(timeoutId);
-        window.removeEventListener(responseEventName, handler as EventListener);
-        logger.debug(`Backend call failed: ${functionName}`, { error });
-        resolve(null);
-      }
-    });
+  private async callBackend<T>(functionName: string, payload?: unknown, timeoutMs: number = 5000): Promise<T | null> {
+    const result = await callBackend<T>(functionName, payload, timeoutMs);
+    if (result.ok) {
+      return result.value;
+    }
+    logger.debug(`Backend call returned no data: ${functionName}`, { error: result.error });
+    return null;
   }
 
   /**
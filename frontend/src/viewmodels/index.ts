@@ -1,4 +1,6 @@
+export * from './backend-events';
 export * from './event-bus.viewmodel';
 export * from './logger';
 export * from './logging.viewmodel';
+export * from './theme.viewmodel';
 export * from './window-state.viewmodel';
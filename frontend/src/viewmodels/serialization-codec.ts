@@ -0,0 +1,553 @@
+// frontend/src/viewmodels/serialization-codec.ts
+// Minimal MessagePack / CBOR codecs for the backend's response envelopes
+//
+// Only the JSON data model (null, booleans, numbers, strings, arrays, maps)
+// plus byte strings is supported, which is everything serde_json::Value can
+// carry. 64-bit integers are converted to JS numbers.
+
+export type SerializationFormat = 'json' | 'messagepack' | 'cbor';
+
+export const SUPPORTED_FORMATS: SerializationFormat[] = ['json', 'messagepack', 'cbor'];
+
+const MIME_TYPES: Record<SerializationFormat, string> = {
+  json: 'application/json',
+  messagepack: 'application/msgpack',
+  cbor: 'application/cbor',
+};
+
+const textEncoder = new TextEncoder();
+const textDecoder = new TextDecoder();
+
+export function isBinaryFormat(format: SerializationFormat): boolean {
+  return format !== 'json';
+}
+
+export function mimeType(format: SerializationFormat): string {
+  return MIME_TYPES[format];
+}
+
+export function formatFromMimeType(mime: string | null): SerializationFormat {
+  const essence = (mime ?? '').split(';')[0].trim().toLowerCase();
+  const match = (Object.keys(MIME_TYPES) as SerializationFormat[]).find((f) => MIME_TYPES[f] === essence);
+  return match ?? 'json';
+}
+
+export function parseFormat(name: unknown): SerializationFormat | undefined {
+  return SUPPORTED_FORMATS.find((format) => format === name);
+}
+
+export function encode(format: SerializationFormat, value: unknown): Uint8Array {
+  switch (format) {
+    case 'messagepack':
+      return encodeMsgPack(value);
+    case 'cbor':
+      return encodeCbor(value);
+    default:
+      return textEncoder.encode(JSON.stringify(value ?? null));
+  }
+}
+
+export function decode(format: SerializationFormat, bytes: Uint8Array): unknown {
+  switch (format) {
+    case 'messagepack':
+      return new MsgPackReader(bytes).read();
+    case 'cbor':
+      return new CborReader(bytes).read();
+    default:
+      return JSON.parse(textDecoder.decode(bytes));
+  }
+}
+
+export function toBase64(bytes: Uint8Array): string {
+  let binary = '';
+  for (let i = 0; i < bytes.length; i += 0x8000) {
+    binary += String.fromCharCode(...bytes.subarray(i, i + 0x8000));
+  }
+  return btoa(binary);
+}
+
+class ByteWriter {
+  private buffer = new Uint8Array(256);
+  private view = new DataView(this.buffer.buffer);
+  length = 0;
+
+  private ensure(extra: number): void {
+    if (this.length + extra <= this.buffer.length) {
+      return;
+    }
+    const next = new Uint8Array(Math.max(this.buffer.length * 2, this.length + extra));
+    next.set(this.buffer);
+    this.buffer = next;
+    this.view = new DataView(next.buffer);
+  }
+
+  u8(value: number): void {
+    this.ensure(1);
+    this.buffer[this.length++] = value;
+  }
+
+  u16(value: number): void {
+    this.ensure(2);
+    this.view.setUint16(this.length, value);
+    this.length += 2;
+  }
+
+  u32(value: number): void {
+    this.ensure(4);
+    this.view.setUint32(this.length, value);
+    this.length += 4;
+  }
+
+  u64(value: number): void {
+    this.ensure(8);
+    this.view.setBigUint64(this.length, BigInt(value));
+    this.length += 8;
+  }
+
+  i64(value: number): void {
+    this.ensure(8);
+    this.view.setBigInt64(this.length, BigInt(value));
+    this.length += 8;
+  }
+
+  f64(value: number): void {
+    this.ensure(8);
+    this.view.setFloat64(this.length, value);
+    this.length += 8;
+  }
+
+  bytes(value: Uint8Array): void {
+    this.ensure(value.length);
+    this.buffer.set(value, this.length);
+    this.length += value.length;
+  }
+
+  result(): Uint8Array {
+    return this.buffer.slice(0, this.length);
+  }
+}
+
+class ByteReader {
+  protected offset = 0;
+  protected readonly view: DataView;
+
+  constructor(protected readonly bytes: Uint8Array) {
+    this.view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+  }
+
+  protected need(count: number): void {
+    if (this.offset + count > this.bytes.length) {
+      throw new Error('Unexpected end of binary payload');
+    }
+  }
+
+  protected u8(): number {
+    this.need(1);
+    return this.bytes[this.offset++];
+  }
+
+  protected u16(): number {
+    this.need(2);
+    const value = this.view.getUint16(this.offset);
+    this.offset += 2;
+    return value;
+  }
+
+  protected u32(): number {
+    this.need(4);
+    const value = this.view.getUint32(this.offset);
+    this.offset += 4;
+    return value;
+  }
+
+  protected u64(): number {
+    this.need(8);
+    const value = Number(this.view.getBigUint64(this.offset));
+    this.offset += 8;
+    return value;
+  }
+
+  protected i64(): number {
+    this.need(8);
+    const value = Number(this.view.getBigInt64(this.offset));
+    this.offset += 8;
+    return value;
+  }
+
+  protected f16(): number {
+    const half = this.u16();
+    const exponent = (half >> 10) & 0x1f;
+    const fraction = half & 0x3ff;
+    const sign = half & 0x8000 ? -1 : 1;
+    if (exponent === 0) {
+      return sign * 2 ** -14 * (fraction / 1024);
+    }
+    if (exponent === 0x1f) {
+      return fraction ? Number.NaN : sign * Number.POSITIVE_INFINITY;
+    }
+    return sign * 2 ** (exponent - 15) * (1 + fraction / 1024);
+  }
+
+  protected f32(): number {
+    this.need(4);
+    const value = this.view.getFloat32(this.offset);
+    this.offset += 4;
+    return value;
+  }
+
+  protected f64(): number {
+    this.need(8);
+    const value = this.view.getFloat64(this.offset);
+    this.offset += 8;
+    return value;
+  }
+
+  protected take(count: number): Uint8Array {
+    this.need(count);
+    const slice = this.bytes.subarray(this.offset, this.offset + count);
+    this.offset += count;
+    return slice;
+  }
+
+  protected text(count: number): string {
+    return textDecoder.decode(this.take(count));
+  }
+}
+
+// ---------------------------------------------------------------------------
+// MessagePack
+// ---------------------------------------------------------------------------
+
+function encodeMsgPack(value: unknown): Uint8Array {
+  const writer = new ByteWriter();
+  writeMsgPack(writer, value);
+  return writer.result();
+}
+
+function writeMsgPack(w: ByteWriter, value: unknown): void {
+  if (value === null || value === undefined) {
+    w.u8(0xc0);
+  } else if (typeof value === 'boolean') {
+    w.u8(value ? 0xc3 : 0xc2);
+  } else if (typeof value === 'number') {
+    if (Number.isSafeInteger(value)) {
+      if (value >= 0 && value < 0x80) {
+        w.u8(value);
+      } else if (value < 0 && value >= -32) {
+        w.u8(value & 0xff);
+      } else if (value >= 0 && value <= 0xffffffff) {
+        w.u8(0xce);
+        w.u32(value);
+      } else if (value >= 0) {
+        w.u8(0xcf);
+        w.u64(value);
+      } else {
+        w.u8(0xd3);
+        w.i64(value);
+      }
+    } else {
+      w.u8(0xcb);
+      w.f64(value);
+    }
+  } else if (typeof value === 'string') {
+    const bytes = textEncoder.encode(value);
+    if (bytes.length < 32) {
+      w.u8(0xa0 | bytes.length);
+    } else if (bytes.length <= 0xffff) {
+      w.u8(0xda);
+      w.u16(bytes.length);
+    } else {
+      w.u8(0xdb);
+      w.u32(bytes.length);
+    }
+    w.bytes(bytes);
+  } else if (value instanceof Uint8Array) {
+    w.u8(0xc6);
+    w.u32(value.length);
+    w.bytes(value);
+  } else if (Array.isArray(value)) {
+    if (value.length < 16) {
+      w.u8(0x90 | value.length);
+    } else {
+      w.u8(0xdd);
+      w.u32(value.length);
+    }
+    for (const item of value) {
+      writeMsgPack(w, item);
+    }
+  } else if (typeof value === 'object') {
+    const entries = Object.entries(value as Record<string, unknown>).filter(([, v]) => v !== undefined);
+    if (entries.length < 16) {
+      w.u8(0x80 | entries.length);
+    } else {
+      w.u8(0xdf);
+      w.u32(entries.length);
+    }
+    for (const [key, item] of entries) {
+      writeMsgPack(w, key);
+      writeMsgPack(w, item);
+    }
+  } else {
+    throw new Error(`Cannot encode ${typeof value} as MessagePack`);
+  }
+}
+
+class MsgPackReader extends ByteReader {
+  read(): unknown {
+    const type = this.u8();
+    if (type < 0x80) return type;
+    if (type < 0x90) return this.map(type & 0x0f);
+    if (type < 0xa0) return this.array(type & 0x0f);
+    if (type < 0xc0) return this.text(type & 0x1f);
+    if (type >= 0xe0) return type - 0x100;
+
+    switch (type) {
+      case 0xc0:
+        return null;
+      case 0xc2:
+        return false;
+      case 0xc3:
+        return true;
+      case 0xc4:
+        return this.take(this.u8()).slice();
+      case 0xc5:
+        return this.take(this.u16()).slice();
+      case 0xc6:
+        return this.take(this.u32()).slice();
+      case 0xca:
+        return this.f32();
+      case 0xcb:
+        return this.f64();
+      case 0xcc:
+        return this.u8();
+      case 0xcd:
+        return this.u16();
+      case 0xce:
+        return this.u32();
+      case 0xcf:
+        return this.u64();
+      case 0xd0:
+        return (this.u8() << 24) >> 24;
+      case 0xd1:
+        return (this.u16() << 16) >> 16;
+      case 0xd2:
+        return this.u32() | 0;
+      case 0xd3:
+        return this.i64();
+      case 0xd9:
+        return this.text(this.u8());
+      case 0xda:
+        return this.text(this.u16());
+      case 0xdb:
+        return this.text(this.u32());
+      case 0xdc:
+        return this.array(this.u16());
+      case 0xdd:
+        return this.array(this.u32());
+      case 0xde:
+        return this.map(this.u16());
+      case 0xdf:
+        return this.map(this.u32());
+      default:
+        throw new Error(`Unsupported MessagePack type 0x${type.toString(16)}`);
+    }
+  }
+
+  private array(length: number): unknown[] {
+    const items: unknown[] = [];
+    for (let i = 0; i < length; i++) {
+      items.push(this.read());
+    }
+    return items;
+  }
+
+  private map(length: number): Record<string, unknown> {
+    const result: Record<string, unknown> = {};
+    for (let i = 0; i < length; i++) {
+      const key = this.read();
+      result[String(key)] = this.read();
+    }
+    return result;
+  }
+}
+
+// ---------------------------------------------------------------------------
+// CBOR (RFC 8949)
+// ---------------------------------------------------------------------------
+
+function encodeCbor(value: unknown): Uint8Array {
+  const writer = new ByteWriter();
+  writeCbor(writer, value);
+  return writer.result();
+}
+
+function writeCborHead(w: ByteWriter, major: number, length: number): void {
+  const prefix = major << 5;
+  if (length < 24) {
+    w.u8(prefix | length);
+  } else if (length <= 0xff) {
+    w.u8(prefix | 24);
+    w.u8(length);
+  } else if (length <= 0xffff) {
+    w.u8(prefix | 25);
+    w.u16(length);
+  } else if (length <= 0xffffffff) {
+    w.u8(prefix | 26);
+    w.u32(length);
+  } else {
+    w.u8(prefix | 27);
+    w.u64(length);
+  }
+}
+
+function writeCbor(w: ByteWriter, value: unknown): void {
+  if (value === null || value === undefined) {
+    w.u8(0xf6);
+  } else if (typeof value === 'boolean') {
+    w.u8(value ? 0xf5 : 0xf4);
+  } else if (typeof value === 'number') {
+    if (Number.isSafeInteger(value)) {
+      if (value >= 0) {
+        writeCborHead(w, 0, value);
+      } else {
+        writeCborHead(w, 1, -1 - value);
+      }
+    } else {
+      w.u8(0xfb);
+      w.f64(value);
+    }
+  } else if (typeof value === 'string') {
+    const bytes = textEncoder.encode(value);
+    writeCborHead(w, 3, bytes.length);
+    w.bytes(bytes);
+  } else if (value instanceof Uint8Array) {
+    writeCborHead(w, 2, value.length);
+    w.bytes(value);
+  } else if (Array.isArray(value)) {
+    writeCborHead(w, 4, value.length);
+    for (const item of value) {
+      writeCbor(w, item);
+    }
+  } else if (typeof value === 'object') {
+    const entries = Object.entries(value as Record<string, unknown>).filter(([, v]) => v !== undefined);
+    writeCborHead(w, 5, entries.length);
+    for (const [key, item] of entries) {
+      writeCbor(w, key);
+      writeCbor(w, item);
+    }
+  } else {
+    throw new Error(`Cannot encode ${typeof value} as CBOR`);
+  }
+}
+
+const CBOR_BREAK = Symbol('cbor-break');
+
+class CborReader extends ByteReader {
+  read(): unknown {
+    const value = this.item();
+    if (value === CBOR_BREAK) {
+      throw new Error('Unexpected CBOR break');
+    }
+    return value;
+  }
+
+  private length(info: number): number {
+    if (info < 24) return info;
+    switch (info) {
+      case 24:
+        return this.u8();
+      case 25:
+        return this.u16();
+      case 26:
+        return this.u32();
+      case 27:
+        return this.u64();
+      case 31:
+        return -1;
+      default:
+        throw new Error(`Invalid CBOR length encoding ${info}`);
+    }
+  }
+
+  private item(): unknown {
+    const head = this.u8();
+    const major = head >> 5;
+    const info = head & 0x1f;
+
+    if (major === 7) {
+      switch (info) {
+        case 20:
+          return false;
+        case 21:
+          return true;
+        case 22:
+        case 23:
+          return null;
+        case 25:
+          return this.f16();
+        case 26:
+          return this.f32();
+        case 27:
+          return this.f64();
+        case 31:
+          return CBOR_BREAK;
+        default:
+          throw new Error(`Unsupported CBOR simple value ${info}`);
+      }
+    }
+
+    const length = this.length(info);
+    switch (major) {
+      case 0:
+        return length;
+      case 1:
+        return -1 - length;
+      case 2:
+        return length < 0 ? this.chunks(2) : this.take(length).slice();
+      case 3:
+        return length < 0 ? textDecoder.decode(this.chunks(3)) : this.text(length);
+      case 4: {
+        const items: unknown[] = [];
+        for (let i = 0; length < 0 || i < length; i++) {
+          const item = this.item();
+          if (item === CBOR_BREAK) break;
+          items.push(item);
+        }
+        return items;
+      }
+      case 5: {
+        const result: Record<string, unknown> = {};
+        for (let i = 0; length < 0 || i < length; i++) {
+          const key = this.item();
+          if (key === CBOR_BREAK) break;
+          result[String(key)] = this.read();
+        }
+        return result;
+      }
+      default:
+        // Major type 6: semantic tag, the tagged item follows
+        return this.read();
+    }
+  }
+
+  /** Concatenate the chunks of an indefinite-length byte or text string */
+  private chunks(major: number): Uint8Array {
+    const parts: Uint8Array[] = [];
+    for (;;) {
+      const head = this.u8();
+      if (head === 0xff) break;
+      if (head >> 5 !== major) {
+        throw new Error('Invalid chunk in indefinite-length CBOR string');
+      }
+      parts.push(this.take(this.length(head & 0x1f)));
+    }
+    const total = parts.reduce((sum, part) => sum + part.length, 0);
+    const joined = new Uint8Array(total);
+    let offset = 0;
+    for (const part of parts) {
+      joined.set(part, offset);
+      offset += part.length;
+    }
+    return joined;
+  }
+}
@@ -0,0 +1,54 @@
+import { Injectable, OnDestroy, signal } from '@angular/core';
+import { callBackend } from './api-client';
+import { onBackendEvent } from './backend-events';
+import { getLogger } from './logger';
+
+/** The `features` part of the backend's `config.changed` payload */
+interface FeatureSettings {
+  dark_mode?: boolean | null;
+  show_tray_icon?: boolean | null;
+}
+
+@Injectable({ providedIn: 'root' })
+export class ThemeViewModel implements OnDestroy {
+  private readonly logger = getLogger('theme');
+  private configChangesUnsubscribe: (() => void) | null = null;
+
+  readonly darkMode = signal(true);
+
+  /**
+   * Apply `features.dark_mode` now and again whenever the backend reloads its config
+   */
+  init(): void {
+    if (this.configChangesUnsubscribe) {
+      return;
+    }
+    void callBackend<{ features?: string[] }>('get_config_info').then((result) => {
+      if (result.ok) {
+        this.apply((result.value.features ?? []).includes('dark_mode'));
+      }
+    });
+    this.configChangesUnsubscribe = onBackendEvent<{ features?: FeatureSettings }>('config.changed', (event) => {
+      const features = event.payload.features;
+      if (features) {
+        // Unset means the backend default, which is dark
+        this.apply(features.dark_mode ?? true);
+      }
+    });
+  }
+
+  ngOnDestroy(): void {
+    this.configChangesUnsubscribe?.();
+    this.configChangesUnsubscribe = null;
+  }
+
+  private apply(dark: boolean): void {
+    if (dark !== this.darkMode()) {
+      this.logger.info(`Theme: ${dark ? 'dark' : 'light'}`);
+    }
+    this.darkMode.set(dark);
+    if (typeof document !== 'undefined') {
+      document.body.classList.toggle('dark-theme', dark);
+    }
+  }
+}
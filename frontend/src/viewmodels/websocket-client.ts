@@ -0,0 +1,174 @@
+// frontend/src/viewmodels/websocket-client.ts
+// WebSocket transport client: RPC calls plus server push (events, logs, metrics)
+//
+// Used when the backend runs with `transport = "websocket"` and announces
+// `window.__WS_URL`. One socket is shared by all callers.
+
+import { ApiResponse, ErrorCode, Result } from '../types/error.types';
+import { getLogger } from './logger';
+import { SerializationFormat, decode, encode, isBinaryFormat, parseFormat } from './serialization-codec';
+
+const logger = getLogger('websocket-client');
+
+export type PushChannel = 'events' | 'logs' | 'metrics';
+
+type PushHandler = (data: unknown) => void;
+type ResponseFrame = ApiResponse<unknown> & { type: 'response'; method: string };
+
+interface PendingCall {
+  resolve: (response: ResponseFrame) => void;
+  timeoutId: ReturnType<typeof setTimeout>;
+}
+
+let socket: WebSocket | null = null;
+let ready: Promise<WebSocket> | null = null;
+/** Format from the server's welcome frame; binary formats use binary frames */
+let frameFormat: SerializationFormat = 'json';
+const pending = new Map<string, PendingCall>();
+const pushHandlers = new Map<PushChannel, Set<PushHandler>>();
+
+export function getWebSocketUrl(): string | undefined {
+  const url = (window as unknown as { __WS_URL?: unknown }).__WS_URL;
+  return typeof url === 'string' && url.length > 0 ? url : undefined;
+}
+
+function connect(url: string): Promise<WebSocket> {
+  if (ready) {
+    return ready;
+  }
+
+  ready = new Promise<WebSocket>((resolve, reject) => {
+    const ws = new WebSocket(url);
+    ws.binaryType = 'arraybuffer';
+    ws.onerror = () => reject(new Error(`WebSocket connection failed: ${url}`));
+    ws.onclose = () => {
+      logger.warn('WebSocket closed');
+      socket = null;
+      ready = null;
+    };
+    ws.onmessage = (message) => {
+      const frame = readFrame(message.data);
+      if (frame?.type === 'welcome') {
+        // The connection is usable once the server has told us its format
+        frameFormat = parseFormat(frame['format']) ?? 'json';
+        socket = ws;
+        const channels = [...pushHandlers.keys()];
+        if (channels.length > 0) {
+          sendFrame(ws, { type: 'subscribe', channels });
+        }
+        resolve(ws);
+      } else if (frame) {
+        handleFrame(frame);
+      }
+    };
+  });
+
+  return ready;
+}
+
+type Frame = { type?: string; [key: string]: unknown };
+
+function readFrame(data: unknown): Frame | undefined {
+  try {
+    if (data instanceof ArrayBuffer) {
+      return decode(frameFormat, new Uint8Array(data)) as Frame;
+    }
+    return JSON.parse(String(data)) as Frame;
+  } catch {
+    logger.error('Invalid WebSocket frame');
+    return undefined;
+  }
+}
+
+function sendFrame(ws: WebSocket, frame: Frame): void {
+  ws.send(isBinaryFormat(frameFormat) ? encode(frameFormat, frame) : JSON.stringify(frame));
+}
+
+function handleFrame(frame: Frame): void {
+  if (frame.type === 'response') {
+    const response = frame as unknown as ResponseFrame;
+    const call = response.request_id ? pending.get(response.request_id) : undefined;
+    if (call && response.request_id) {
+      clearTimeout(call.timeoutId);
+      pending.delete(response.request_id);
+      call.resolve(response);
+    }
+  } else if (frame.type === 'push') {
+    const handlers = pushHandlers.get(frame['channel'] as PushChannel);
+    handlers?.forEach((handler) => handler(frame['data']));
+  }
+}
+
+/**
+ * Send an RPC call over the socket; resolves with the response envelope
+ */
+export async function callOverWebSocket<T>(
+  url: string,
+  functionName: string,
+  requestId: string,
+  payload: unknown,
+  timeoutMs: number,
+): Promise<Result<ApiResponse<T>>> {
+  let ws: WebSocket;
+  try {
+    ws = await connect(url);
+  } catch (error) {
+    return {
+      ok: false,
+      error: {
+        code: ErrorCode.InternalError,
+        message: `Failed to call backend: ${functionName}`,
+        cause: error instanceof Error ? error.message : String(error),
+        context: { request_id: requestId },
+      },
+    };
+  }
+
+  return new Promise((resolve) => {
+    const timeoutId = setTimeout(() => {
+      pending.delete(requestId);
+      resolve({
+        ok: false,
+        error: {
+          code: ErrorCode.InternalError,
+          message: `Backend call timeout: ${functionName}`,
+          details: 'No response received within timeout period',
+          context: { request_id: requestId },
+        },
+      });
+    }, timeoutMs);
+
+    pending.set(requestId, {
+      resolve: (response) => resolve({ ok: true, value: response as ApiResponse<T> }),
+      timeoutId,
+    });
+    sendFrame(ws, { type: 'rpc', method: functionName, request_id: requestId, payload: payload ?? null });
+  });
+}
+
+/**
+ * Receive server pushes for a channel; returns an unsubscribe function
+ */
+export function onPush(channel: PushChannel, handler: PushHandler): () => void {
+  const handlers = pushHandlers.get(channel) ?? new Set<PushHandler>();
+  const isFirst = handlers.size === 0;
+  handlers.add(handler);
+  pushHandlers.set(channel, handlers);
+
+  const url = getWebSocketUrl();
+  if (isFirst && socket) {
+    sendFrame(socket, { type: 'subscribe', channels: [channel] });
+  } else if (url && !socket) {
+    connect(url).catch((error) => logger.error('WebSocket connection failed', { error: String(error) }));
+  }
+
+  return () => {
+    handlers.delete(handler);
+    if (handlers.size === 0) {
+      pushHandlers.delete(channel);
+      if (socket) {
+        sendFrame(socket, { type: 'unsubscribe', channels: [channel] });
+      }
+    }
+  };
+}
@@ -8,6 +8,7 @@ import { getLogger } from '../viewmodels/logger';
 import { WindowStateViewModel } from '../viewmodels/window-state.viewmodel';
 import { ErrorModalComponent } from './shared/error-modal.component';
 import { ConnectionMonitorService } from '../viewmodels/connection-monitor.service';
+import { ThemeViewModel } from '../viewmodels/theme.viewmodel';
 import { ViewportService } from '../viewmodels/viewport.service';
 import { DevToolsComponent } from './devtools/devtools.component';
 
@@ -26,6 +27,7 @@ export class AppComponent implements OnInit, OnDestroy {
   private readonly windowState = inject(WindowStateViewModel);
   private readonly connectionMonitor = inject(ConnectionMonitorService);
   private readonly viewportService = inject(ViewportService);
+  private readonly theme = inject(ThemeViewModel);
 
   searchQuery = signal('');
   windowEntries = signal<WindowEntry[]>([]);
@@ -70,6 +72,7 @@ export class AppComponent implements OnInit, OnDestroy {
 
   ngOnInit(): void {
     this.windowState.init();
+    this.theme.init();
     this.initWebSocketMonitor();
 
     if (typeof window !== 'undefined') {
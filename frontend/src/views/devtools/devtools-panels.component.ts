@@ -31,205 +31,205 @@ import { DevToolsService } from '../../viewmodels/devtools.service';
               <span class="info-row__value">{{ systemInfo()?.cpu_count }}</span>
             </div>
           } @else {
-            <div class="info-row__empty">Loading...</div>
-          }
-        </div>
+              <div class="info-row__empty">Loading...</div>
+            }
+          </div>
 
-        <div class="info-group">
-          <h3 class="info-group__title">⚙️ Process</h3>
-          @if (processInfo()) {
-            <div class="info-row">
-              <span class="info-row__label">PID:</span>
-              <span class="info-row__value">{{ processInfo()?.pid }}</span>
-            </div>
-            <div class="info-row">
-              <span class="info-row__label">CPU:</span>
-              <span class="info-row__value">{{ processInfo()?.cpu_percent }}%</span>
-            </div>
-            <div class="info-row">
-              <span class="info-row__label">Memory:</span>
-              <span class="info-row__value">{{ processInfo()?.memory_mb }} MB</span>
-            </div>
-          } @else {
-            <div class="info-row__empty">Loading...</div>
-          }
-        </div>
+          <div class="info-group">
+            <h3 class="info-group__title">⚙️ Process</h3>
+            @if (processInfo()) {
+              <div class="info-row">
+                <span class="info-row__label">PID:</span>
+                <span class="info-row__value">{{ processInfo()?.pid }}</span>
+              </div>
+              <div class="info-row">
+                <span class="info-row__label">CPU:</span>
+                <span class="info-row__value">{{ processInfo()?.cpu_percent }}%</span>
+              </div>
+              <div class="info-row">
+                <span class="info-row__label">Memory:</span>
+                <span class="info-row__value">{{ processInfo()?.memory_mb }} MB</span>
+              </div>
+            } @else {
+              <div class="info-row__empty">Loading...</div>
+            }
+          </div>
 
-        <div class="info-group">
-          <h3 class="info-group__title">🧠 Memory</h3>
-          @if (memoryInfo()) {
-            <div class="info-row">
-              <span class="info-row__label">Used:</span>
-              <span class="info-row__value">{{ memoryInfo()?.used_mb }} / {{ memoryInfo()?.total_mb }} MB</span>
-            </div>
-            <div class="info-row">
-              <span class="info-row__label">Usage:</span>
-              <span class="info-row__value">{{ memoryInfo()?.percent_used }}%</span>
-            </div>
-            <div class="info-row">
-              <span class="info-row__label">Free:</span>
-              <span class="info-row__value">{{ memoryInfo()?.free_mb }} MB</span>
-            </div>
-          } @else {
-            <div class="info-row__empty">Loading...</div>
-          }
-        </div>
+          <div class="info-group">
+            <h3 class="info-group__title">🧠 Memory</h3>
+            @if (memoryInfo()) {
+              <div class="info-row">
+                <span class="info-row__label">Used:</span>
+                <span class="info-row__value">{{ memoryInfo()?.used_mb }} / {{ memoryInfo()?.total_mb }} MB</span>
+              </div>
+              <div class="info-row">
+                <span class="info-row__label">Usage:</span>
+                <span class="info-row__value">{{ memoryInfo()?.percent_used }}%</span>
+              </div>
+              <div class="info-row">
+                <span class="info-row__label">Free:</span>
+                <span class="info-row__value">{{ memoryInfo()?.free_mb }} MB</span>
+              </div>
+            } @else {
+              <div class="info-row__empty">Loading...</div>
+            }
+          </div>
 
-        <div class="info-group">
-          <h3 class="info-group__title">🌐 Network</h3>
-          @if (networkInfo()) {
-            <div class="info-row">
-              <span class="info-row__label">Port:</span>
-              <span class="info-row__value">{{ networkInfo()?.default_port }}</span>
-            </div>
-            <div class="info-row">
-              <span class="info-row__label">Bound:</span>
-              <span class="info-row__value" [class.status-ok]="networkInfo()?.is_webui_bound">{{ networkInfo()?.is_webui_bound ? 'Yes' : 'No' }}</span>
-            </div>
-            <div class="info-row">
-              <span class="info-row__label">Interfaces:</span>
-              <span class="info-row__value">{{ networkInfo()?.interfaces?.length ?? 0 }}</span>
-            </div>
-          } @else {
-            <div class="info-row__empty">Loading...</div>
-          }
-        </div>
+          <div class="info-group">
+            <h3 class="info-group__title">🌐 Network</h3>
+            @if (networkInfo()) {
+              <div class="info-row">
+                <span class="info-row__label">Port:</span>
+                <span class="info-row__value">{{ networkInfo()?.default_port }}</span>
+              </div>
+              <div class="info-row">
+                <span class="info-row__label">Bound:</span>
+                <span class="info-row__value" [class.status-ok]="networkInfo()?.is_webui_bound">{{ networkInfo()?.is_webui_bound ? 'Yes' : 'No' }}</span>
+              </div>
+              <div class="info-row">
+                <span class="info-row__label">Interfaces:</span>
+                <span class="info-row__value">{{ networkInfo()?.interfaces?.length ?? 0 }}</span>
+              </div>
+            } @else {
+              <div class="info-row__empty">Loading...</div>
+            }
+          </div>
 
-        <div class="info-group">
-          <h3 class="info-group__title">📊 Performance</h3>
-          @if (performance()) {
+          <div class="info-group">
+            <h3 class="info-group__title">📊 Performance</h3>
+            @if (performance()) {
+              <div class="info-row">
+                <span class="info-row__label">DOM Nodes:</span>
+                <span class="info-row__value">{{ performance()?.dom_nodes }}</span>
+              </div>
+              <div class="info-row">
+                <span class="info-row__label">Heap Used:</span>
+                <span class="info-row__value">{{ performance()?.js_heap_used_mb }} MB</span>
+              </div>
+              <div class="info-row">
+                <span class="info-row__label">Windows:</span>
+                <span class="info-row__value">{{ performance()?.open_windows }}</span>
+              </div>
+            } @else {
+              <div class="info-row__empty">Loading...</div>
+            }
+          </div>
+
+          <div class="info-group">
+            <h3 class="info-group__title">📜 Events</h3>
             <div class="info-row">
-              <span class="info-row__label">DOM Nodes:</span>
-              <span class="info-row__value">{{ performance()?.dom_nodes }}</span>
+              <span class="info-row__label">Total:</span>
+              <span class="info-row__value">{{ events().length }}</span>
             </div>
             <div class="info-row">
-              <span class="info-row__label">Heap Used:</span>
-              <span class="info-row__value">{{ performance()?.js_heap_used_mb }} MB</span>
+              <span class="info-row__label">Errors:</span>
+              <span class="info-row__value" [class.status-error]="errorCount() > 0">{{ errorCount() }}</span>
             </div>
             <div class="info-row">
-              <span class="info-row__label">Windows:</span>
-              <span class="info-row__value">{{ performance()?.open_windows }}</span>
+              <span class="info-row__label">Warnings:</span>
+              <span class="info-row__value" [class.status-warn]="warnCount() > 0">{{ warnCount() }}</span>
             </div>
-          } @else {
-            <div class="info-row__empty">Loading...</div>
-          }
-        </div>
-
-        <div class="info-group">
-          <h3 class="info-group__title">📜 Events</h3>
-          <div class="info-row">
-            <span class="info-row__label">Total:</span>
-            <span class="info-row__value">{{ events().length }}</span>
-          </div>
-          <div class="info-row">
-            <span class="info-row__label">Errors:</span>
-            <span class="info-row__value" [class.status-error]="errorCount() > 0">{{ errorCount() }}</span>
-          </div>
-          <div class="info-row">
-            <span class="info-row__label">Warnings:</span>
-            <span class="info-row__value" [class.status-warn]="warnCount() > 0">{{ warnCount() }}</span>
           </div>
         </div>
       </div>
-    </div>
-  `,
-  styles: [`
-    .devtools-panel { padding: 8px; }
-    .panel-grid {
-      display: grid;
-      grid-template-columns: repeat(auto-fit, minmax(250px, 1fr));
-      gap: 16px;
-    }
-    .info-group {
-      background: #252526;
-      border-radius: 6px;
-      padding: 12px;
-    }
-    .info-group__title {
-      margin: 0 0 12px;
-      font-size: 13px;
-      font-weight: 600;
-      color: #fff;
-    }
-    .info-row {
-      display: flex;
-      justify-content: space-between;
-      padding: 6px 0;
-      border-bottom: 1px solid #3c3c3c;
-      font-size: 11px;
-    }
-    .info-row:last-child { border-bottom: none; }
-    .info-row__label { color: #888; }
-    .info-row__value { color: #d4d4d4; font-weight: 500; }
-    .info-row__empty { color: #666; font-style: italic; }
-    .status-ok { color: #4ade80; }
-    .status-warn { color: #fbbf24; }
-    .status-error { color: #ef4444; }
-  `],
-})
-export class DevToolsOverviewComponent implements OnInit {
-  private readonly devToolsService = inject(DevToolsService);
+    `,
+    styles: [`
+      .devtools-panel { padding: 8px; }
+      .panel-grid {
+        display: grid;
+        grid-template-columns: repeat(auto-fit, minmax(250px, 1fr));
+        gap: 16px;
+      }
+      .info-group {
+        background: #252526;
+        border-radius: 6px;
+        padding: 12px;
+      }
+      .info-group__title {
+        margin: 0 0 12px;
+        font-size: 13px;
+        font-weight: 600;
+        color: #fff;
+      }
+      .info-row {
+        display: flex;
+        justify-content: space-between;
+        padding: 6px 0;
+        border-bottom: 1px solid #3c3c3c;
+        font-size: 11px;
+      }
+      .info-row:last-child { border-bottom: none; }
+      .info-row__label { color: #888; }
+      .info-row__value { color: #d4d4d4; font-weight: 500; }
+      .info-row__empty { color: #666; font-style: italic; }
+      .status-ok { color: #4ade80; }
+      .status-warn { color: #fbbf24; }
+      .status-error { color: #ef4444; }
+    `],
+  })
+  export class DevToolsOverviewComponent implements OnInit {
+    private readonly devToolsService = inject(DevToolsService);
 
-  readonly systemInfo = computed(() => this.devToolsService.systemInfo());
-  readonly memoryInfo = computed(() => this.devToolsService.memoryInfo());
-  readonly processInfo = computed(() => this.devToolsService.processInfo());
-  readonly networkInfo = computed(() => this.devToolsService.networkInfo());
-  readonly performance = computed(() => this.devToolsService.performance());
-  readonly events = computed(() => this.devToolsService.events());
+    readonly systemInfo = computed(() => this.devToolsService.systemInfo());
+    readonly memoryInfo = computed(() => this.devToolsService.memoryInfo());
+    readonly processInfo = computed(() => this.devToolsService.processInfo());
+    readonly networkInfo = computed(() => this.devToolsService.networkInfo());
+    readonly performance = computed(() => this.devToolsService.performance());
+    readonly events = computed(() => this.devToolsService.events());
 
-  readonly errorCount = computed(() => this.events().filter(e => e.type === 'error').length);
-  readonly warnCount = computed(() => this.events().filter(e => e.type === 'warn').length);
+    readonly errorCount = computed(() => this.events().filter(e => e.type === 'error').length);
+    readonly warnCount = computed(() => this.events().filter(e => e.type === 'warn').length);
 
-  ngOnInit(): void {
-    this.devToolsService.gatherAllData();
+    ngOnInit(): void {
+      this.devToolsService.gatherAllData();
+    }
   }
-}
 
-// ==================== System Component ====================
-@Component({
-  selector: 'app-devtools-system',
-  standalone: true,
-  imports: [CommonModule],
-  template: `
-    <div class="devtools-panel">
-      <h3 class="panel-title">💻 System Information</h3>
-      @if (systemInfo()) {
-        <table class="info-table">
-          <tr>
-            <td class="info-table__label">Hostname</td>
-            <td class="info-table__value">{{ systemInfo()?.hostname }}</td>
-          </tr>
-          <tr>
-            <td class="info-table__label">Username</td>
-            <td class="info-table__value">{{ systemInfo()?.username }}</td>
-          </tr>
-          <tr>
-            <td class="info-table__label">Operating System</td>
-            <td class="info-table__value">{{ systemInfo()?.os }}</td>
-          </tr>
-          <tr>
-            <td class="info-table__label">Architecture</td>
-            <td class="info-table__value">{{ systemInfo()?.arch }}</td>
-          </tr>
-          <tr>
-            <td class="info-table__label">CPU Cores</td>
-            <td class="info-table__value">{{ systemInfo()?.cpu_count }}</td>
-          </tr>
-          <tr>
-            <td class="info-table__label">Rust Version</td>
-            <td class="info-table__value">{{ systemInfo()?.rust_version }}</td>
-          </tr>
-          <tr>
-            <td class="info-table__label">App Version</td>
-            <td class="info-table__value">{{ systemInfo()?.app_version }}</td>
-          </tr>
-          <tr>
-            <td class="info-table__label">Build Time</td>
-            <td class="info-table__value">{{ systemInfo()?.build_time }}</td>
-          </tr>
-        </table>
-      } @else {
-        <div class="loading">Loading system information...</div>
+  // ==================== System Component ====================
+  @Component({
+    selector: 'app-devtools-system',
+    standalone: true,
+    imports: [CommonModule],
+    template: `
+      <div class="devtools-panel">
+        <h3 class="panel-title">💻 System Information</h3>
+        @if (systemInfo()) {
+          <table class="info-table">
+            <tr>
+              <td class="info-table__label">Hostname</td>
+              <td class="info-table__value">{{ systemInfo()?.hostname }}</td>
+            </tr>
+            <tr>
+              <td class="info-table__label">Username</td>
+              <td class="info-table__value">{{ systemInfo()?.username }}</td>
+            </tr>
+            <tr>
+              <td class="info-table__label">Operating System</td>
+              <td class="info-table__value">{{ systemInfo()?.os }}</td>
+            </tr>
+            <tr>
+              <td class="info-table__label">Architecture</td>
+              <td class="info-table__value">{{ systemInfo()?.arch }}</td>
+            </tr>
+            <tr>
+              <td class="info-table__label">CPU Cores</td>
+              <td class="info-table__value">{{ systemInfo()?.cpu_count }}</td>
+            </tr>
+            <tr>
+              <td class="info-table__label">Rust Version</td>
+              <td class="info-table__value">{{ systemInfo()?.rust_version }}</td>
+            </tr>
+            <tr>
+              <td class="info-table__label">App Version</td>
+              <td class="info-table__value">{{ systemInfo()?.app_version }}</td>
+            </tr>
+            <tr>
+              <td class="info-table__label">Build Time</td>
+              <td class="info-table__value">{{ systemInfo()?.build_time }}</td>
+            </tr>
+          </table>
+        } @else {
+          <div class="loading">Loading system information...</div>
       }
     </div>
   `,
@@ -620,6 +620,61 @@ export class DevToolsDatabaseComponent {
               }
             </td>
           </tr>
+          <tr>
+            <td class="info-table__label">Config File</td>
+            <td class="info-table__value">{{ configInfo()?.config_file || 'None (defaults)' }}</td>
+          </tr>
+          <tr>
+            <td class="info-table__label">Profile</td>
+            <td class="info-table__value">
+              @if (configInfo()?.profile) {
+                <span class="layer-badge layer-profile">{{ configInfo()?.profile }}</span>
+              } @else {
+                <span class="text-muted">None</span>
+              }
+              @if (configInfo()?.profiles?.length) {
+                <span class="text-muted">defined: {{ configInfo()?.profiles?.join(', ') }}</span>
+              }
+            </td>
+          </tr>
+        </table>
+
+        @if (configInfo()?.problems?.length) {
+          <div class="config-problems">
+            Running degraded: these settings were invalid and use their defaults
+            <ul>
+              @for (problem of configInfo()?.problems; track $index) {
+                <li><code>{{ problem.field || '—' }}</code> {{ problem.message }}</li>
+              }
+            </ul>
+          </div>
+        }
+
+        @if (configInfo()?.pending_restart?.length) {
+          <div class="config-problems">
+            Changed on disk; restart to apply:
+            @for (key of configInfo()?.pending_restart; track key) {
+              <code class="pending-key">{{ key }}</code>
+            }
+          </div>
+        }
+
+        <h3 class="panel-title panel-title--spaced">Effective Values</h3>
+        <table class="info-table">
+          @for (entry of configInfo()?.values; track entry.key) {
+            <tr>
+              <td class="info-table__label"><code>{{ entry.key }}</code></td>
+              <td class="info-table__value">{{ entry.value ?? '—' }}</td>
+              <td class="info-table__value">
+                <span class="layer-badge" [class]="'layer-badge layer-' + entry.source.layer" [title]="entry.source.origin || ''">
+                  {{ entry.source.layer }}
+                </span>
+                @if (entry.source.origin) {
+                  <span class="text-muted">{{ entry.source.origin }}</span>
+                }
+              </td>
+            </tr>
+          }
         </table>
       } @else {
         <div class="loading">Loading configuration...</div>
@@ -645,6 +700,15 @@ export class DevToolsDatabaseComponent {
     }
     .text-muted { color: #666; }
     .loading { color: #666; font-style: italic; }
+    .panel-title--spaced { margin-top: 24px; }
+    .layer-badge { padding: 2px 8px; border-radius: 3px; font-size: 10px; margin-right: 6px; background: #3c3c3c; }
+    .layer-file { background: #0e639c; }
+    .layer-profile { background: #059669; }
+    .layer-env { background: #7c3aed; }
+    .layer-cli { background: #fbbf24; color: #000; }
+    .config-problems { margin-top: 16px; padding: 8px; border-left: 3px solid #fbbf24; color: #fde68a; }
+    .config-problems ul { margin: 6px 0 0; padding-left: 18px; }
+    .pending-key { margin-left: 6px; }
   `],
 })
 export class DevToolsConfigComponent {
@@ -756,6 +820,145 @@ export class DevToolsPerformanceComponent {
   readonly environment = computed(() => this.devToolsService.environment());
 }
 
+// ==================== Serialization Component ====================
+@Component({
+  selector: 'app-devtools-serialization',
+  standalone: true,
+  imports: [CommonModule],
+  template: `
+    <div class="devtools-panel">
+      <h3 class="panel-title">📦 Serialization</h3>
+      @if (stats()) {
+        <div class="panel-subtitle">Selected format: <code class="code">{{ stats()?.selected }}</code></div>
+        <table class="info-table">
+          <thead>
+            <tr>
+              <th class="info-table__header">Format</th>
+              <th class="info-table__header">Encoded</th>
+              <th class="info-table__header">Decoded</th>
+              <th class="info-table__header">Bytes Sent</th>
+              <th class="info-table__header">Bytes Received</th>
+              <th class="info-table__header">Avg Encode</th>
+              <th class="info-table__header">Avg Decode</th>
+              <th class="info-table__header">Size vs JSON (benchmark)</th>
+            </tr>
+          </thead>
+          <tbody>
+            @for (format of stats()?.formats; track format.format) {
+              <tr>
+                <td class="info-table__cell">{{ format.format }}</td>
+                <td class="info-table__cell">{{ format.total_serializations }}</td>
+                <td class="info-table__cell">{{ format.total_deserializations }}</td>
+                <td class="info-table__cell">{{ format.total_bytes_sent | number }}</td>
+                <td class="info-table__cell">{{ format.total_bytes_received | number }}</td>
+                <td class="info-table__cell">{{ format.avg_serialization_time_us | number: '1.1-1' }} µs</td>
+                <td class="info-table__cell">{{ format.avg_deserialization_time_us | number: '1.1-1' }} µs</td>
+                <td class="info-table__cell">{{ format.compression_ratio | number: '1.2-2' }}x</td>
+              </tr>
+            }
+          </tbody>
+        </table>
+        <button class="btn btn--small" (click)="resetStats()">Reset Counters</button>
+      } @else {
+        <div class="loading">Loading serialization stats...</div>
+      }
+
+      <h4 class="subsection-title">Benchmark</h4>
+      <button class="btn btn--small" [disabled]="running()" (click)="runBenchmark()">
+        {{ running() ? 'Running...' : 'Run Benchmark' }}
+      </button>
+      @if (benchmark()) {
+        <table class="info-table">
+          <thead>
+            <tr>
+              <th class="info-table__header">Format</th>
+              <th class="info-table__header">Size</th>
+              <th class="info-table__header">Speed</th>
+              <th class="info-table__header">Readability</th>
+              <th class="info-table__header">Browser Support</th>
+            </tr>
+          </thead>
+          <tbody>
+            @for (row of benchmark()?.comparison; track row.format) {
+              <tr>
+                <td class="info-table__cell">{{ row.format }}</td>
+                <td class="info-table__cell">{{ row.size_ratio }}</td>
+                <td class="info-table__cell">{{ row.speed_ratio }}</td>
+                <td class="info-table__cell">{{ row.readability }}</td>
+                <td class="info-table__cell">{{ row.browser_support }}</td>
+              </tr>
+            }
+          </tbody>
+        </table>
+        <table class="info-table">
+          <thead>
+            <tr>
+              <th class="info-table__header">Payload</th>
+              <th class="info-table__header">Format</th>
+              <th class="info-table__header">Bytes</th>
+              <th class="info-table__header">Encode</th>
+              <th class="info-table__header">Decode</th>
+            </tr>
+          </thead>
+          <tbody>
+            @for (result of benchmark()?.results; track result.payload + result.format) {
+              <tr>
+                <td class="info-table__cell"><code class="code">{{ result.payload }}</code></td>
+                <td class="info-table__cell">{{ result.format }}</td>
+                <td class="info-table__cell">{{ result.bytes | number }} ({{ result.size_ratio | number: '1.2-2' }}x)</td>
+                <td class="info-table__cell">{{ result.avg_serialization_time_us | number: '1.1-1' }} µs</td>
+                <td class="info-table__cell">{{ result.avg_deserialization_time_us | number: '1.1-1' }} µs</td>
+              </tr>
+            }
+          </tbody>
+        </table>
+        <div class="text-muted">{{ benchmark()?.iterations }} iterations per payload and format</div>
+      }
+    </div>
+  `,
+  styles: [`
+    .devtools-panel { padding: 8px; }
+    .panel-title { margin: 0 0 16px; font-size: 14px; color: #fff; }
+    .panel-subtitle { margin-bottom: 12px; color: #888; }
+    .subsection-title { margin: 20px 0 12px; font-size: 12px; color: #888; text-transform: uppercase; }
+    .info-table { width: 100%; border-collapse: collapse; margin: 12px 0; }
+    .info-table__header { padding: 10px; text-align: left; color: #888; font-weight: 500; border-bottom: 2px solid #3c3c3c; }
+    .info-table__cell { padding: 10px; border-bottom: 1px solid #3c3c3c; color: #d4d4d4; }
+    .code {
+      background: #1e1e1e;
+      padding: 2px 6px;
+      border-radius: 3px;
+      font-family: 'Consolas', 'Monaco', monospace;
+      font-size: 11px;
+      color: #9cdcfe;
+    }
+    .btn { background: #0e639c; color: #fff; border: none; padding: 6px 12px; border-radius: 4px; cursor: pointer; }
+    .btn--small { padding: 4px 8px; font-size: 11px; }
+    .btn:disabled { opacity: 0.5; cursor: default; }
+    .text-muted { color: #666; }
+    .loading { color: #666; font-style: italic; }
+  `],
+})
+export class DevToolsSerializationComponent {
+  private readonly devToolsService = inject(DevToolsService);
+  readonly stats = computed(() => this.devToolsService.serializationStats());
+  readonly benchmark = computed(() => this.devToolsService.serializationBenchmark());
+  readonly running = signal(false);
+
+  async runBenchmark(): Promise<void> {
+    this.running.set(true);
+    try {
+      await this.devToolsService.runSerializationBenchmark();
+    } finally {
+      this.running.set(false);
+    }
+  }
+
+  resetStats(): void {
+    this.devToolsService.resetSerializationStats();
+  }
+}
+
 // ==================== Events Component ====================
 @Component({
   selector: 'app-devtools-events',
@@ -775,8 +978,29 @@ export class DevToolsPerformanceComponent {
         <button class="filter-btn" [class.active]="filter() === 'error'" (click)="filter.set('error')">Errors</button>
         <button class="filter-btn" [class.active]="filter() === 'warn'" (click)="filter.set('warn')">Warnings</button>
         <button class="filter-btn" [class.active]="filter() === 'info'" (click)="filter.set('info')">Info</button>
+        <button class="filter-btn" [class.active]="filter() === 'catalogue'" (click)="showCatalogue()">Catalogue</button>
       </div>
 
+      @if (filter() === 'catalogue') {
+        <div class="events-list">
+          @for (entry of catalogue(); track entry.event_type) {
+            <div class="event-item">
+              <div class="event-item__header">
+                <span class="event-item__type event-type--system">{{ entry.event_type }}</span>
+                <span class="event-item__source">{{ entry.kind === 'rust_type' ? entry.rust_type : 'JSON schema' }}</span>
+              </div>
+              @if (entry.description) {
+                <div class="event-item__message">{{ entry.description }}</div>
+              }
+              @if (entry.schema) {
+                <pre class="event-item__data">{{ entry.schema | json }}</pre>
+              }
+            </div>
+          } @empty {
+            <div class="events-empty">No event schemas registered</div>
+          }
+        </div>
+      } @else {
       <div class="events-list">
         @for (event of filteredEvents(); track event.id) {
           <div class="event-item" [class.event-item--error]="event.type === 'error'" [class.event-item--warn]="event.type === 'warn'">
@@ -794,6 +1018,7 @@ export class DevToolsPerformanceComponent {
           <div class="events-empty">No events to display</div>
         }
       </div>
+      }
     </div>
   `,
   styles: [`
@@ -849,18 +1074,24 @@ export class DevToolsPerformanceComponent {
 export class DevToolsEventsComponent {
   private readonly devToolsService = inject(DevToolsService);
   readonly events = computed(() => this.devToolsService.events());
-  readonly filter = signal<'all' | 'error' | 'warn' | 'info'>('all');
+  readonly catalogue = computed(() => this.devToolsService.eventCatalogue());
+  readonly filter = signal<'all' | 'error' | 'warn' | 'info' | 'catalogue'>('all');
 
   readonly filteredEvents = computed(() => {
     const allEvents = this.events();
     const f = this.filter();
-    if (f === 'all') return allEvents;
+    if (f === 'all' || f === 'catalogue') return allEvents;
     return allEvents.filter(e => e.type === f);
   });
 
   clearEvents(): void {
     this.devToolsService.clearEvents();
   }
+
+  showCatalogue(): void {
+    this.filter.set('catalogue');
+    void this.devToolsService.loadEventCatalogue();
+  }
 }
 
 // ==================== Bindings Component ====================
@@ -897,6 +1128,53 @@ export class DevToolsEventsComponent {
           }
         </tbody>
       </table>
+
+      <h3 class="panel-title panel-title--spaced">💉 DI Services</h3>
+      @if (diValidation(); as validation) {
+        @if (!validation.valid) {
+          <div class="di-error">
+            {{ validation.error?.message }}
+            @if (validation.error?.details) {
+              <code class="code">{{ validation.error?.details }}</code>
+            }
+          </div>
+        }
+      }
+      <table class="info-table">
+        <thead>
+          <tr>
+            <th class="info-table__header">Service</th>
+            <th class="info-table__header">Lifetime</th>
+            <th class="info-table__header">Instantiated</th>
+            <th class="info-table__header">Depends On</th>
+          </tr>
+        </thead>
+        <tbody>
+          @for (service of diServices(); track service.type_name + (service.name ?? '')) {
+            <tr>
+              <td class="info-table__cell">
+                <code class="code">{{ service.type_name }}{{ service.name ? '["' + service.name + '"]' : '' }}</code>
+                @if (service.depth > 0) { <span class="scope-tag">root</span> }
+              </td>
+              <td class="info-table__cell">{{ service.lifetime }}</td>
+              <td class="info-table__cell">
+                <span class="status-badge" [class.status-ok]="service.instantiated">
+                  {{ service.instantiated ? 'Yes' : 'No' }}
+                </span>
+              </td>
+              <td class="info-table__cell">
+                @for (dependency of service.dependencies; track dependency) {
+                  <code class="code">{{ dependency }}</code>
+                } @empty { — }
+              </td>
+            </tr>
+          } @empty {
+            <tr>
+              <td class="info-table__cell" colspan="4">No services registered</td>
+            </tr>
+          }
+        </tbody>
+      </table>
     </div>
   `,
   styles: [`
@@ -916,11 +1194,20 @@ export class DevToolsEventsComponent {
     .status-badge { padding: 2px 8px; border-radius: 3px; font-size: 10px; background: #3c3c3c; }
     .status-ok { background: #4ade80; color: #000; }
     .status-error { background: #ef4444; color: #fff; }
+    .panel-title--spaced { margin-top: 24px; }
+    .scope-tag { margin-left: 6px; font-size: 10px; color: #888; }
+    .di-error { margin-bottom: 12px; padding: 8px; border-left: 3px solid #ef4444; color: #fca5a5; }
   `],
 })
-export class DevToolsBindingsComponent {
+export class DevToolsBindingsComponent implements OnInit {
   private readonly devToolsService = inject(DevToolsService);
   readonly bindings = computed(() => this.devToolsService.bindings());
+  readonly diServices = computed(() => this.devToolsService.diServices());
+  readonly diValidation = computed(() => this.devToolsService.diValidation());
+
+  ngOnInit(): void {
+    void this.devToolsService.loadDiServices();
+  }
 }
 
 // ==================== Windows Component ====================
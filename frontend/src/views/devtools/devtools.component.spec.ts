@@ -19,7 +19,13 @@ const mockDevToolsService = {
   configInfo: jest.fn().mockReturnValue(null),
   performance: jest.fn().mockReturnValue(null),
   environment: jest.fn().mockReturnValue(null),
+  serializationStats: jest.fn().mockReturnValue(null),
+  serializationBenchmark: jest.fn().mockReturnValue(null),
   events: jest.fn().mockReturnValue([]),
+  eventCatalogue: jest.fn().mockReturnValue([]),
+  diServices: jest.fn().mockReturnValue([]),
+  diValidation: jest.fn().mockReturnValue(null),
+  loadDiServices: jest.fn(),
   bindings: jest.fn().mockReturnValue([]),
   windows: jest.fn().mockReturnValue([]),
   logs: jest.fn().mockReturnValue([]),
@@ -54,8 +60,8 @@ describe('DevToolsComponent', () => {
     expect(component.tabs.length).toBeGreaterThan(0);
   });
 
-  test('should have 12 tabs', () => {
-    expect(component.tabs.length).toBe(12);
+  test('should have 13 tabs', () => {
+    expect(component.tabs.length).toBe(13);
   });
 
   test('should have overview as initial active tab', () => {
@@ -11,6 +11,7 @@ import {
   DevToolsDatabaseComponent,
   DevToolsConfigComponent,
   DevToolsPerformanceComponent,
+  DevToolsSerializationComponent,
   DevToolsEventsComponent,
   DevToolsBindingsComponent,
   DevToolsWindowsComponent,
@@ -30,6 +31,7 @@ import {
     DevToolsDatabaseComponent,
     DevToolsConfigComponent,
     DevToolsPerformanceComponent,
+    DevToolsSerializationComponent,
     DevToolsEventsComponent,
     DevToolsBindingsComponent,
     DevToolsWindowsComponent,
@@ -99,6 +101,9 @@ import {
           @case ('performance') {
             <app-devtools-performance></app-devtools-performance>
           }
+          @case ('serialization') {
+            <app-devtools-serialization></app-devtools-serialization>
+          }
           @case ('events') {
             <app-devtools-events></app-devtools-events>
           }
@@ -304,6 +309,7 @@ export class DevToolsComponent implements OnInit, OnDestroy {
     { id: 'database', label: 'Database', icon: '🗄️' },
     { id: 'config', label: 'Config', icon: '📋' },
     { id: 'performance', label: 'Performance', icon: '📈' },
+    { id: 'serialization', label: 'Serialization', icon: '📦' },
     { id: 'events', label: 'Events', icon: '📜' },
     { id: 'bindings', label: 'Bindings', icon: '🔗' },
     { id: 'windows', label: 'Windows', icon: '🪟' },
@@ -406,6 +406,8 @@ export class ErrorModalComponent {
       case ErrorCode.DeserializationFailed:
       case ErrorCode.InvalidFormat:
         return '📦';
+      case ErrorCode.Timeout:
+        return '⏱️';
       case ErrorCode.InternalError:
       case ErrorCode.LockPoisoned:
         return '🔧';
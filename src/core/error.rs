@@ -44,6 +44,7 @@ pub enum ErrorCode {
     
     // System errors (6000-6999)
     LockPoisoned = 6000,
+    Timeout = 6001,
     InternalError = 6999,
     
     // Custom/unknown
@@ -71,12 +72,40 @@ impl fmt::Display for ErrorCode {
             ErrorCode::UserNotFound => write!(f, "USER_NOT_FOUND"),
             ErrorCode::EntityNotFound => write!(f, "ENTITY_NOT_FOUND"),
             ErrorCode::LockPoisoned => write!(f, "LOCK_POISONED"),
+            ErrorCode::Timeout => write!(f, "TIMEOUT"),
             ErrorCode::InternalError => write!(f, "INTERNAL_ERROR"),
             ErrorCode::Unknown => write!(f, "UNKNOWN"),
         }
     }
 }
 
+impl ErrorCode {
+    /// HTTP status code used when the error crosses the REST transport
+    pub fn http_status(&self) -> u16 {
+        match self {
+            ErrorCode::DbConnectionFailed => 503,
+            ErrorCode::DbConstraintViolation | ErrorCode::DbAlreadyExists => 409,
+            ErrorCode::DbNotFound
+            | ErrorCode::ResourceNotFound
+            | ErrorCode::UserNotFound
+            | ErrorCode::EntityNotFound => 404,
+            ErrorCode::DeserializationFailed
+            | ErrorCode::InvalidFormat
+            | ErrorCode::MissingRequiredField => 400,
+            ErrorCode::ValidationFailed | ErrorCode::InvalidFieldValue => 422,
+            ErrorCode::Timeout => 504,
+            ErrorCode::DbQueryFailed
+            | ErrorCode::ConfigNotFound
+            | ErrorCode::ConfigInvalid
+            | ErrorCode::ConfigMissingField
+            | ErrorCode::SerializationFailed
+            | ErrorCode::LockPoisoned
+            | ErrorCode::InternalError
+            | ErrorCode::Unknown => 500,
+        }
+    }
+}
+
 /// Structured error value with metadata for cross-boundary communication
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorValue {
@@ -365,6 +394,16 @@ mod tests {
         assert_eq!(format!("{}", ErrorCode::InternalError), "INTERNAL_ERROR");
     }
 
+    #[test]
+    fn test_error_code_http_status() {
+        assert_eq!(ErrorCode::DbNotFound.http_status(), 404);
+        assert_eq!(ErrorCode::DbAlreadyExists.http_status(), 409);
+        assert_eq!(ErrorCode::DeserializationFailed.http_status(), 400);
+        assert_eq!(ErrorCode::InvalidFieldValue.http_status(), 422);
+        assert_eq!(ErrorCode::LockPoisoned.http_status(), 500);
+        assert_eq!(ErrorCode::Timeout.http_status(), 504);
+    }
+
     #[test]
     fn test_app_error_to_json() {
         let error = AppError::Database(
@@ -0,0 +1,284 @@
+// src/core/infrastructure/config/format.rs
+// Config file formats: TOML, YAML, JSON and INI, read into one JSON tree
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use super::layers::{coerce, set_key};
+use super::AppConfig;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+
+/// File names looked for in each config directory, in this order
+pub const CONFIG_FILE_NAMES: [&str; 5] = [
+    "app.config.toml",
+    "app.config.yaml",
+    "app.config.yml",
+    "app.config.json",
+    "app.config.ini",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+    Json,
+    Ini,
+}
+
+impl ConfigFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            "ini" => Some(Self::Ini),
+            _ => None,
+        }
+    }
+
+    /// By extension; unknown extensions are read as TOML
+    pub fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_name)
+            .unwrap_or(Self::Toml)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Toml => "toml",
+            Self::Yaml => "yaml",
+            Self::Json => "json",
+            Self::Ini => "ini",
+        }
+    }
+
+    /// Parse a file into a tree of sections
+    ///
+    /// INI values are strings; each is parsed into the type of its default, like
+    /// an environment variable.
+    pub fn parse(&self, content: &str, path: &Path) -> AppResult<Value> {
+        let parsed = match self {
+            Self::Toml => toml::from_str::<toml::Value>(content)
+                .map_err(|e| e.to_string())
+                .and_then(|table| serde_json::to_value(table).map_err(|e| e.to_string())),
+            Self::Yaml if content.trim().is_empty() => Ok(Value::Object(Map::new())),
+            Self::Yaml => serde_yaml::from_str::<Value>(content).map_err(|e| e.to_string()),
+            Self::Json => serde_json::from_str::<Value>(content).map_err(|e| e.to_string()),
+            Self::Ini => return parse_ini(content, path),
+        };
+        match parsed {
+            Ok(tree) if tree.is_object() => Ok(tree),
+            Ok(_) => Err(parse_error(path, self, "Expected a table of sections".to_string())),
+            Err(cause) => Err(parse_error(path, self, cause)),
+        }
+    }
+
+    /// Write a tree in this format; unset values are left out
+    pub fn render(&self, tree: &Value) -> AppResult<String> {
+        let tree = without_nulls(tree);
+        let rendered = match self {
+            Self::Toml => toml::to_string_pretty(&tree).map_err(|e| e.to_string()),
+            Self::Yaml => serde_yaml::to_string(&tree).map_err(|e| e.to_string()),
+            Self::Json => serde_json::to_string_pretty(&tree)
+                .map(|json| json + "\n")
+                .map_err(|e| e.to_string()),
+            Self::Ini => Ok(render_ini(&tree)),
+        };
+        rendered.map_err(|cause| {
+            AppError::Serialization(
+                ErrorValue::new(ErrorCode::SerializationFailed, format!("Failed to write {} config", self.name()))
+                    .with_cause(cause),
+            )
+        })
+    }
+}
+
+fn parse_error(path: &Path, format: &ConfigFormat, cause: String) -> AppError {
+    AppError::Configuration(
+        ErrorValue::new(ErrorCode::ConfigInvalid, "Failed to parse config file")
+            .with_cause(cause)
+            .with_context("path", path.display().to_string())
+            .with_context("format", format.name()),
+    )
+}
+
+fn parse_ini(content: &str, path: &Path) -> AppResult<Value> {
+    let sections = ini::configparser::ini::Ini::new_cs()
+        .read(content.to_string())
+        .map_err(|cause| parse_error(path, &ConfigFormat::Ini, cause))?;
+    let defaults = serde_json::to_value(AppConfig::default())?;
+    let origin = path.display().to_string();
+
+    let mut tree = Value::Object(Map::new());
+    for (section, values) in sections {
+        for (name, raw) in values {
+            let key = format!("{}.{}", section, name);
+            let raw = raw.unwrap_or_default();
+            let raw = raw
+                .strip_prefix('"')
+                .and_then(|r| r.strip_suffix('"'))
+                .unwrap_or(&raw);
+            // `[profile.dev.logging]` holds logging keys, typed like the base ones
+            let setting = key
+                .strip_prefix("profile.")
+                .and_then(|rest| rest.split_once('.'))
+                .map_or(key.as_str(), |(_, setting)| setting);
+            let value = coerce(&defaults, setting, raw, &origin).map_err(|e| match e {
+                AppError::Configuration(value) => AppError::Configuration(value.with_field(key.clone())),
+                other => other,
+            })?;
+            set_key(&mut tree, &key, value);
+        }
+    }
+    Ok(tree)
+}
+
+/// One `[section]` per table of values; nested tables such as profiles become
+/// dotted sections like `[profile.dev.logging]`
+fn render_ini(tree: &Value) -> String {
+    fn section(name: &str, table: &Map<String, Value>, out: &mut String) {
+        let values: Vec<(&String, &Value)> = table.iter().filter(|(_, v)| !v.is_object()).collect();
+        if !values.is_empty() && !name.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("[{}]\n", name));
+            for (key, value) in values {
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                out.push_str(&format!("{} = {}\n", key, value));
+            }
+        }
+        for (key, child) in table {
+            if let Value::Object(child) = child {
+                let name = if name.is_empty() { key.clone() } else { format!("{}.{}", name, key) };
+                section(&name, child, out);
+            }
+        }
+    }
+    let mut out = String::new();
+    if let Value::Object(table) = tree {
+        section("", table, &mut out);
+    }
+    out
+}
+
+fn without_nulls(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| (k.clone(), without_nulls(v)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Translate a config file into another format, picked by the output's extension
+/// unless `format` is given; comments are not carried over
+pub fn convert(input: &Path, output: &Path, format: Option<ConfigFormat>) -> AppResult<ConfigFormat> {
+    let content = fs::read_to_string(input).map_err(|e| {
+        AppError::Configuration(
+            ErrorValue::new(ErrorCode::ConfigNotFound, "Failed to read config file")
+                .with_cause(e.to_string())
+                .with_context("path", input.display().to_string()),
+        )
+    })?;
+    let tree = ConfigFormat::from_path(input).parse(&content, input)?;
+    let format = format.unwrap_or_else(|| ConfigFormat::from_path(output));
+    fs::write(output, format.render(&tree)?)?;
+    Ok(format)
+}
+
+/// `config convert <input> <output> [--to <format>]`; returns the exit code
+pub fn run_command(args: &[String]) -> i32 {
+    const USAGE: &str = "Usage: config convert <input> <output> [--to toml|yaml|json|ini]";
+    let mut paths = Vec::new();
+    let mut format = None;
+    let mut args = args.iter();
+    match args.next().map(String::as_str) {
+        Some("convert") => {}
+        _ => {
+            eprintln!("{}", USAGE);
+            return 2;
+        }
+    }
+    while let Some(arg) = args.next() {
+        if arg == "--to" {
+            match args.next().and_then(|name| ConfigFormat::from_name(name)) {
+                Some(f) => format = Some(f),
+                None => {
+                    eprintln!("Unknown format; {}", USAGE);
+                    return 2;
+                }
+            }
+        } else {
+            paths.push(arg);
+        }
+    }
+    let [input, output] = paths.as_slice() else {
+        eprintln!("{}", USAGE);
+        return 2;
+    };
+    match convert(Path::new(input), Path::new(output), format) {
+        Ok(format) => {
+            println!("Converted {} to {} ({})", input, output, format.name());
+            0
+        }
+        Err(e) => {
+            eprintln!("Failed to convert {}: {}", input, e);
+            if let Some(cause) = &e.to_value().cause {
+                eprintln!("  {}", cause);
+            }
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats_read_the_same_settings() {
+        let toml = "[window]\nwidth = 900\nresizable = false\n\n[app]\nversion = \"2.0\"\n";
+        let yaml = "window:\n  width: 900\n  resizable: false\napp:\n  version: \"2.0\"\n";
+        let json = r#"{"window": {"width": 900, "resizable": false}, "app": {"version": "2.0"}}"#;
+        let ini = "[window]\nwidth = 900\nresizable = false\n\n[app]\nversion = 2.0\n";
+
+        let expected = ConfigFormat::Toml.parse(toml, Path::new("a.toml")).unwrap();
+        assert_eq!(ConfigFormat::Yaml.parse(yaml, Path::new("a.yaml")).unwrap(), expected);
+        assert_eq!(ConfigFormat::Json.parse(json, Path::new("a.json")).unwrap(), expected);
+        assert_eq!(ConfigFormat::Ini.parse(ini, Path::new("a.ini")).unwrap(), expected);
+
+        let profile = ConfigFormat::Ini
+            .parse("[profile.test.database]\ncreate_sample_data = false\n", Path::new("a.ini"))
+            .unwrap();
+        assert_eq!(profile["profile"]["test"]["database"]["create_sample_data"], Value::Bool(false));
+
+        let err = ConfigFormat::Ini.parse("[window]\nwidth = wide\n", Path::new("a.ini")).unwrap_err();
+        assert_eq!(err.to_value().field.as_deref(), Some("window.width"));
+    }
+
+    #[test]
+    fn test_convert_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("app.config.toml");
+        fs::write(&input, "[logging]\nlevel = \"debug\"\nappend = false\n").unwrap();
+        let expected = ConfigFormat::Toml.parse(&fs::read_to_string(&input).unwrap(), &input).unwrap();
+
+        for name in ["app.config.yaml", "app.config.json", "app.config.ini"] {
+            let output = dir.path().join(name);
+            convert(&input, &output, None).unwrap();
+            let content = fs::read_to_string(&output).unwrap();
+            assert_eq!(ConfigFormat::from_path(&output).parse(&content, &output).unwrap(), expected, "{}", name);
+        }
+        assert_eq!(run_command(&["convert".to_string(), "only-one".to_string()]), 2);
+    }
+}
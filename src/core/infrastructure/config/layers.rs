@@ -0,0 +1,690 @@
+// src/core/infrastructure/config/layers.rs
+// Layered configuration: defaults, then the config file, then APP__SECTION__KEY
+// environment variables, then command-line flags
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::format::{ConfigFormat, CONFIG_FILE_NAMES};
+use super::validation::{check_range, check_types, combine, ValidationPolicy};
+use super::AppConfig;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+
+/// Prefix of override variables: `APP__LOGGING__LEVEL=debug` sets `logging.level`
+pub const ENV_PREFIX: &str = "APP__";
+
+/// Selects a `[profile.<name>]` section when `--profile` is not given
+pub const PROFILE_VAR: &str = "APP_PROFILE";
+
+/// Directories searched in order when neither `--config` nor `APP_CONFIG` names
+/// a file; the first holding an `app.config.*` file wins
+const CONFIG_DIRS: [&str; 2] = [".", "config"];
+
+/// Shorthand flags and the keys they set; `--set section.key=value` sets any key
+const CLI_FLAGS: [(&str, &str); 5] = [
+    ("--log-level", "logging.level"),
+    ("--db", "database.path"),
+    ("--transport", "communication.transport"),
+    ("--serialization", "communication.serialization"),
+    ("--port", "communication.http_port"),
+];
+
+/// Where an effective value came from, lowest precedence first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigLayer {
+    Default,
+    File,
+    /// The active `[profile.<name>]` section of the file
+    Profile,
+    Env,
+    Cli,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueSource {
+    pub layer: ConfigLayer,
+    /// The file path, variable name or flag that set the value
+    pub origin: Option<String>,
+}
+
+/// What the loader used, kept on the loaded `AppConfig`
+#[derive(Debug, Clone, Default)]
+pub struct Provenance {
+    pub file: Option<PathBuf>,
+    /// The profile merged over the file, from `--profile` or `APP_PROFILE`
+    pub profile: Option<String>,
+    /// Profiles the file defines
+    pub profiles: Vec<String>,
+    /// Keys such as `logging.level` set by a layer above the defaults
+    pub values: BTreeMap<String, ValueSource>,
+    /// Problems the degraded policy replaced with defaults
+    pub problems: Vec<ErrorValue>,
+    /// Keys changed by a hot reload that only take effect on the next start
+    pub pending_restart: Vec<String>,
+}
+
+/// One effective setting, for devtools
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigValue {
+    pub key: String,
+    pub value: Value,
+    pub source: ValueSource,
+}
+
+/// Builds an `AppConfig` from the layers it is given
+#[derive(Debug, Default)]
+pub struct ConfigLoader {
+    file: Option<PathBuf>,
+    /// Used instead of reading `file`, to check a file before it is written
+    contents: Option<String>,
+    search: bool,
+    env: Vec<(String, String)>,
+    args: Vec<String>,
+}
+
+impl ConfigLoader {
+    /// Defaults only; add layers with the `with_*` methods
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process's environment and arguments, and the first config file found
+    pub fn from_environment() -> Self {
+        Self::new()
+            .search_default_paths()
+            .with_env_vars(std::env::vars())
+            .with_args(std::env::args().skip(1))
+    }
+
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    /// Load `contents` as if it were the file at `path`
+    pub fn with_contents(mut self, path: impl Into<PathBuf>, contents: impl Into<String>) -> Self {
+        self.file = Some(path.into());
+        self.contents = Some(contents.into());
+        self
+    }
+
+    /// Look for `APP_CONFIG`, then `app.config.{toml,yaml,yml,json,ini}` in the
+    /// working directory and in `config/`
+    pub fn search_default_paths(mut self) -> Self {
+        self.search = true;
+        self
+    }
+
+    pub fn with_env_vars<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env = vars.into_iter().map(|(k, v)| (k.into(), v.into())).collect();
+        self
+    }
+
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Merge the layers and validate the result
+    ///
+    /// With `validation.on_invalid = "fail"` any problem fails the load with
+    /// every problem listed; with `"degraded"` each invalid value is replaced by
+    /// its default and the problems are kept in `provenance.problems`.
+    pub fn load(&self) -> AppResult<AppConfig> {
+        let defaults = serde_json::to_value(AppConfig::default())?;
+        let mut tree = defaults.clone();
+        let mut provenance = Provenance::default();
+        let mut problems = Vec::new();
+
+        let cli = parse_args(&self.args, &mut problems);
+        let profile = cli
+            .profile
+            .or_else(|| {
+                self.env
+                    .iter()
+                    .find(|(name, _)| name == PROFILE_VAR)
+                    .map(|(_, value)| value.trim().to_string())
+            })
+            .filter(|name| !name.is_empty());
+
+        let path = self.config_path(cli.file).unwrap_or_else(|e| {
+            problems.push(e);
+            None
+        });
+        if let Some(path) = path {
+            let file = match &self.contents {
+                Some(contents) if self.file.as_ref() == Some(&path) => parse_file(contents, &path),
+                _ => read_file(&path),
+            };
+            match file {
+                Ok(mut file) => {
+                    let origin = path.display().to_string();
+                    let profiles = take_profiles(&mut file);
+                    let mut layers = vec![(ConfigLayer::File, origin.clone(), file)];
+                    if let Some(overrides) = profile.as_ref().and_then(|name| profiles.get(name)) {
+                        let name = profile.as_deref().unwrap_or_default();
+                        layers.push((ConfigLayer::Profile, format!("{} [profile.{}]", origin, name), overrides.clone()));
+                    }
+                    for (layer, origin, values) in layers {
+                        for (key, value) in leaves(&values) {
+                            if let Some(problem) = check_types(&defaults, &key, &value) {
+                                problems.push(with_origin(problem, &origin));
+                                continue;
+                            }
+                            set_key(&mut tree, &key, value);
+                            provenance.values.insert(key, source(layer, &origin));
+                        }
+                    }
+                    provenance.file = Some(path);
+                    provenance.profiles = profiles.keys().cloned().collect();
+                }
+                Err(e) => problems.push(e),
+            }
+        }
+        if let Some(name) = profile {
+            if provenance.profiles.contains(&name) {
+                provenance.profile = Some(name);
+            } else {
+                let available = if provenance.profiles.is_empty() {
+                    "none".to_string()
+                } else {
+                    provenance.profiles.join(", ")
+                };
+                problems.push(AppError::Configuration(
+                    ErrorValue::new(ErrorCode::ConfigInvalid, format!("Unknown profile '{}', defined: {}", name, available))
+                        .with_context("profile", name),
+                ));
+            }
+        }
+
+        let mut env: Vec<&(String, String)> = self
+            .env
+            .iter()
+            .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+            .collect();
+        env.sort();
+        for (name, raw) in env {
+            let key = name[ENV_PREFIX.len()..]
+                .split("__")
+                .map(str::to_ascii_lowercase)
+                .collect::<Vec<_>>()
+                .join(".");
+            match coerce(&defaults, &key, raw, name) {
+                Ok(value) => {
+                    set_key(&mut tree, &key, value);
+                    provenance.values.insert(key, source(ConfigLayer::Env, name));
+                }
+                Err(e) => problems.push(e),
+            }
+        }
+
+        for (key, raw, flag) in cli.values {
+            match coerce(&defaults, &key, &raw, &flag) {
+                Ok(value) => {
+                    set_key(&mut tree, &key, value);
+                    provenance.values.insert(key, source(ConfigLayer::Cli, &flag));
+                }
+                Err(e) => problems.push(e),
+            }
+        }
+
+        let policy = get_key(&tree, "validation.on_invalid")
+            .and_then(Value::as_str)
+            .and_then(ValidationPolicy::from_name)
+            .unwrap_or_default();
+
+        let mut config = match deserialize_tree(tree.clone()) {
+            Ok(config) => config,
+            Err(e) if policy == ValidationPolicy::Degraded => {
+                // No field to blame: drop every override
+                problems.push(e);
+                tree = defaults.clone();
+                provenance.values.clear();
+                deserialize_tree(tree.clone())?
+            }
+            Err(e) => {
+                problems.push(e);
+                return Err(combine(&problems));
+            }
+        };
+        problems.extend(config.validate());
+
+        if !problems.is_empty() {
+            if policy == ValidationPolicy::Fail {
+                return Err(combine(&problems));
+            }
+            for field in problems.iter().filter_map(|p| p.to_value().field.as_deref()) {
+                set_key(&mut tree, field, get_key(&defaults, field).cloned().unwrap_or(Value::Null));
+                provenance.values.remove(field);
+            }
+            config = deserialize_tree(tree)?;
+            provenance.problems = problems.iter().map(|p| p.to_value().clone()).collect();
+        }
+
+        config.provenance = provenance;
+        Ok(config)
+    }
+
+    fn config_path(&self, cli_file: Option<PathBuf>) -> AppResult<Option<PathBuf>> {
+        if let Some(path) = cli_file.or_else(|| self.file.clone()) {
+            return Ok(Some(path));
+        }
+        if !self.search {
+            return Ok(None);
+        }
+        if let Some((_, path)) = self.env.iter().find(|(name, _)| name == "APP_CONFIG") {
+            if Path::new(path).exists() {
+                return Ok(Some(PathBuf::from(path)));
+            }
+        }
+        for dir in CONFIG_DIRS {
+            let found = find_config_files(Path::new(dir));
+            match found.as_slice() {
+                [] => continue,
+                [path] => return Ok(Some(path.clone())),
+                _ => {
+                    let names: Vec<String> = found.iter().map(|p| p.display().to_string()).collect();
+                    return Err(AppError::Configuration(
+                        ErrorValue::new(ErrorCode::ConfigInvalid, "Several config files found; keep one or pick one with --config")
+                            .with_details(names.join("\n"))
+                            .with_context("files", names.join(", ")),
+                    ));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl AppConfig {
+    /// Every effective setting with the layer that set it
+    pub fn effective_values(&self) -> Vec<ConfigValue> {
+        let Ok(tree) = serde_json::to_value(self) else {
+            return Vec::new();
+        };
+        leaves(&tree)
+            .into_iter()
+            .map(|(key, value)| ConfigValue {
+                source: self.source_of(&key),
+                key,
+                value,
+            })
+            .collect()
+    }
+
+    pub fn source_of(&self, key: &str) -> ValueSource {
+        self.provenance.values.get(key).cloned().unwrap_or(ValueSource {
+            layer: ConfigLayer::Default,
+            origin: None,
+        })
+    }
+}
+
+fn source(layer: ConfigLayer, origin: &str) -> ValueSource {
+    ValueSource {
+        layer,
+        origin: Some(origin.to_string()),
+    }
+}
+
+/// The `app.config.*` files present in `dir`, without a `./` prefix
+fn find_config_files(dir: &Path) -> Vec<PathBuf> {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| if dir == Path::new(".") { PathBuf::from(name) } else { dir.join(name) })
+        .filter(|path| path.exists())
+        .collect()
+}
+
+fn read_file(path: &Path) -> AppResult<Value> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        AppError::Configuration(
+            ErrorValue::new(ErrorCode::ConfigNotFound, "Failed to read config file")
+                .with_cause(e.to_string())
+                .with_context("path", path.display().to_string()),
+        )
+    })?;
+    parse_file(&content, path)
+}
+
+fn parse_file(content: &str, path: &Path) -> AppResult<Value> {
+    ConfigFormat::from_path(path).parse(content, path)
+}
+
+/// Remove the `profile` table from a file, keeping each `[profile.<name>]` table
+fn take_profiles(file: &mut Value) -> BTreeMap<String, Value> {
+    let Some(Value::Object(profiles)) = file.as_object_mut().and_then(|map| map.remove("profile")) else {
+        return BTreeMap::new();
+    };
+    profiles.into_iter().filter(|(_, values)| values.is_object()).collect()
+}
+
+#[derive(Debug, Default)]
+struct CliArgs {
+    file: Option<PathBuf>,
+    profile: Option<String>,
+    /// (key, value, flag) in the order given
+    values: Vec<(String, String, String)>,
+}
+
+fn parse_args(args: &[String], problems: &mut Vec<AppError>) -> CliArgs {
+    let mut cli = CliArgs::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let known = flag == "--config"
+            || flag == "--profile"
+            || flag == "--set"
+            || CLI_FLAGS.iter().any(|(name, _)| *name == flag);
+        if !known {
+            continue;
+        }
+        let Some(value) = inline.or_else(|| args.next().cloned()) else {
+            problems.push(cli_error(flag, "Missing value for command-line flag"));
+            continue;
+        };
+        match flag {
+            "--config" => cli.file = Some(PathBuf::from(value)),
+            "--profile" => cli.profile = Some(value),
+            "--set" => {
+                let Some((key, raw)) = value.split_once('=') else {
+                    problems.push(cli_error(flag, "Expected --set section.key=value"));
+                    continue;
+                };
+                cli.values.push((key.trim().to_string(), raw.to_string(), format!("--set {}", key.trim())));
+            }
+            _ => {
+                let key = CLI_FLAGS.iter().find(|(name, _)| *name == flag).map(|(_, key)| *key);
+                if let Some(key) = key {
+                    cli.values.push((key.to_string(), value, flag.to_string()));
+                }
+            }
+        }
+    }
+    cli
+}
+
+fn with_origin(problem: AppError, origin: &str) -> AppError {
+    match problem {
+        AppError::Configuration(value) => AppError::Configuration(value.with_context("origin", origin)),
+        other => other,
+    }
+}
+
+fn cli_error(flag: &str, message: &str) -> AppError {
+    AppError::Configuration(
+        ErrorValue::new(ErrorCode::ConfigInvalid, message).with_context("flag", flag),
+    )
+}
+
+/// Parse a string override into the type the default has at `key`
+///
+/// Keys without a typed default (unset optional strings, unknown keys) stay strings.
+pub(super) fn coerce(defaults: &Value, key: &str, raw: &str, origin: &str) -> AppResult<Value> {
+    let invalid = |expected: &str| {
+        AppError::Configuration(
+            ErrorValue::new(ErrorCode::ConfigInvalid, format!("Expected {} for {}", expected, key))
+                .with_field(key)
+                .with_context("origin", origin)
+                .with_context("value", raw),
+        )
+    };
+    match get_key(defaults, key) {
+        Some(Value::Bool(_)) => match raw.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Ok(Value::Bool(true)),
+            "false" | "0" | "no" | "off" => Ok(Value::Bool(false)),
+            _ => Err(invalid("a boolean")),
+        },
+        Some(Value::Number(n)) if n.is_f64() => raw
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| invalid("a number")),
+        Some(Value::Number(_)) => {
+            let value = raw
+                .trim()
+                .parse::<u64>()
+                .map(Value::from)
+                .map_err(|_| invalid("a non-negative integer"))?;
+            match check_range(defaults, key, &value) {
+                Some(problem) => Err(with_origin(problem, origin)),
+                None => Ok(value),
+            }
+        }
+        Some(Value::Array(_)) | Some(Value::Object(_)) => {
+            serde_json::from_str(raw).map_err(|_| invalid("JSON"))
+        }
+        _ => Ok(Value::String(raw.to_string())),
+    }
+}
+
+/// Deserialize the merged tree, naming the offending key on failure
+fn deserialize_tree(tree: Value) -> AppResult<AppConfig> {
+    serde_json::from_value(tree).map_err(|e| {
+        AppError::Configuration(
+            ErrorValue::new(ErrorCode::ConfigInvalid, "Invalid configuration value")
+                .with_cause(e.to_string()),
+        )
+    })
+}
+
+/// Leaf values keyed by dotted path; arrays are leaves
+pub(super) fn leaves(value: &Value) -> Vec<(String, Value)> {
+    fn walk(prefix: &str, value: &Value, out: &mut Vec<(String, Value)>) {
+        match value {
+            Value::Object(map) => {
+                for (name, child) in map {
+                    let key = if prefix.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{}.{}", prefix, name)
+                    };
+                    walk(&key, child, out);
+                }
+            }
+            leaf => out.push((prefix.to_string(), leaf.clone())),
+        }
+    }
+    let mut out = Vec::new();
+    walk("", value, &mut out);
+    out
+}
+
+pub(super) fn get_key<'a>(tree: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(tree, |node, part| node.get(part))
+}
+
+pub(super) fn set_key(tree: &mut Value, key: &str, value: Value) {
+    let mut node = tree;
+    let mut parts = key.split('.').peekable();
+    while let Some(part) = parts.next() {
+        if !node.is_object() {
+            *node = Value::Object(Map::new());
+        }
+        let Value::Object(map) = node else { return };
+        if parts.peek().is_none() {
+            map.insert(part.to_string(), value);
+            return;
+        }
+        node = map.entry(part.to_string()).or_insert_with(|| Value::Object(Map::new()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn partial_file() -> tempfile::NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+        writeln!(file, "[logging]\nlevel = \"warn\"\n\n[communication]\nhttp_port = 9000").unwrap();
+        file
+    }
+
+    #[test]
+    fn test_partial_file_merges_over_defaults() {
+        let file = partial_file();
+        let config = ConfigLoader::new().with_file(file.path()).load().unwrap();
+
+        assert_eq!(config.get_log_level(), "warn");
+        assert_eq!(config.get_http_port(), 9000);
+        assert_eq!(config.get_db_path(), "app.db");
+        assert_eq!(config.source_of("logging.level").layer, ConfigLayer::File);
+        assert_eq!(config.source_of("database.path").layer, ConfigLayer::Default);
+        assert_eq!(config.provenance.file.as_deref(), Some(file.path()));
+    }
+
+    #[test]
+    fn test_other_formats_and_discovery() {
+        let dir = tempfile::tempdir().unwrap();
+        let yaml = dir.path().join("app.config.yaml");
+        fs::write(&yaml, "logging:\n  level: warn\n").unwrap();
+        let config = ConfigLoader::new().with_file(&yaml).load().unwrap();
+        assert_eq!(config.get_log_level(), "warn");
+        assert_eq!(find_config_files(dir.path()), vec![yaml.clone()]);
+
+        let toml = dir.path().join("app.config.toml");
+        fs::write(&toml, "").unwrap();
+        assert_eq!(find_config_files(dir.path()), vec![toml, yaml]);
+    }
+
+    #[test]
+    fn test_profile_merges_over_the_file() {
+        let mut file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+        writeln!(
+            file,
+            "[logging]\nlevel = \"warn\"\n\n[profile.dev.logging]\nlevel = \"debug\"\n\n[profile.test.database]\npath = \":memory:\"\ncreate_sample_data = false"
+        )
+        .unwrap();
+        let load = |env: &[(&str, &str)], args: &[&str]| {
+            ConfigLoader::new()
+                .with_file(file.path())
+                .with_env_vars(env.iter().copied())
+                .with_args(args.iter().copied())
+                .load()
+        };
+
+        let base = load(&[], &[]).unwrap();
+        assert_eq!(base.get_log_level(), "warn");
+        assert_eq!(base.get_profile(), None);
+        assert_eq!(base.provenance.profiles, vec!["dev", "test"]);
+
+        let dev = load(&[(PROFILE_VAR, "dev")], &[]).unwrap();
+        assert_eq!(dev.get_log_level(), "debug");
+        assert_eq!(dev.get_profile(), Some("dev"));
+        assert_eq!(dev.source_of("logging.level").layer, ConfigLayer::Profile);
+
+        // --profile wins over APP_PROFILE, and env variables over the profile
+        let test = load(&[(PROFILE_VAR, "dev"), ("APP__DATABASE__PATH", "env.db")], &["--profile", "test"]).unwrap();
+        assert_eq!(test.get_log_level(), "warn");
+        assert!(!test.should_create_sample_data());
+        assert_eq!(test.get_db_path(), "env.db");
+
+        let err = load(&[], &["--profile=prod"]).unwrap_err();
+        assert!(err.to_value().details.as_ref().unwrap().contains("Unknown profile 'prod', defined: dev, test"));
+    }
+
+    #[test]
+    fn test_env_and_cli_override_in_order() {
+        let file = partial_file();
+        let config = ConfigLoader::new()
+            .with_file(file.path())
+            .with_env_vars([
+                ("APP__LOGGING__LEVEL", "debug"),
+                ("APP__EVENTS__PERSIST", "yes"),
+                ("APP__COMMUNICATION__HTTP_PORT", "9100"),
+                ("APP_CONFIG", "ignored.toml"),
+            ])
+            .with_args(["--port", "9200", "--set", "window.title=Dev", "--verbose"])
+            .load()
+            .unwrap();
+
+        assert_eq!(config.get_log_level(), "debug");
+        assert!(config.is_event_store_enabled());
+        assert_eq!(config.get_http_port(), 9200);
+        assert_eq!(config.get_window_title(), "Dev");
+        assert_eq!(
+            config.source_of("logging.level"),
+            ValueSource { layer: ConfigLayer::Env, origin: Some("APP__LOGGING__LEVEL".into()) }
+        );
+        assert_eq!(config.source_of("communication.http_port").origin.as_deref(), Some("--port"));
+
+        let values = config.effective_values();
+        let title = values.iter().find(|v| v.key == "window.title").unwrap();
+        assert_eq!(title.source.layer, ConfigLayer::Cli);
+        assert!(values.iter().any(|v| v.key == "app.name" && v.source.layer == ConfigLayer::Default));
+    }
+
+    #[test]
+    fn test_bad_overrides_name_the_key() {
+        let err = ConfigLoader::new()
+            .with_env_vars([("APP__COMMUNICATION__HTTP_PORT", "eighty")])
+            .load()
+            .unwrap_err();
+        let value = err.to_value();
+        assert_eq!(value.code, ErrorCode::ConfigInvalid);
+        assert!(value.context.as_ref().unwrap().contains_key("communication.http_port"));
+
+        let err = ConfigLoader::new().with_args(["--port", "70000"]).load().unwrap_err();
+        assert!(err.to_value().context.as_ref().unwrap().contains_key("communication.http_port"));
+
+        let err = ConfigLoader::new().with_args(["--set", "logging.level"]).load().unwrap_err();
+        assert!(err.to_value().details.as_deref().unwrap().contains("Expected --set"));
+    }
+
+    #[test]
+    fn test_degraded_policy_falls_back_per_field() {
+        let mut file = tempfile::Builder::new().suffix(".toml").tempfile().unwrap();
+        writeln!(
+            file,
+            "[window]\nwidth = \"wide\"\ntitle = \"Mine\"\n\n[logging]\nlevel = \"loud\"\n\n[validation]\non_invalid = \"degraded\""
+        )
+        .unwrap();
+
+        let config = ConfigLoader::new().with_file(file.path()).load().unwrap();
+        assert_eq!(config.get_window_size(), (1200, 800));
+        assert_eq!(config.get_window_title(), "Mine");
+        assert_eq!(config.get_log_level(), "info");
+        assert_eq!(config.source_of("logging.level").layer, ConfigLayer::Default);
+        let fields: Vec<_> = config.provenance.problems.iter().filter_map(|p| p.field.as_deref()).collect();
+        assert_eq!(fields, vec!["window.width", "logging.level"]);
+
+        // Out-of-range numbers only lose their own key
+        let config = ConfigLoader::new()
+            .with_file(file.path())
+            .with_env_vars([("APP__WINDOW__HEIGHT", "-1")])
+            .with_args(["--port", "70000"])
+            .load()
+            .unwrap();
+        assert_eq!(config.get_window_title(), "Mine");
+        assert_eq!(config.get_http_port(), 0);
+        let fields: Vec<_> = config.provenance.problems.iter().filter_map(|p| p.field.as_deref()).collect();
+        assert_eq!(fields, vec!["window.width", "window.height", "communication.http_port", "logging.level"]);
+
+        // The same file fails under the default policy
+        let strict = file.path().with_extension("strict.toml");
+        std::fs::write(&strict, std::fs::read_to_string(file.path()).unwrap().replace("degraded", "fail")).unwrap();
+        let err = ConfigLoader::new().with_file(&strict).load().unwrap_err();
+        assert_eq!(err.to_value().details.as_deref().unwrap().lines().count(), 2);
+        std::fs::remove_file(strict).unwrap();
+    }
+}
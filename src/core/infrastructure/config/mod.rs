@@ -1,14 +1,26 @@
-// infrastructure/config.rs
+// infrastructure/config/mod.rs
 // Application configuration module
 
 #![allow(dead_code)]
 
-use serde::Deserialize;
+mod format;
+mod layers;
+mod repository;
+mod validation;
+mod watcher;
+
+pub use format::run_command;
+pub use layers::{ConfigLoader, Provenance};
+pub use repository::FileConfigRepository;
+pub use validation::describe as describe_problem;
+pub use watcher::{ConfigChangedEvent, ConfigWatcher, CONFIG_CHANGED_EVENT};
+
+use serde::{Deserialize, Serialize};
 use std::env;
-use std::fs;
-use std::path::Path;
 
-#[derive(Debug, Deserialize, Clone)]
+use crate::core::error::AppResult;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
     pub app: AppSettings,
     pub executable: ExecutableSettings,
@@ -17,9 +29,16 @@ pub struct AppConfig {
     pub logging: LoggingSettings,
     pub communication: CommunicationSettings,
     pub features: FeatureSettings,
+    #[serde(default)]
+    pub events: EventSettings,
+    #[serde(default)]
+    pub validation: ValidationSettings,
+    /// Which layer set each value; filled in by `ConfigLoader`
+    #[serde(skip)]
+    pub provenance: Provenance,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppSettings {
     pub name: String,
     pub version: String,
@@ -28,18 +47,18 @@ pub struct AppSettings {
     pub website: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ExecutableSettings {
     pub name: String,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DatabaseSettings {
     pub path: String,
     pub create_sample_data: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct WindowSettings {
     pub title: String,
     pub width: Option<u32>,
@@ -49,20 +68,42 @@ pub struct WindowSettings {
     pub resizable: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LoggingSettings {
     pub level: String,
     pub file: String,
     pub append: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CommunicationSettings {
     pub transport: Option<String>,
     pub serialization: Option<String>,
+    /// Loopback port for the http_rest and websocket transports (0 picks a free port)
+    pub http_port: Option<u16>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct EventSettings {
+    /// Keep a durable event log in the app database
+    pub persist: Option<bool>,
+    /// Delete stored events older than this many days (0 keeps them forever)
+    pub retention_days: Option<u32>,
+    /// Keep at most this many stored events (0 means unlimited)
+    pub max_stored_events: Option<u64>,
+    /// Pending pushes per window subscription
+    pub queue_capacity: Option<usize>,
+    /// What a full window queue does: drop_oldest, drop_new or block
+    pub overflow: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ValidationSettings {
+    /// What an invalid configuration does at startup: fail or degraded
+    pub on_invalid: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FeatureSettings {
     pub dark_mode: Option<bool>,
     pub show_tray_icon: Option<bool>,
@@ -101,62 +142,32 @@ impl Default for AppConfig {
             communication: CommunicationSettings {
                 transport: Some(String::from("webview_ffi")),
                 serialization: Some(String::from("json")),
+                http_port: Some(0),
             },
             features: FeatureSettings {
                 dark_mode: Some(true),
                 show_tray_icon: Some(false),
             },
+            events: EventSettings {
+                persist: Some(false),
+                retention_days: Some(30),
+                max_stored_events: Some(100_000),
+                queue_capacity: Some(1024),
+                overflow: Some(String::from("drop_oldest")),
+            },
+            validation: ValidationSettings {
+                on_invalid: Some(String::from("fail")),
+            },
+            provenance: Provenance::default(),
         }
     }
 }
 
 impl AppConfig {
-    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        // Try to find config file
-        let config_paths = [
-            "app.config.toml",
-            "config/app.config.toml",
-            "./app.config.toml",
-            "./config/app.config.toml",
-        ];
-
-        let mut config_content = None;
-        let mut config_path = String::new();
-
-        for path in &config_paths {
-            if Path::new(path).exists() {
-                config_content = Some(fs::read_to_string(path)?);
-                config_path = path.to_string();
-                break;
-            }
-        }
-
-        // Also check APP_CONFIG environment variable
-        if config_content.is_none() {
-            if let Ok(env_path) = env::var("APP_CONFIG") {
-                if Path::new(&env_path).exists() {
-                    config_content = Some(fs::read_to_string(&env_path)?);
-                    config_path = env_path;
-                }
-            }
-        }
-
-        // Try to parse TOML if config found
-        if let Some(content) = config_content {
-            match toml::from_str(&content) {
-                Ok(config) => {
-                    println!("Loaded configuration from: {}", config_path);
-                    return Ok(config);
-                }
-                Err(e) => {
-                    eprintln!("Warning: Failed to parse config file: {}", e);
-                    eprintln!("Using default configuration");
-                }
-            }
-        }
-
-        // Return default config if no config file found or parsing failed
-        Ok(AppConfig::default())
+    /// Merge defaults, the config file, `APP__SECTION__KEY` variables and
+    /// command-line flags, later layers winning
+    pub fn load() -> AppResult<Self> {
+        ConfigLoader::from_environment().load()
     }
 
     pub fn get_app_name(&self) -> &str {
@@ -167,6 +178,11 @@ impl AppConfig {
         &self.app.version
     }
 
+    /// The `[profile.<name>]` merged over the file, if one was selected
+    pub fn get_profile(&self) -> Option<&str> {
+        self.provenance.profile.as_deref()
+    }
+
     pub fn get_db_path(&self) -> &str {
         &self.database.path
     }
@@ -199,6 +215,30 @@ impl AppConfig {
         self.communication.serialization.as_deref().unwrap_or("json")
     }
 
+    pub fn get_http_port(&self) -> u16 {
+        self.communication.http_port.unwrap_or(0)
+    }
+
+    pub fn is_event_store_enabled(&self) -> bool {
+        self.events.persist.unwrap_or(false)
+    }
+
+    pub fn get_event_retention_days(&self) -> u32 {
+        self.events.retention_days.unwrap_or(30)
+    }
+
+    pub fn get_max_stored_events(&self) -> u64 {
+        self.events.max_stored_events.unwrap_or(100_000)
+    }
+
+    pub fn get_event_queue_capacity(&self) -> usize {
+        self.events.queue_capacity.unwrap_or(1024)
+    }
+
+    pub fn get_event_overflow(&self) -> &str {
+        self.events.overflow.as_deref().unwrap_or("drop_oldest")
+    }
+
     pub fn is_dark_mode(&self) -> bool {
         self.features.dark_mode.unwrap_or(true)
     }
@@ -279,5 +319,8 @@ mod tests {
         assert!(config.is_dark_mode());
         assert!(config.is_resizable());
         assert_eq!(config.get_window_size(), (1200, 800));
+        assert!(!config.is_event_store_enabled());
+        assert_eq!(config.get_event_retention_days(), 30);
+        assert_eq!(config.get_event_overflow(), "drop_oldest");
     }
 }
@@ -0,0 +1,273 @@
+// src/core/infrastructure/config/repository.rs
+// File-backed ConfigRepository: maps the domain settings entity onto the config
+// file and writes it back, keeping the user's comments in TOML files
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde_json::Value;
+use toml_edit::{DocumentMut, Item};
+
+use super::format::ConfigFormat;
+use super::layers::set_key;
+use super::validation::combine;
+use super::{AppConfig, ConfigLoader};
+use crate::core::domain::entities;
+use crate::core::domain::traits::ConfigRepository;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+
+impl From<&AppConfig> for entities::AppConfig {
+    fn from(config: &AppConfig) -> Self {
+        Self {
+            app_name: config.app.name.clone(),
+            version: config.app.version.clone(),
+            window_title: config.window.title.clone(),
+            log_level: config.logging.level.clone(),
+            log_file: Some(config.logging.file.clone()),
+            append_log: config.is_append_log(),
+            db_path: config.database.path.clone(),
+            create_sample_data: config.should_create_sample_data(),
+        }
+    }
+}
+
+/// The config file keys the entity covers, with their values
+fn file_values(settings: &entities::AppConfig) -> Vec<(&'static str, Value)> {
+    let mut values = vec![
+        ("app.name", Value::from(settings.app_name.as_str())),
+        ("app.version", Value::from(settings.version.as_str())),
+        ("window.title", Value::from(settings.window_title.as_str())),
+        ("logging.level", Value::from(settings.log_level.as_str())),
+        ("logging.append", Value::from(settings.append_log)),
+        ("database.path", Value::from(settings.db_path.as_str())),
+        ("database.create_sample_data", Value::from(settings.create_sample_data)),
+    ];
+    // No log file means "leave it as it is"
+    if let Some(file) = &settings.log_file {
+        values.push(("logging.file", Value::from(file.as_str())));
+    }
+    values
+}
+
+/// Reads and writes the settings in one config file, in the file's format
+///
+/// Reads merge the file over the defaults; environment variables and
+/// command-line flags are left out, since they are not saved. Writes change only
+/// the keys whose value differs, validate the result like a startup load, and
+/// replace the file in one rename. TOML files keep their comments; YAML, JSON
+/// and INI files are written out again without them.
+pub struct FileConfigRepository {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl FileConfigRepository {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The file over the defaults; a missing file gives the defaults
+    pub fn load(&self) -> AppResult<AppConfig> {
+        if !self.path.exists() {
+            return ConfigLoader::new().load();
+        }
+        ConfigLoader::new().with_file(&self.path).load()
+    }
+
+    /// Write the settings that changed and return the configuration now on disk
+    pub fn save(&self, settings: &entities::AppConfig) -> AppResult<AppConfig> {
+        let _guard = self.write_lock.lock().map_err(|e| {
+            AppError::LockPoisoned(
+                ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire config file lock")
+                    .with_cause(e.to_string()),
+            )
+        })?;
+
+        // A file that no longer loads is rewritten key by key
+        let saved = self.load().ok().map(|config| file_values(&(&config).into()));
+        let text = if self.path.exists() {
+            fs::read_to_string(&self.path)?
+        } else {
+            String::new()
+        };
+        let changed: Vec<(&str, Value)> = file_values(settings)
+            .into_iter()
+            .filter(|(key, value)| {
+                !saved
+                    .as_ref()
+                    .is_some_and(|saved| saved.iter().any(|(k, v)| k == key && v == value))
+            })
+            .collect();
+
+        let contents = match ConfigFormat::from_path(&self.path) {
+            ConfigFormat::Toml => {
+                let mut document: DocumentMut = text.parse().map_err(|e: toml_edit::TomlError| {
+                    AppError::Configuration(
+                        ErrorValue::new(ErrorCode::ConfigInvalid, "Failed to parse config file")
+                            .with_cause(e.to_string())
+                            .with_context("path", self.path.display().to_string()),
+                    )
+                })?;
+                for (key, value) in &changed {
+                    set_value(&mut document, key, value);
+                }
+                document.to_string()
+            }
+            format => {
+                let mut tree = if text.trim().is_empty() {
+                    Value::Object(Default::default())
+                } else {
+                    format.parse(&text, &self.path)?
+                };
+                for (key, value) in changed {
+                    set_key(&mut tree, key, value);
+                }
+                format.render(&tree)?
+            }
+        };
+        let config = ConfigLoader::new().with_contents(&self.path, contents.as_str()).load()?;
+        if !config.provenance.problems.is_empty() {
+            // The degraded policy would start with defaults; a save must not
+            let problems: Vec<AppError> = config
+                .provenance
+                .problems
+                .iter()
+                .cloned()
+                .map(AppError::Configuration)
+                .collect();
+            return Err(combine(&problems));
+        }
+        self.write(&contents)?;
+        Ok(config)
+    }
+
+    /// Write a sibling temporary file and rename it over the config file
+    fn write(&self, contents: &str) -> AppResult<()> {
+        let dir = match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let mut file = tempfile::NamedTempFile::new_in(dir)?;
+        file.write_all(contents.as_bytes())?;
+        file.as_file().sync_all()?;
+        if let Ok(metadata) = fs::metadata(&self.path) {
+            fs::set_permissions(file.path(), metadata.permissions())?;
+        }
+        file.persist(&self.path).map_err(|e| e.error)?;
+        Ok(())
+    }
+}
+
+impl ConfigRepository for FileConfigRepository {
+    fn load_config(&self) -> anyhow::Result<entities::AppConfig> {
+        Ok((&self.load()?).into())
+    }
+
+    fn save_config(&self, config: &entities::AppConfig) -> anyhow::Result<()> {
+        self.save(config)?;
+        Ok(())
+    }
+}
+
+/// Set `section.key`, keeping the comments around an existing value
+fn set_value(document: &mut DocumentMut, key: &str, value: &Value) {
+    let Some((section, name)) = key.split_once('.') else {
+        return;
+    };
+    let value = match value {
+        Value::Bool(b) => toml_edit::Value::from(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => toml_edit::Value::from(i),
+            None => toml_edit::Value::from(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => toml_edit::Value::from(s.as_str()),
+        other => toml_edit::Value::from(other.to_string()),
+    };
+
+    let item = document.entry(section).or_insert_with(toml_edit::table);
+    if !item.is_table_like() {
+        *item = toml_edit::table();
+    }
+    let Some(table) = item.as_table_like_mut() else {
+        return;
+    };
+    match table.get_mut(name).and_then(Item::as_value_mut) {
+        Some(existing) => {
+            let decor = existing.decor().clone();
+            *existing = value;
+            *existing.decor_mut() = decor;
+        }
+        None => {
+            table.insert(name, Item::Value(value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "# My settings\n[app]\nname = \"Demo\" # shown in the title bar\nversion = \"1.0.0\"\n\n[logging]\n# chatty while debugging\nlevel = \"debug\"\n";
+
+    #[test]
+    fn test_save_keeps_comments_and_untouched_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.config.toml");
+        fs::write(&path, FILE).unwrap();
+        let repository = FileConfigRepository::new(&path);
+
+        let mut settings = repository.load_config().unwrap();
+        assert_eq!(settings.app_name, "Demo");
+        assert_eq!(settings.log_level, "debug");
+
+        settings.app_name = "Renamed".into();
+        settings.log_level = "warn".into();
+        repository.save_config(&settings).unwrap();
+
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(written.starts_with("# My settings\n"));
+        assert!(written.contains("name = \"Renamed\" # shown in the title bar"));
+        assert!(written.contains("# chatty while debugging\nlevel = \"warn\""));
+        // Unchanged settings stay at their defaults instead of being written out
+        assert!(!written.contains("database"));
+        assert_eq!(repository.load_config().unwrap().app_name, "Renamed");
+    }
+
+    #[test]
+    fn test_save_keeps_the_file_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.config.yaml");
+        fs::write(&path, "app:\n  name: Demo\n").unwrap();
+        let repository = FileConfigRepository::new(&path);
+
+        let mut settings = repository.load_config().unwrap();
+        settings.create_sample_data = false;
+        repository.save_config(&settings).unwrap();
+
+        let written: Value = serde_yaml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, serde_json::json!({"app": {"name": "Demo"}, "database": {"create_sample_data": false}}));
+    }
+
+    #[test]
+    fn test_invalid_settings_leave_the_file_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.config.toml");
+        fs::write(&path, FILE).unwrap();
+        let repository = FileConfigRepository::new(&path);
+
+        let mut settings = repository.load_config().unwrap();
+        settings.log_level = "loud".into();
+        let err = repository.save(&settings).unwrap_err();
+        assert!(err.to_value().context.as_ref().unwrap().contains_key("logging.level"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), FILE);
+    }
+}
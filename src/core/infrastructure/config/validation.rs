@@ -0,0 +1,270 @@
+// src/core/infrastructure/config/validation.rs
+// Configuration checks reported per field, and the policy applied to failures
+
+use std::fs::OpenOptions;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::AppConfig;
+use crate::core::error::{AppError, ErrorCode, ErrorValue};
+use crate::core::infrastructure::event_bus::OverflowPolicy;
+use crate::utils::serialization::SerializationFormat;
+
+pub const LOG_LEVELS: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+pub const TRANSPORTS: [&str; 3] = ["webview_ffi", "http_rest", "websocket"];
+
+/// What startup does with an invalid configuration (`validation.on_invalid`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationPolicy {
+    /// Refuse to start and report every problem
+    #[default]
+    Fail,
+    /// Put the default back for each invalid value and start anyway
+    Degraded,
+}
+
+impl ValidationPolicy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "fail" => Some(Self::Fail),
+            "degraded" => Some(Self::Degraded),
+            _ => None,
+        }
+    }
+}
+
+pub fn invalid(field: &str, message: impl Into<String>) -> AppError {
+    AppError::Configuration(ErrorValue::new(ErrorCode::ConfigInvalid, message).with_field(field))
+}
+
+pub fn missing(field: &str) -> AppError {
+    AppError::Configuration(
+        ErrorValue::new(ErrorCode::ConfigMissingField, format!("{} must be set", field))
+            .with_field(field),
+    )
+}
+
+/// All problems as one error: `details` has a line per problem, `context` maps
+/// each field to its message
+pub fn combine(problems: &[AppError]) -> AppError {
+    let lines: Vec<String> = problems.iter().map(|p| describe(p.to_value())).collect();
+    let mut error = ErrorValue::new(
+        ErrorCode::ConfigInvalid,
+        format!("Invalid configuration ({} problem(s))", problems.len()),
+    )
+    .with_details(lines.join("\n"));
+    for problem in problems {
+        let value = problem.to_value();
+        if let Some(field) = &value.field {
+            error = error.with_context(field.clone(), value.message.clone());
+        }
+    }
+    AppError::Configuration(error)
+}
+
+/// `logging.level: Unknown log level 'loud'`, or the bare message without a field
+pub fn describe(value: &ErrorValue) -> String {
+    let mut line = match &value.field {
+        Some(field) => format!("{}: {}", field, value.message),
+        None => value.message.clone(),
+    };
+    if let Some(cause) = &value.cause {
+        line.push_str(&format!(" ({})", cause));
+    }
+    line
+}
+
+/// Overrides whose JSON kind differs from the default's, e.g. `width = "wide"`
+///
+/// Numeric defaults are all unsigned, so negative numbers are rejected too.
+pub fn check_types(defaults: &Value, key: &str, value: &Value) -> Option<AppError> {
+    let expected = match super::layers::get_key(defaults, key)? {
+        Value::Null => match value {
+            Value::String(_) | Value::Null => return None,
+            _ => "a string",
+        },
+        Value::Bool(_) if value.is_boolean() => return None,
+        Value::Bool(_) => "a boolean",
+        Value::Number(n) if n.is_f64() && value.is_number() => return None,
+        Value::Number(_) if value.is_u64() => return check_range(defaults, key, value),
+        Value::Number(_) => "a non-negative integer",
+        Value::String(_) if value.is_string() => return None,
+        Value::String(_) => "a string",
+        Value::Array(_) if value.is_array() => return None,
+        Value::Array(_) => "an array",
+        Value::Object(_) if value.is_object() => return None,
+        Value::Object(_) => "a table",
+    };
+    Some(invalid(key, format!("Expected {}, found {}", expected, value)))
+}
+
+/// Integers too large for the field's type, e.g. `http_port = 70000` for a `u16`
+///
+/// Probes the defaults with only `key` replaced, so the field's own type decides.
+pub fn check_range(defaults: &Value, key: &str, value: &Value) -> Option<AppError> {
+    let mut probe = defaults.clone();
+    super::layers::set_key(&mut probe, key, value.clone());
+    let e = serde_json::from_value::<AppConfig>(probe).err()?;
+    Some(AppError::Configuration(
+        ErrorValue::new(ErrorCode::ConfigInvalid, format!("{} is out of range", value))
+            .with_field(key)
+            .with_cause(e.to_string()),
+    ))
+}
+
+impl AppConfig {
+    /// Check values that parse but make no sense; one error per problem
+    pub fn validate(&self) -> Vec<AppError> {
+        let mut problems = Vec::new();
+
+        for (field, value) in [
+            ("app.name", &self.app.name),
+            ("window.title", &self.window.title),
+            ("logging.file", &self.logging.file),
+            ("database.path", &self.database.path),
+        ] {
+            if value.trim().is_empty() {
+                problems.push(missing(field));
+            }
+        }
+
+        if !LOG_LEVELS.contains(&self.logging.level.to_ascii_lowercase().as_str()) {
+            problems.push(invalid(
+                "logging.level",
+                format!("Unknown log level '{}', expected one of {}", self.logging.level, LOG_LEVELS.join(", ")),
+            ));
+        }
+        if !TRANSPORTS.contains(&self.get_transport()) {
+            problems.push(invalid(
+                "communication.transport",
+                format!("Unknown transport '{}', expected one of {}", self.get_transport(), TRANSPORTS.join(", ")),
+            ));
+        }
+        if SerializationFormat::from_name(self.get_serialization()).is_none() {
+            problems.push(invalid(
+                "communication.serialization",
+                format!("Unknown serialization format '{}', expected json, messagepack or cbor", self.get_serialization()),
+            ));
+        }
+        if OverflowPolicy::from_name(self.get_event_overflow()).is_none() {
+            problems.push(invalid(
+                "events.overflow",
+                format!("Unknown overflow policy '{}', expected drop_oldest, drop_new or block", self.get_event_overflow()),
+            ));
+        }
+        if let Some(policy) = &self.validation.on_invalid {
+            if ValidationPolicy::from_name(policy).is_none() {
+                problems.push(invalid(
+                    "validation.on_invalid",
+                    format!("Unknown policy '{}', expected fail or degraded", policy),
+                ));
+            }
+        }
+
+        let (width, height) = self.get_window_size();
+        let (min_width, min_height) = self.get_min_window_size();
+        for (field, size, min_field, min) in [
+            ("window.width", width, "window.min_width", min_width),
+            ("window.height", height, "window.min_height", min_height),
+        ] {
+            if size == 0 {
+                problems.push(invalid(field, "Must be greater than 0"));
+            } else if min > size {
+                problems.push(invalid(
+                    min_field,
+                    format!("{} ({}) is larger than {} ({})", min_field, min, field, size),
+                ));
+            }
+        }
+
+        if let Some(problem) = check_writable(&self.database.path) {
+            problems.push(problem);
+        }
+        problems
+    }
+
+    pub fn validation_policy(&self) -> ValidationPolicy {
+        self.validation
+            .on_invalid
+            .as_deref()
+            .and_then(ValidationPolicy::from_name)
+            .unwrap_or_default()
+    }
+}
+
+/// The database file, or the directory it would be created in, must be writable
+fn check_writable(db_path: &str) -> Option<AppError> {
+    if db_path.trim().is_empty() || db_path == ":memory:" {
+        return None;
+    }
+    let path = Path::new(db_path);
+    let result = if path.exists() {
+        OpenOptions::new().append(true).open(path).map(|_| ())
+    } else {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        tempfile::tempfile_in(dir).map(|_| ())
+    };
+    result.err().map(|e| {
+        AppError::Configuration(
+            ErrorValue::new(ErrorCode::ConfigInvalid, format!("Database path '{}' is not writable", db_path))
+                .with_field("database.path")
+                .with_cause(e.to_string()),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(problems: &[AppError]) -> Vec<String> {
+        problems
+            .iter()
+            .map(|p| p.to_value().field.clone().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_reports_each_field() {
+        let mut config = AppConfig::default();
+        assert!(config.validate().is_empty());
+
+        config.logging.level = "loud".into();
+        config.communication.transport = Some("carrier_pigeon".into());
+        config.window.min_width = Some(1600);
+        config.app.name = " ".into();
+        config.database.path = "/nonexistent-dir/app.db".into();
+        let problems = config.validate();
+        assert_eq!(
+            fields(&problems),
+            vec!["app.name", "logging.level", "communication.transport", "window.min_width", "database.path"]
+        );
+        assert_eq!(problems[0].to_value().code, ErrorCode::ConfigMissingField);
+        assert_eq!(problems[1].to_value().code, ErrorCode::ConfigInvalid);
+
+        let combined = combine(&problems).to_value().clone();
+        assert_eq!(combined.details.unwrap().lines().count(), 5);
+        assert!(combined.context.unwrap()["window.min_width"].contains("1600"));
+    }
+
+    #[test]
+    fn test_type_checks_against_defaults() {
+        let defaults = serde_json::to_value(AppConfig::default()).unwrap();
+        assert!(check_types(&defaults, "window.width", &serde_json::json!(900)).is_none());
+        assert!(check_types(&defaults, "app.description", &serde_json::json!("Demo")).is_none());
+        let err = check_types(&defaults, "window.width", &serde_json::json!(-1)).unwrap();
+        assert_eq!(err.to_value().field.as_deref(), Some("window.width"));
+        assert!(check_types(&defaults, "logging.level", &serde_json::json!(3)).is_some());
+        let err = check_types(&defaults, "communication.http_port", &serde_json::json!(70000)).unwrap();
+        assert_eq!(err.to_value().field.as_deref(), Some("communication.http_port"));
+        assert!(check_types(&defaults, "communication.http_port", &serde_json::json!(65535)).is_none());
+        assert!(check_types(&defaults, "events.max_stored_events", &serde_json::json!(u64::MAX)).is_none());
+        assert_eq!(ValidationPolicy::from_name("Degraded"), Some(ValidationPolicy::Degraded));
+    }
+}
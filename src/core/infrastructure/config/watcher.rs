@@ -0,0 +1,390 @@
+// src/core/infrastructure/config/watcher.rs
+// Hot reload: watch the config file, re-validate it, swap it into the container
+// and announce what changed
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use log::{error, info, warn};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::layers::ValueSource;
+use super::validation::describe;
+use super::{AppConfig, ConfigLoader, FeatureSettings};
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::di::Container;
+use crate::core::infrastructure::event_bus::get_global_event_bus;
+use crate::core::infrastructure::lifecycle::{Service, ServiceHealth};
+use crate::core::infrastructure::logging::LoggingConfig;
+
+pub const CONFIG_CHANGED_EVENT: &str = "config.changed";
+pub const CONFIG_RELOAD_FAILED_EVENT: &str = "config.reload_failed";
+const EVENT_SOURCE: &str = "config-watcher";
+
+/// Keys, or `section.` prefixes, that take effect without a restart
+const LIVE_KEYS: [&str; 6] = [
+    "logging.level",
+    "features.dark_mode",
+    "features.show_tray_icon",
+    "events.queue_capacity",
+    "events.overflow",
+    "validation.on_invalid",
+];
+
+/// Editors write a file in several steps; wait this long for them to settle
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// One setting that differs between the running and the reloaded configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChange {
+    pub key: String,
+    pub old: Value,
+    pub new: Value,
+    pub source: ValueSource,
+    /// Applied immediately; otherwise it takes effect on the next start
+    pub live: bool,
+}
+
+/// Payload of `config.changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigChangedEvent {
+    pub file: Option<String>,
+    pub changes: Vec<ConfigChange>,
+    /// Keys among `changes` that need a restart
+    pub restart_required: Vec<String>,
+    /// The reloaded `[features]`, for the UI to re-apply
+    pub features: FeatureSettings,
+}
+
+pub fn is_live(key: &str) -> bool {
+    LIVE_KEYS
+        .iter()
+        .any(|live| if live.ends_with('.') { key.starts_with(live) } else { key == *live })
+}
+
+/// Settings whose effective value differs, in key order
+pub fn diff(old: &AppConfig, new: &AppConfig) -> Vec<ConfigChange> {
+    let before: BTreeMap<String, Value> = old
+        .effective_values()
+        .into_iter()
+        .map(|v| (v.key, v.value))
+        .collect();
+    new.effective_values()
+        .into_iter()
+        .filter(|v| before.get(&v.key) != Some(&v.value))
+        .map(|v| ConfigChange {
+            old: before.get(&v.key).cloned().unwrap_or(Value::Null),
+            live: is_live(&v.key),
+            key: v.key,
+            new: v.value,
+            source: v.source,
+        })
+        .collect()
+}
+
+type ConfigLoadFn = Box<dyn Fn() -> AppResult<AppConfig> + Send + Sync>;
+type ReloadListener = Box<dyn Fn(&AppConfig, &[ConfigChange]) + Send + Sync>;
+
+struct Reloader {
+    container: Arc<Container>,
+    /// The configuration the process started with, to tell what awaits a restart
+    startup: Option<Arc<AppConfig>>,
+    load: ConfigLoadFn,
+    listeners: Mutex<Vec<ReloadListener>>,
+}
+
+impl Reloader {
+    fn reload(&self) -> AppResult<Vec<ConfigChange>> {
+        let mut config = match (self.load)() {
+            Ok(config) => config,
+            Err(e) => {
+                error!("Configuration reload rejected, keeping the running settings: {}", e);
+                if let Some(details) = &e.to_value().details {
+                    for line in details.lines() {
+                        error!("  - {}", line);
+                    }
+                }
+                get_global_event_bus().emit_with_source(
+                    CONFIG_RELOAD_FAILED_EVENT,
+                    serde_json::to_value(e.to_value())?,
+                    EVENT_SOURCE,
+                );
+                return Err(e);
+            }
+        };
+
+        let running = self.container.resolve_arc::<AppConfig>()?;
+        let changes = diff(&running, &config);
+        if changes.is_empty() {
+            return Ok(changes);
+        }
+        for problem in &config.provenance.problems {
+            warn!("Invalid configuration, using the default instead: {}", describe(problem));
+        }
+
+        let startup = self.startup.as_deref().unwrap_or(&running);
+        config.provenance.pending_restart = diff(startup, &config)
+            .into_iter()
+            .filter(|c| !c.live)
+            .map(|c| c.key)
+            .collect();
+
+        // Replacing the registration is one insert under the container's lock
+        self.container.register_singleton(config.clone())?;
+
+        if changes.iter().any(|c| c.key == "logging.level") {
+            log::set_max_level(LoggingConfig::level_from_str(config.get_log_level()));
+        }
+        for listener in lock(&self.listeners)?.iter() {
+            listener(&config, &changes);
+        }
+
+        let restart_required: Vec<String> = changes
+            .iter()
+            .filter(|c| !c.live)
+            .map(|c| c.key.clone())
+            .collect();
+        for change in &changes {
+            info!("Configuration changed: {} = {} (was {})", change.key, change.new, change.old);
+        }
+        if !restart_required.is_empty() {
+            warn!("Restart to apply: {}", restart_required.join(", "));
+        }
+
+        let event = ConfigChangedEvent {
+            file: config.provenance.file.as_ref().map(|p| p.display().to_string()),
+            changes: changes.clone(),
+            restart_required,
+            features: config.features.clone(),
+        };
+        get_global_event_bus().emit_with_source(CONFIG_CHANGED_EVENT, serde_json::to_value(event)?, EVENT_SOURCE);
+        Ok(changes)
+    }
+}
+
+struct Running {
+    // Dropping the watcher closes the channel, which ends the thread
+    watcher: RecommendedWatcher,
+    thread: JoinHandle<()>,
+}
+
+/// Reloads the configuration file whenever it changes on disk
+pub struct ConfigWatcher {
+    path: PathBuf,
+    reloader: Arc<Reloader>,
+    running: Mutex<Option<Running>>,
+}
+
+impl ConfigWatcher {
+    /// Watch `path`; reloads run every layer again, as at startup, and replace
+    /// the `AppConfig` registered in `container`
+    pub fn new(path: impl Into<PathBuf>, container: Arc<Container>) -> Self {
+        Self {
+            path: path.into(),
+            reloader: Arc::new(Reloader {
+                startup: container.resolve_arc::<AppConfig>().ok(),
+                container,
+                load: Box::new(|| ConfigLoader::from_environment().load()),
+                listeners: Mutex::new(Vec::new()),
+            }),
+            running: Mutex::new(None),
+        }
+    }
+
+    /// Build the reloaded configuration some other way
+    pub fn with_loader<F>(mut self, load: F) -> Self
+    where
+        F: Fn() -> AppResult<AppConfig> + Send + Sync + 'static,
+    {
+        if let Some(reloader) = Arc::get_mut(&mut self.reloader) {
+            reloader.load = Box::new(load);
+        }
+        self
+    }
+
+    /// Run `listener` after each reload that changed something, once the new
+    /// configuration is registered
+    pub fn on_reload<F>(&self, listener: F) -> AppResult<()>
+    where
+        F: Fn(&AppConfig, &[ConfigChange]) + Send + Sync + 'static,
+    {
+        lock(&self.reloader.listeners)?.push(Box::new(listener));
+        Ok(())
+    }
+
+    /// Load the configuration now; an invalid file leaves the running one in place
+    pub fn reload(&self) -> AppResult<Vec<ConfigChange>> {
+        self.reloader.reload()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn watch(&self) -> AppResult<Running> {
+        let file_name = self.path.file_name().map(|n| n.to_os_string()).ok_or_else(|| {
+            watch_error(&self.path, "Not a file path".to_string())
+        })?;
+        // Watch the directory: editors often replace the file instead of writing it
+        let dir = match self.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+            let Ok(event) = result else { return };
+            let relevant = !matches!(event.kind, EventKind::Access(_))
+                && event.paths.iter().any(|p| p.file_name() == Some(file_name.as_os_str()));
+            if relevant {
+                let _ = tx.send(());
+            }
+        })
+        .map_err(|e| watch_error(&self.path, e.to_string()))?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|e| watch_error(&self.path, e.to_string()))?;
+
+        let reloader = Arc::clone(&self.reloader);
+        let thread = thread::Builder::new()
+            .name("config-watcher".to_string())
+            .spawn(move || {
+                while rx.recv().is_ok() {
+                    loop {
+                        match rx.recv_timeout(DEBOUNCE) {
+                            Ok(()) => continue,
+                            Err(RecvTimeoutError::Timeout) => break,
+                            Err(RecvTimeoutError::Disconnected) => return,
+                        }
+                    }
+                    // Failures are logged and announced by the reloader
+                    let _ = reloader.reload();
+                }
+            })?;
+        Ok(Running { watcher, thread })
+    }
+}
+
+impl Service for ConfigWatcher {
+    fn name(&self) -> &str {
+        EVENT_SOURCE
+    }
+
+    /// A file that cannot be watched only loses hot reload, so this never fails startup
+    fn start(&self) -> AppResult<()> {
+        let mut running = lock(&self.running)?;
+        if running.is_none() {
+            match self.watch() {
+                Ok(watch) => {
+                    *running = Some(watch);
+                    info!("Watching {} for changes", self.path.display());
+                }
+                Err(e) => warn!("Configuration hot reload unavailable: {}", e),
+            }
+        }
+        Ok(())
+    }
+
+    fn stop(&self) -> AppResult<()> {
+        let running = lock(&self.running)?.take();
+        if let Some(Running { watcher, thread }) = running {
+            drop(watcher);
+            let _ = thread.join();
+        }
+        Ok(())
+    }
+
+    fn health(&self) -> ServiceHealth {
+        match self.running.lock() {
+            Ok(running) if running.is_some() => ServiceHealth::Healthy,
+            Ok(_) => ServiceHealth::Degraded("not watching".to_string()),
+            Err(e) => ServiceHealth::Unhealthy(e.to_string()),
+        }
+    }
+}
+
+fn watch_error(path: &Path, cause: String) -> AppError {
+    AppError::Configuration(
+        ErrorValue::new(ErrorCode::InternalError, format!("Cannot watch {}", path.display()))
+            .with_cause(cause),
+    )
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> AppResult<std::sync::MutexGuard<'_, T>> {
+    mutex.lock().map_err(|e| {
+        AppError::LockPoisoned(
+            ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire config watcher lock")
+                .with_cause(e.to_string()),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Instant;
+
+    #[test]
+    fn test_diff_classifies_live_settings() {
+        let old = AppConfig::default();
+        let mut new = old.clone();
+        new.features.dark_mode = Some(false);
+        new.communication.transport = Some("websocket".into());
+        new.logging.level = "debug".into();
+
+        let changes = diff(&old, &new);
+        let keys: Vec<(&str, bool)> = changes.iter().map(|c| (c.key.as_str(), c.live)).collect();
+        assert_eq!(
+            keys,
+            vec![("communication.transport", false), ("features.dark_mode", true), ("logging.level", true)]
+        );
+        assert_eq!(changes[0].old, Value::from("webview_ffi"));
+        assert!(diff(&old, &old.clone()).is_empty());
+        assert!(!is_live("logging.levelx"));
+    }
+
+    #[test]
+    fn test_reload_swaps_valid_config_and_keeps_it_on_failure() {
+        let container = Arc::new(Container::new());
+        container.register_singleton(AppConfig::default()).unwrap();
+        let path = std::env::temp_dir().join(format!("watcher-{}.toml", std::process::id()));
+        let source = path.clone();
+
+        let watcher = ConfigWatcher::new(&path, Arc::clone(&container))
+            .with_loader(move || ConfigLoader::new().with_file(&source).load());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        watcher
+            .on_reload(move |_, changes| sink.lock().unwrap().extend(changes.iter().map(|c| c.key.clone())))
+            .unwrap();
+
+        fs::write(&path, "").unwrap();
+        watcher.start().unwrap();
+        fs::write(&path, "[features]\nshow_tray_icon = true\n").unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !container.resolve_arc::<AppConfig>().unwrap().features.show_tray_icon.unwrap_or(false) {
+            assert!(Instant::now() < deadline, "file change was not picked up");
+            thread::sleep(Duration::from_millis(50));
+        }
+        watcher.stop().unwrap();
+        assert_eq!(*seen.lock().unwrap(), vec!["features.show_tray_icon"]);
+
+        fs::write(&path, "[communication]\ntransport = \"websocket\"\n").unwrap();
+        let changes = watcher.reload().unwrap();
+        assert_eq!(changes.len(), 2);
+        let config = container.resolve_arc::<AppConfig>().unwrap();
+        assert_eq!(config.provenance.pending_restart, vec!["communication.transport"]);
+
+        fs::write(&path, "[window]\nwidth = \"wide\"\n").unwrap();
+        assert!(watcher.reload().is_err());
+        assert_eq!(container.resolve_arc::<AppConfig>().unwrap().get_window_size(), (1200, 800));
+        let _ = fs::remove_file(&path);
+    }
+}
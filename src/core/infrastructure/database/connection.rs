@@ -8,6 +8,7 @@ use std::sync::{Arc, Mutex};
 
 use super::models::QueryResult;
 use crate::core::error::{AppError, AppResult, ErrorValue, ErrorCode};
+use crate::core::infrastructure::lifecycle::{Service, ServiceHealth};
 
 /// Database manager with raw query support
 pub struct Database {
@@ -133,6 +134,48 @@ impl Database {
 
         Ok(serde_json::Value::Null)
     }
+
+    /// Switch to write-ahead logging; in-memory databases keep their own mode
+    pub fn enable_wal(&self) -> AppResult<String> {
+        let conn = self.get_connection()?;
+        let mode: String = conn.query_row("PRAGMA journal_mode=WAL", [], |row| row.get(0))?;
+        Ok(mode)
+    }
+
+    /// Copy the WAL back into the database file and truncate it
+    pub fn checkpoint(&self) -> AppResult<()> {
+        let conn = self.get_connection()?;
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        Ok(())
+    }
+}
+
+impl Service for Database {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    fn start(&self) -> AppResult<()> {
+        let mode = self.enable_wal()?;
+        info!("Database journal mode: {}", mode);
+        Ok(())
+    }
+
+    fn stop(&self) -> AppResult<()> {
+        self.checkpoint()?;
+        info!("Database checkpointed");
+        Ok(())
+    }
+
+    fn health(&self) -> ServiceHealth {
+        let check = self
+            .get_connection()
+            .and_then(|conn| Ok(conn.query_row("SELECT 1", [], |row| row.get::<_, i64>(0))?));
+        match check {
+            Ok(_) => ServiceHealth::Healthy,
+            Err(e) => ServiceHealth::Unhealthy(e.to_string()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -144,4 +187,18 @@ mod tests {
         let db = Database::new(":memory:").expect("Failed to create in-memory database");
         assert!(db.init().is_ok());
     }
+
+    #[test]
+    fn test_database_service_checkpoints_wal() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(dir.path().join("app.db").to_str().unwrap()).unwrap();
+        db.init().unwrap();
+        assert_eq!(db.enable_wal().unwrap(), "wal");
+        db.execute("INSERT INTO products (name, price, category) VALUES ('a', 1.0, 'b')", &[]).unwrap();
+        assert!(dir.path().join("app.db-wal").metadata().unwrap().len() > 0);
+
+        db.stop().unwrap();
+        assert_eq!(dir.path().join("app.db-wal").metadata().unwrap().len(), 0);
+        assert_eq!(db.health(), ServiceHealth::Healthy);
+    }
 }
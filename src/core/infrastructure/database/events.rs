@@ -0,0 +1,287 @@
+#![allow(dead_code)]
+// src/core/infrastructure/database/events.rs
+// Durable event log table backing the event bus store
+
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, params_from_iter};
+
+use super::connection::Database;
+use super::models::StoredEvent;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::event_bus::{topic, EventData, EventQuery};
+
+impl Database {
+    /// Create the event log table and its indexes
+    ///
+    /// Logs created before correlation ids were stored gain the column.
+    pub fn init_event_log(&self) -> AppResult<()> {
+        let conn = self.get_connection()?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS event_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                event_type TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                source TEXT,
+                target TEXT,
+                correlation_id TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_event_log_type ON event_log (event_type, timestamp);
+            CREATE INDEX IF NOT EXISTS idx_event_log_timestamp ON event_log (timestamp);",
+        )?;
+
+        let has_correlation_id: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('event_log') WHERE name = 'correlation_id'",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| event_log_error("Failed to inspect event log columns", e))?;
+        if !has_correlation_id {
+            conn.execute("ALTER TABLE event_log ADD COLUMN correlation_id TEXT", [])
+                .map_err(|e| event_log_error("Failed to add the correlation_id column", e))?;
+        }
+        Ok(())
+    }
+
+    /// Append an event and return its row id
+    pub fn insert_event(&self, event: &EventData) -> AppResult<i64> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "INSERT INTO event_log (event_type, payload, timestamp, source, target, correlation_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                event.event_type,
+                event.payload.to_string(),
+                event.timestamp,
+                event.source,
+                event.target,
+                event.correlation_id
+            ],
+        )
+        .map_err(|e| {
+            AppError::Database(
+                ErrorValue::new(ErrorCode::DbQueryFailed, "Failed to store event")
+                    .with_cause(e.to_string())
+                    .with_context("table", "event_log")
+                    .with_context("event_type", event.event_type.clone()),
+            )
+        })?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Stored events matching the query, oldest first
+    ///
+    /// With a limit, the newest matching events are returned. Topic patterns are
+    /// narrowed in SQL by their literal prefix and matched exactly afterwards.
+    pub fn query_events(&self, query: &EventQuery) -> AppResult<Vec<StoredEvent>> {
+        let mut clauses: Vec<&str> = Vec::new();
+        let mut values: Vec<SqlValue> = Vec::new();
+        let pattern = query.event_type.as_deref().filter(|t| topic::is_pattern(t));
+
+        match query.event_type.as_deref() {
+            Some(event_type) if pattern.is_none() => {
+                clauses.push("event_type = ?");
+                values.push(SqlValue::Text(event_type.to_string()));
+            }
+            Some(event_type) => {
+                let prefix = topic::literal_prefix(event_type);
+                if !prefix.is_empty() {
+                    clauses.push("substr(event_type, 1, ?) = ?");
+                    values.push(SqlValue::Integer(prefix.len() as i64));
+                    values.push(SqlValue::Text(prefix));
+                }
+            }
+            None => {}
+        }
+        if let Some(source) = &query.source {
+            clauses.push("source = ?");
+            values.push(SqlValue::Text(source.clone()));
+        }
+        if let Some(target) = &query.target {
+            clauses.push("target = ?");
+            values.push(SqlValue::Text(target.clone()));
+        }
+        if let Some(since) = query.since {
+            clauses.push("timestamp >= ?");
+            values.push(SqlValue::Integer(since));
+        }
+        if let Some(until) = query.until {
+            clauses.push("timestamp <= ?");
+            values.push(SqlValue::Integer(until));
+        }
+
+        let mut sql = String::from(
+            "SELECT id, event_type, payload, timestamp, source, target, correlation_id FROM event_log",
+        );
+        if !clauses.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&clauses.join(" AND "));
+        }
+        sql.push_str(" ORDER BY id DESC");
+        if let (Some(limit), None) = (query.limit, pattern) {
+            sql.push_str(" LIMIT ?");
+            values.push(SqlValue::Integer(limit as i64));
+        }
+
+        let conn = self.get_connection()?;
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| event_log_error("Failed to prepare event query", e))?;
+        let rows = stmt
+            .query_map(params_from_iter(values), |row| {
+                let payload: String = row.get(2)?;
+                let event = StoredEvent {
+                    id: row.get(0)?,
+                    event_type: row.get(1)?,
+                    payload: serde_json::Value::Null,
+                    timestamp: row.get(3)?,
+                    source: row.get(4)?,
+                    target: row.get(5)?,
+                    correlation_id: row.get(6)?,
+                };
+                Ok((event, payload))
+            })
+            .map_err(|e| event_log_error("Failed to query events", e))?;
+
+        let mut events = Vec::new();
+        for row in rows {
+            let (mut event, payload) = row.map_err(|e| event_log_error("Failed to read event row", e))?;
+            event.payload = serde_json::from_str(&payload).map_err(|e| {
+                AppError::Database(
+                    ErrorValue::new(ErrorCode::DbQueryFailed, "Stored event payload is not valid JSON")
+                        .with_cause(e.to_string())
+                        .with_context("table", "event_log")
+                        .with_context("id", event.id.to_string()),
+                )
+            })?;
+            if pattern.is_none_or(|p| topic::matches(p, &event.event_type)) {
+                events.push(event);
+                if query.limit.is_some_and(|limit| events.len() >= limit) {
+                    break;
+                }
+            }
+        }
+        events.reverse();
+        Ok(events)
+    }
+
+    /// Delete events older than the cutoff and beyond the newest `max_events`
+    pub fn prune_events(&self, older_than: Option<i64>, max_events: Option<u64>) -> AppResult<usize> {
+        let conn = self.get_connection()?;
+        let mut deleted = 0;
+        if let Some(cutoff) = older_than {
+            deleted += conn
+                .execute("DELETE FROM event_log WHERE timestamp < ?1", params![cutoff])
+                .map_err(|e| event_log_error("Failed to prune old events", e))?;
+        }
+        if let Some(max_events) = max_events {
+            deleted += conn
+                .execute(
+                    "DELETE FROM event_log WHERE id NOT IN
+                     (SELECT id FROM event_log ORDER BY id DESC LIMIT ?1)",
+                    params![max_events as i64],
+                )
+                .map_err(|e| event_log_error("Failed to trim event log", e))?;
+        }
+        Ok(deleted)
+    }
+
+    pub fn count_events(&self) -> AppResult<u64> {
+        let conn = self.get_connection()?;
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM event_log", [], |row| row.get(0))
+            .map_err(|e| event_log_error("Failed to count events", e))?;
+        Ok(count as u64)
+    }
+}
+
+fn event_log_error(message: &str, err: rusqlite::Error) -> AppError {
+    AppError::Database(
+        ErrorValue::new(ErrorCode::DbQueryFailed, message)
+            .with_cause(err.to_string())
+            .with_context("table", "event_log"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: &str, timestamp: i64, source: &str) -> EventData {
+        let mut event = EventData::new(event_type, serde_json::json!({ "t": timestamp })).with_source(source);
+        event.timestamp = timestamp;
+        event
+    }
+
+    fn store() -> Database {
+        let db = Database::new(":memory:").unwrap();
+        db.init_event_log().unwrap();
+        for (event_type, timestamp, source) in [
+            ("user.created", 100, "frontend"),
+            ("user.deleted", 200, "backend"),
+            ("order.created", 300, "frontend"),
+            ("user.profile.updated", 400, "frontend"),
+        ] {
+            db.insert_event(&event(event_type, timestamp, source)).unwrap();
+        }
+        db
+    }
+
+    #[test]
+    fn test_query_filters() {
+        let db = store();
+        let types = |query: EventQuery| -> Vec<String> {
+            db.query_events(&query).unwrap().into_iter().map(|e| e.event_type).collect()
+        };
+
+        assert_eq!(types(EventQuery::for_type("user.created")), vec!["user.created"]);
+        assert_eq!(types(EventQuery::for_type("user.*")), vec!["user.created", "user.deleted"]);
+        assert_eq!(
+            types(EventQuery { source: Some("frontend".into()), since: Some(150), ..Default::default() }),
+            vec!["order.created", "user.profile.updated"]
+        );
+        assert_eq!(
+            types(EventQuery { limit: Some(2), ..Default::default() }),
+            vec!["order.created", "user.profile.updated"]
+        );
+        assert_eq!(types(EventQuery { until: Some(100), ..Default::default() }), vec!["user.created"]);
+    }
+
+    #[test]
+    fn test_prune_by_age_and_count() {
+        let db = store();
+        assert_eq!(db.prune_events(Some(150), None).unwrap(), 1);
+        assert_eq!(db.prune_events(None, Some(2)).unwrap(), 1);
+        assert_eq!(db.count_events().unwrap(), 2);
+
+        let stored = db.query_events(&EventQuery::default()).unwrap();
+        assert_eq!(stored[0].payload["t"], 300);
+    }
+
+    #[test]
+    fn test_correlation_id_and_bad_payloads() {
+        let db = Database::new(":memory:").unwrap();
+        db.get_connection()
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE event_log (id INTEGER PRIMARY KEY AUTOINCREMENT, event_type TEXT NOT NULL,
+                 payload TEXT NOT NULL, timestamp INTEGER NOT NULL, source TEXT, target TEXT)",
+            )
+            .unwrap();
+        // An older log gains the column
+        db.init_event_log().unwrap();
+
+        db.insert_event(&event("request.ping", 100, "frontend").with_correlation_id("req-7"))
+            .unwrap();
+        let stored = db.query_events(&EventQuery::default()).unwrap();
+        assert_eq!(stored[0].correlation_id.as_deref(), Some("req-7"));
+
+        db.get_connection()
+            .unwrap()
+            .execute("UPDATE event_log SET payload = '{broken'", [])
+            .unwrap();
+        let err = db.query_events(&EventQuery::default()).unwrap_err();
+        assert!(matches!(err, AppError::Database(_)));
+    }
+}
@@ -2,7 +2,32 @@
 // Database module - SQLite integration with raw query support
 
 pub mod connection;
+pub mod events;
 pub mod models;
 pub mod users;
 
 pub use connection::Database;
+
+use log::info;
+
+use crate::core::error::AppResult;
+use crate::core::infrastructure::config::AppConfig;
+use crate::core::infrastructure::di::{Container, Dependency};
+
+/// Wire the database into the container; it is opened from `AppConfig` on first resolve
+pub fn register(container: &Container) -> AppResult<()> {
+    container.register_factory(|container| {
+        let config = container.resolve_arc::<AppConfig>()?;
+        info!("Database path: {}", config.get_db_path());
+
+        let db = Database::new(config.get_db_path())?;
+        db.init()?;
+        if config.should_create_sample_data() {
+            db.insert_sample_data()?;
+            info!("Sample data created (if not exists)");
+        }
+        info!("Database initialized successfully");
+        Ok(db)
+    })?;
+    container.declare_dependencies::<Database>(vec![Dependency::on::<AppConfig>()])
+}
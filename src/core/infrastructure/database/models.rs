@@ -73,3 +73,15 @@ pub struct Product {
     pub category: String,
     pub stock: i64,
 }
+
+/// Event record from the durable event log
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StoredEvent {
+    pub id: i64,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub timestamp: i64,
+    pub source: Option<String>,
+    pub target: Option<String>,
+    pub correlation_id: Option<String>,
+}
@@ -1,37 +1,271 @@
 #![allow(dead_code)]
 use std::any::{Any, TypeId};
+use std::cell::RefCell;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
 
 use crate::core::error::{AppError, AppResult, ErrorValue, ErrorCode, ToAppResult};
 
+type Instance = Arc<dyn Any + Send + Sync>;
+type Factory = Arc<dyn Fn(&Container) -> AppResult<Instance> + Send + Sync>;
+
+/// How a registered service is produced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Lifetime {
+    /// A ready-made instance
+    Instance,
+    /// Built by its factory on first resolve, then shared
+    Lazy,
+    /// Built by its factory on every resolve
+    Transient,
+    /// Built by its factory once per scope (window, request)
+    Scoped,
+}
+
+type Slot = Arc<Mutex<Option<Instance>>>;
+
+enum Registration {
+    Instance(Instance),
+    /// The slot is locked while the factory runs so the service is built once
+    Lazy {
+        factory: Factory,
+        slot: Slot,
+    },
+    Transient(Factory),
+    Scoped(Factory),
+}
+
+impl Registration {
+    fn lifetime(&self) -> Lifetime {
+        match self {
+            Registration::Instance(_) => Lifetime::Instance,
+            Registration::Lazy { .. } => Lifetime::Lazy,
+            Registration::Transient(_) => Lifetime::Transient,
+            Registration::Scoped(_) => Lifetime::Scoped,
+        }
+    }
+}
+
+/// A registration with what introspection and graph validation need
+struct Registered {
+    registration: Registration,
+    type_name: &'static str,
+    dependencies: Vec<Dependency>,
+}
+
+/// A service another service needs, declared with `declare_dependencies`
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    key: ServiceKey,
+    label: String,
+}
+
+impl Dependency {
+    pub fn on<T: 'static>() -> Self {
+        Self {
+            key: ServiceKey::of::<T>(None),
+            label: service_label::<T>(None),
+        }
+    }
+
+    pub fn named<T: 'static>(name: &str) -> Self {
+        Self {
+            key: ServiceKey::of::<T>(Some(name)),
+            label: service_label::<T>(Some(name)),
+        }
+    }
+
+    /// A trait implementation registered with `register_trait`
+    pub fn on_trait<T: ?Sized + 'static>() -> Self {
+        Self::on::<Arc<T>>()
+    }
+}
+
+/// One registration as listed by `describe`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceInfo {
+    pub type_name: String,
+    pub name: Option<String>,
+    pub lifetime: Lifetime,
+    /// Whether an instance exists; always false for transients
+    pub instantiated: bool,
+    pub dependencies: Vec<String>,
+    /// 0 for the described container, 1 for its parent, ...
+    pub depth: usize,
+}
+
+/// A registration slot: the service type plus an optional name such as `"archive"`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ServiceKey {
+    type_id: TypeId,
+    name: Option<String>,
+}
+
+impl ServiceKey {
+    fn of<T: ?Sized + 'static>(name: Option<&str>) -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            name: name.map(str::to_string),
+        }
+    }
+}
+
+/// `Database` or `Database["archive"]`, for error messages
+fn service_label<T: ?Sized>(name: Option<&str>) -> String {
+    label(std::any::type_name::<T>(), name)
+}
+
+fn label(type_name: &str, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("{}[\"{}\"]", type_name, name),
+        None => type_name.to_string(),
+    }
+}
+
+thread_local! {
+    /// Services being built on this thread, to report dependency cycles
+    static RESOLVING: RefCell<Vec<(ServiceKey, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pops the service off the resolution stack even when its factory fails
+struct ResolveGuard;
+
+impl ResolveGuard {
+    fn enter(key: &ServiceKey, label: &str) -> AppResult<Self> {
+        RESOLVING.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            if let Some(start) = stack.iter().position(|(k, _)| k == key) {
+                let chain: Vec<&str> = stack[start..]
+                    .iter()
+                    .map(|(_, label)| label.as_str())
+                    .chain(std::iter::once(label))
+                    .collect();
+                return Err(AppError::DependencyInjection(
+                    ErrorValue::new(ErrorCode::InternalError, "Circular service dependency")
+                        .with_details(chain.join(" -> "))
+                        .with_context("service", label),
+                ));
+            }
+            stack.push((key.clone(), label.to_string()));
+            Ok(ResolveGuard)
+        })
+    }
+}
+
+impl Drop for ResolveGuard {
+    fn drop(&mut self) {
+        RESOLVING.with(|stack| {
+            stack.borrow_mut().pop();
+        });
+    }
+}
+
+/// Services keyed by type and optional name
+///
+/// Trait objects are registered as `Arc<dyn Trait>` (see `register_trait`), so
+/// a domain trait can be backed by SQLite in the app and by a fake in tests.
+///
+/// A scope is a child container: it resolves its own registrations first, then
+/// its parent's. Scoped services are built once per scope and dropped with it.
 pub struct Container {
-    services: Mutex<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+    services: Mutex<HashMap<ServiceKey, Registered>>,
+    parent: Option<Arc<Container>>,
+    /// Instances of the parents' scoped registrations built for this scope
+    scoped: Mutex<HashMap<ServiceKey, Slot>>,
 }
 
 impl Container {
     pub fn new() -> Self {
         Self {
             services: Mutex::new(HashMap::new()),
+            parent: None,
+            scoped: Mutex::new(HashMap::new()),
         }
     }
 
-    pub fn register<T: 'static + Send + Sync>(&self, instance: T) -> AppResult<()> {
-        let type_id = TypeId::of::<T>();
-        let mut services = self
-            .services
+    /// Create a child scope that inherits every registration of this container
+    pub fn create_scope(self: &Arc<Self>) -> Arc<Container> {
+        Arc::new(Self {
+            parent: Some(Arc::clone(self)),
+            ..Self::new()
+        })
+    }
+
+    pub fn parent(&self) -> Option<&Arc<Container>> {
+        self.parent.as_ref()
+    }
+
+    /// This container followed by its parents, innermost first
+    fn chain(&self) -> impl Iterator<Item = &Container> {
+        std::iter::successors(Some(self), |container| container.parent.as_deref())
+    }
+
+    fn lock(&self, operation: &str) -> AppResult<MutexGuard<'_, HashMap<ServiceKey, Registered>>> {
+        self.services
             .lock()
             .map_err(|e| {
                 AppError::LockPoisoned(
                     ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI container lock")
                         .with_cause(e.to_string())
-                        .with_context("operation", "register")
+                        .with_context("operation", operation)
                 )
-            })?;
-        services.insert(type_id, Arc::new(instance));
+            })
+    }
+
+    fn insert<T: 'static>(
+        &self,
+        name: Option<&str>,
+        registration: Registration,
+        operation: &str,
+    ) -> AppResult<()> {
+        self.lock(operation)?.insert(
+            ServiceKey::of::<T>(name),
+            Registered {
+                registration,
+                type_name: std::any::type_name::<T>(),
+                dependencies: Vec::new(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Declare the services `T`'s factory resolves, for `validate` and `describe`
+    pub fn declare_dependencies<T: 'static>(&self, dependencies: Vec<Dependency>) -> AppResult<()> {
+        self.set_dependencies(ServiceKey::of::<T>(None), service_label::<T>(None), dependencies)
+    }
+
+    pub fn declare_named_dependencies<T: 'static>(
+        &self,
+        name: &str,
+        dependencies: Vec<Dependency>,
+    ) -> AppResult<()> {
+        self.set_dependencies(
+            ServiceKey::of::<T>(Some(name)),
+            service_label::<T>(Some(name)),
+            dependencies,
+        )
+    }
+
+    fn set_dependencies(&self, key: ServiceKey, label: String, dependencies: Vec<Dependency>) -> AppResult<()> {
+        let mut services = self.lock("declare_dependencies")?;
+        let Some(registered) = services.get_mut(&key) else {
+            return Err(AppError::NotFound(ErrorValue::new(
+                ErrorCode::ResourceNotFound,
+                format!("Service {} not found in container", label),
+            )));
+        };
+        registered.dependencies = dependencies;
         Ok(())
     }
 
+    pub fn register<T: 'static + Send + Sync>(&self, instance: T) -> AppResult<()> {
+        self.insert::<T>(None, Registration::Instance(Arc::new(instance)), "register")
+    }
+
+    /// Register a ready-made instance shared by every resolve
     pub fn register_singleton<T>(&self, service: T) -> AppResult<()>
     where
         T: Send + Sync + 'static,
@@ -39,63 +273,372 @@ impl Container {
         self.register(service)
     }
 
-    pub fn resolve<T: 'static + Clone>(&self) -> AppResult<T> {
-        let type_id = TypeId::of::<T>();
-        let services = self
-            .services
-            .lock()
-            .map_err(|e| {
-                AppError::LockPoisoned(
-                    ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI container lock")
-                        .with_cause(e.to_string())
-                        .with_context("operation", "resolve")
-                )
-            })?;
+    /// Register a factory run on the first resolve; later resolves share its result
+    ///
+    /// The factory receives the container to resolve its own dependencies.
+    pub fn register_factory<T, F>(&self, factory: F) -> AppResult<()>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> AppResult<T> + Send + Sync + 'static,
+    {
+        self.insert::<T>(None, lazy(factory), "register_factory")
+    }
+
+    /// Register a factory run on every resolve, so each caller gets a fresh instance
+    pub fn register_transient<T, F>(&self, factory: F) -> AppResult<()>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> AppResult<T> + Send + Sync + 'static,
+    {
+        self.insert::<T>(None, Registration::Transient(erase(factory)), "register_transient")
+    }
+
+    /// Register a factory run once in each scope that resolves the service
+    ///
+    /// The factory receives the resolving scope, so it can use values registered
+    /// there, such as the window's session.
+    pub fn register_scoped<T, F>(&self, factory: F) -> AppResult<()>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> AppResult<T> + Send + Sync + 'static,
+    {
+        self.insert::<T>(None, Registration::Scoped(erase(factory)), "register_scoped")
+    }
+
+    /// Register one of several instances of a type, such as the `"archive"` database
+    ///
+    /// Named registrations are separate from the unnamed one of the same type.
+    pub fn register_named<T: 'static + Send + Sync>(&self, name: &str, instance: T) -> AppResult<()> {
+        self.insert::<T>(Some(name), Registration::Instance(Arc::new(instance)), "register_named")
+    }
+
+    /// Register a named factory run on its first resolve
+    pub fn register_named_factory<T, F>(&self, name: &str, factory: F) -> AppResult<()>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> AppResult<T> + Send + Sync + 'static,
+    {
+        self.insert::<T>(Some(name), lazy(factory), "register_named_factory")
+    }
+
+    /// Register an implementation of a trait, resolved with `resolve_trait::<dyn Trait>()`
+    pub fn register_trait<T>(&self, service: Arc<T>) -> AppResult<()>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.register::<Arc<T>>(service)
+    }
+
+    /// Register a factory building a trait implementation on its first resolve
+    pub fn register_trait_factory<T, F>(&self, factory: F) -> AppResult<()>
+    where
+        T: ?Sized + Send + Sync + 'static,
+        F: Fn(&Container) -> AppResult<Arc<T>> + Send + Sync + 'static,
+    {
+        self.register_factory::<Arc<T>, F>(factory)
+    }
+
+    /// Look up or build a service; container locks are released before factories run
+    fn instance<T: 'static>(&self, name: Option<&str>, operation: &str) -> AppResult<Instance> {
+        let key = ServiceKey::of::<T>(name);
+        let label = service_label::<T>(name);
+
+        // The first container in the chain with a registration decides
+        let mut found = None;
+        for container in self.chain() {
+            let services = container.lock(operation)?;
+            let pending = match services.get(&key).map(|r| &r.registration) {
+                Some(Registration::Instance(instance)) => return Ok(Arc::clone(instance)),
+                Some(Registration::Lazy { factory, slot }) => {
+                    (Arc::clone(factory), Some(Arc::clone(slot)), container)
+                }
+                Some(Registration::Transient(factory)) => (Arc::clone(factory), None, self),
+                Some(Registration::Scoped(factory)) => {
+                    (Arc::clone(factory), Some(self.scoped_slot(&key, operation)?), self)
+                }
+                None => continue,
+            };
+            found = Some(pending);
+            break;
+        }
+        let Some((factory, slot, owner)) = found else {
+            return Err(AppError::NotFound(ErrorValue::new(
+                ErrorCode::ResourceNotFound,
+                format!("Service {} not found in container", label),
+            )));
+        };
+
+        let _guard = ResolveGuard::enter(&key, &label)?;
+        let Some(slot) = slot else {
+            return factory(owner);
+        };
+        let mut slot = slot.lock().map_err(|e| {
+            AppError::LockPoisoned(
+                ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI service lock")
+                    .with_cause(e.to_string())
+                    .with_context("service", label.clone())
+            )
+        })?;
+        if let Some(instance) = slot.as_ref() {
+            return Ok(Arc::clone(instance));
+        }
+        let instance = factory(owner)?;
+        *slot = Some(Arc::clone(&instance));
+        Ok(instance)
+    }
 
-        services
-            .get(&type_id)
-            .and_then(|service| service.downcast_ref::<T>().cloned())
+    /// This scope's slot for a scoped service
+    fn scoped_slot(&self, key: &ServiceKey, operation: &str) -> AppResult<Slot> {
+        let mut scoped = self.scoped.lock().map_err(|e| {
+            AppError::LockPoisoned(
+                ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI scope lock")
+                    .with_cause(e.to_string())
+                    .with_context("operation", operation)
+            )
+        })?;
+        Ok(Arc::clone(scoped.entry(key.clone()).or_default()))
+    }
+
+    fn downcast<T: 'static + Send + Sync>(instance: Instance, name: Option<&str>) -> AppResult<Arc<T>> {
+        instance
+            .downcast::<T>()
+            .ok()
             .to_app_error(&format!(
                 "Service {} not found in container",
-                std::any::type_name::<T>()
+                service_label::<T>(name)
             ))
     }
 
-    pub fn resolve_arc<T: 'static + Send + Sync>(&self) -> AppResult<Arc<T>> {
-        let type_id = TypeId::of::<T>();
-        let services = self
-            .services
-            .lock()
-            .map_err(|e| {
-                AppError::LockPoisoned(
-                    ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI container lock")
-                        .with_cause(e.to_string())
-                        .with_context("operation", "resolve_arc")
-                )
-            })?;
-
-        services
-            .get(&type_id)
-            .and_then(|service| service.clone().downcast::<T>().ok())
+    pub fn resolve<T: 'static + Clone>(&self) -> AppResult<T> {
+        self.instance::<T>(None, "resolve")?
+            .downcast_ref::<T>()
+            .cloned()
             .to_app_error(&format!(
                 "Service {} not found in container",
                 std::any::type_name::<T>()
             ))
     }
 
+    pub fn resolve_arc<T: 'static + Send + Sync>(&self) -> AppResult<Arc<T>> {
+        Self::downcast(self.instance::<T>(None, "resolve_arc")?, None)
+    }
+
+    pub fn resolve_named<T: 'static + Clone + Send + Sync>(&self, name: &str) -> AppResult<T> {
+        Ok(self.resolve_named_arc::<T>(name)?.as_ref().clone())
+    }
+
+    pub fn resolve_named_arc<T: 'static + Send + Sync>(&self, name: &str) -> AppResult<Arc<T>> {
+        Self::downcast(self.instance::<T>(Some(name), "resolve_named")?, Some(name))
+    }
+
+    /// Resolve the implementation registered for a trait, e.g. `dyn UserRepository`
+    pub fn resolve_trait<T>(&self) -> AppResult<Arc<T>>
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        self.resolve::<Arc<T>>()
+    }
+
     pub fn has<T: 'static>(&self) -> AppResult<bool> {
+        Ok(self.lifetime::<T>()?.is_some())
+    }
+
+    pub fn has_named<T: 'static>(&self, name: &str) -> AppResult<bool> {
+        self.lookup(&ServiceKey::of::<T>(Some(name)), "has_named")
+            .map(|lifetime| lifetime.is_some())
+    }
+
+    /// How `T` is registered here or in a parent, if it is
+    pub fn lifetime<T: 'static>(&self) -> AppResult<Option<Lifetime>> {
+        self.lookup(&ServiceKey::of::<T>(None), "has")
+    }
+
+    fn lookup(&self, key: &ServiceKey, operation: &str) -> AppResult<Option<Lifetime>> {
+        for container in self.chain() {
+            if let Some(registered) = container.lock(operation)?.get(key) {
+                return Ok(Some(registered.registration.lifetime()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Names registered for `T` here or in a parent, sorted
+    pub fn names<T: 'static>(&self) -> AppResult<Vec<String>> {
         let type_id = TypeId::of::<T>();
-        let services = self
-            .services
-            .lock()
-            .map_err(|e| {
-                AppError::LockPoisoned(
-                    ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire DI container lock")
-                        .with_cause(e.to_string())
-                        .with_context("operation", "has")
-                )
-            })?;
-        Ok(services.contains_key(&type_id))
+        let mut names = Vec::new();
+        for container in self.chain() {
+            names.extend(
+                container
+                    .lock("names")?
+                    .keys()
+                    .filter(|key| key.type_id == type_id)
+                    .filter_map(|key| key.name.clone()),
+            );
+        }
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+}
+
+impl Container {
+    /// Every registration visible from this container, parents' included
+    pub fn describe(&self) -> AppResult<Vec<ServiceInfo>> {
+        let mut seen = std::collections::HashSet::new();
+        let mut infos = Vec::new();
+        for (depth, container) in self.chain().enumerate() {
+            let services = container.lock("describe")?;
+            for (key, registered) in services.iter() {
+                // An inner registration hides the same key further up
+                if !seen.insert(key.clone()) {
+                    continue;
+                }
+                let instantiated = match &registered.registration {
+                    Registration::Instance(_) => true,
+                    Registration::Lazy { slot, .. } => is_filled(slot),
+                    Registration::Transient(_) => false,
+                    Registration::Scoped(_) => self
+                        .scoped
+                        .lock()
+                        .map(|scoped| scoped.get(key).is_some_and(is_filled))
+                        .unwrap_or(false),
+                };
+                infos.push(ServiceInfo {
+                    type_name: registered.type_name.to_string(),
+                    name: key.name.clone(),
+                    lifetime: registered.registration.lifetime(),
+                    instantiated,
+                    dependencies: registered.dependencies.iter().map(|d| d.label.clone()).collect(),
+                    depth,
+                });
+            }
+        }
+        infos.sort_by(|a, b| (a.depth, &a.type_name, &a.name).cmp(&(b.depth, &b.type_name, &b.name)));
+        Ok(infos)
+    }
+
+    /// Check the declared dependency graph without building anything
+    ///
+    /// Missing services and cycles are reported together in one
+    /// `DependencyInjection` error: `details` lists every problem, and the
+    /// `missing` and `cycles` context entries hold them by kind.
+    pub fn validate(&self) -> AppResult<()> {
+        // Innermost registration wins, as when resolving
+        let mut graph: HashMap<ServiceKey, (String, Vec<Dependency>)> = HashMap::new();
+        for container in self.chain() {
+            for (key, registered) in container.lock("validate")?.iter() {
+                graph.entry(key.clone()).or_insert_with(|| {
+                    (
+                        label(registered.type_name, key.name.as_deref()),
+                        registered.dependencies.clone(),
+                    )
+                });
+            }
+        }
+
+        let mut keys: Vec<&ServiceKey> = graph.keys().collect();
+        keys.sort_by(|a, b| graph[*a].0.cmp(&graph[*b].0));
+
+        let mut missing = Vec::new();
+        for key in &keys {
+            let (service, dependencies) = &graph[*key];
+            for dependency in dependencies {
+                if !graph.contains_key(&dependency.key) {
+                    missing.push(format!("{} needs {}", service, dependency.label));
+                }
+            }
+        }
+
+        let mut cycles = Vec::new();
+        let mut done = std::collections::HashSet::new();
+        for key in &keys {
+            let mut path = Vec::new();
+            find_cycles(key, &graph, &mut path, &mut done, &mut cycles);
+        }
+
+        if missing.is_empty() && cycles.is_empty() {
+            return Ok(());
+        }
+        let problems: Vec<String> = missing
+            .iter()
+            .map(|m| format!("missing: {}", m))
+            .chain(cycles.iter().map(|c| format!("cycle: {}", c)))
+            .collect();
+        let mut error = ErrorValue::new(ErrorCode::ValidationFailed, "Invalid service dependency graph")
+            .with_details(problems.join("; "));
+        if !missing.is_empty() {
+            error = error.with_context("missing", missing.join("; "));
+        }
+        if !cycles.is_empty() {
+            error = error.with_context("cycles", cycles.join("; "));
+        }
+        Err(AppError::DependencyInjection(error))
+    }
+}
+
+fn is_filled(slot: &Slot) -> bool {
+    // A slot locked by a running factory is not built yet
+    slot.try_lock().map(|slot| slot.is_some()).unwrap_or(false)
+}
+
+/// Depth-first walk recording each cycle once, as `A -> B -> A`
+fn find_cycles<'a>(
+    key: &'a ServiceKey,
+    graph: &'a HashMap<ServiceKey, (String, Vec<Dependency>)>,
+    path: &mut Vec<&'a ServiceKey>,
+    done: &mut std::collections::HashSet<&'a ServiceKey>,
+    cycles: &mut Vec<String>,
+) {
+    if done.contains(key) {
+        return;
+    }
+    if let Some(start) = path.iter().position(|k| *k == key) {
+        let chain: Vec<&str> = path[start..]
+            .iter()
+            .chain(std::iter::once(&key))
+            .map(|k| graph[*k].0.as_str())
+            .collect();
+        cycles.push(chain.join(" -> "));
+        return;
+    }
+    let Some((_, dependencies)) = graph.get(key) else {
+        return;
+    };
+    path.push(key);
+    for dependency in dependencies {
+        if let Some((dep_key, _)) = graph.get_key_value(&dependency.key) {
+            find_cycles(dep_key, graph, path, done, cycles);
+        }
+    }
+    path.pop();
+    done.insert(key);
+}
+
+fn lazy<T, F>(factory: F) -> Registration
+where
+    T: Send + Sync + 'static,
+    F: Fn(&Container) -> AppResult<T> + Send + Sync + 'static,
+{
+    Registration::Lazy {
+        factory: erase(factory),
+        slot: Arc::new(Mutex::new(None)),
+    }
+}
+
+fn erase<T, F>(factory: F) -> Factory
+where
+    T: Send + Sync + 'static,
+    F: Fn(&Container) -> AppResult<T> + Send + Sync + 'static,
+{
+    Arc::new(move |container| Ok(Arc::new(factory(container)?) as Instance))
+}
+
+impl std::fmt::Debug for Container {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Container")
+            .field("services", &self.services.lock().map(|s| s.len()).unwrap_or(0))
+            .field("depth", &(self.chain().count() - 1))
+            .finish()
     }
 }
 
@@ -107,10 +650,15 @@ impl Default for Container {
 
 use std::sync::OnceLock;
 
-static GLOBAL_CONTAINER: OnceLock<Container> = OnceLock::new();
+static GLOBAL_CONTAINER: OnceLock<Arc<Container>> = OnceLock::new();
 
 pub fn get_container() -> &'static Container {
-    GLOBAL_CONTAINER.get_or_init(|| Container::new())
+    root_container().as_ref()
+}
+
+/// The root container, for creating scopes
+pub fn root_container() -> &'static Arc<Container> {
+    GLOBAL_CONTAINER.get_or_init(|| Arc::new(Container::new()))
 }
 
 pub fn init_container() -> AppResult<()> {
@@ -143,4 +691,228 @@ mod tests {
         let resolved: Arc<String> = container.resolve_arc().expect("Failed to resolve");
         assert_eq!(*resolved, "test");
     }
+
+    #[test]
+    fn test_factory_is_lazy_and_resolves_dependencies() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Greeter {
+            greeting: String,
+        }
+
+        let built = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&built);
+        let container = Container::new();
+        container
+            .register_factory(move |c: &Container| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(Greeter {
+                    greeting: format!("Hello, {}", c.resolve::<String>()?),
+                })
+            })
+            .expect("Failed to register");
+        container.register(String::from("Ada")).expect("Failed to register");
+        assert_eq!(built.load(Ordering::SeqCst), 0);
+        assert_eq!(container.lifetime::<Greeter>().unwrap(), Some(Lifetime::Lazy));
+
+        let first: Arc<Greeter> = container.resolve_arc().expect("Failed to resolve");
+        let second: Arc<Greeter> = container.resolve_arc().expect("Failed to resolve");
+        assert_eq!(first.greeting, "Hello, Ada");
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(built.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_transient_builds_fresh_instances() {
+        let container = Container::new();
+        container
+            .register_transient(|_: &Container| Ok(Vec::<u8>::new()))
+            .expect("Failed to register");
+
+        let first: Arc<Vec<u8>> = container.resolve_arc().expect("Failed to resolve");
+        let second: Arc<Vec<u8>> = container.resolve_arc().expect("Failed to resolve");
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(container.lifetime::<Vec<u8>>().unwrap(), Some(Lifetime::Transient));
+    }
+
+    #[test]
+    fn test_factory_errors_and_cycles() {
+        let container = Container::new();
+        container
+            .register_factory(|c: &Container| Ok(c.resolve::<u32>()? as u64))
+            .expect("Failed to register");
+        container
+            .register_factory(|c: &Container| Ok(c.resolve::<u64>()? as u32))
+            .expect("Failed to register");
+
+        match container.resolve::<u64>() {
+            Err(AppError::DependencyInjection(e)) => {
+                assert_eq!(e.details.as_deref(), Some("u64 -> u32 -> u64"));
+            }
+            other => panic!("Expected a cycle error, got {:?}", other),
+        }
+
+        // A failed build is not cached; the next resolve tries again
+        container.register(7u32).expect("Failed to register");
+        assert_eq!(container.resolve::<u64>().expect("Failed to resolve"), 7);
+        assert!(matches!(container.resolve::<i8>(), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_trait_registration_swaps_in_a_fake() {
+        use crate::core::domain::entities::User;
+        use crate::core::domain::traits::UserRepository;
+
+        #[derive(Default)]
+        struct InMemoryUsers {
+            users: Mutex<Vec<User>>,
+        }
+
+        impl UserRepository for InMemoryUsers {
+            fn create(&self, user: &User) -> anyhow::Result<i64> {
+                let mut users = self.users.lock().unwrap();
+                users.push(user.clone());
+                Ok(users.len() as i64)
+            }
+            fn get_by_id(&self, id: i64) -> anyhow::Result<Option<User>> {
+                Ok(self.users.lock().unwrap().get(id as usize - 1).cloned())
+            }
+            fn get_all(&self) -> anyhow::Result<Vec<User>> {
+                Ok(self.users.lock().unwrap().clone())
+            }
+            fn update(&self, _user: &User) -> anyhow::Result<()> {
+                Ok(())
+            }
+            fn delete(&self, _id: i64) -> anyhow::Result<()> {
+                Ok(())
+            }
+        }
+
+        let container = Container::new();
+        container
+            .register_trait::<dyn UserRepository>(Arc::new(InMemoryUsers::default()))
+            .expect("Failed to register");
+
+        let repo = container.resolve_trait::<dyn UserRepository>().expect("Failed to resolve");
+        let now = chrono::Utc::now();
+        let id = repo
+            .create(&User {
+                id: None,
+                name: "Ada".into(),
+                email: "ada@example.com".into(),
+                created_at: now,
+                updated_at: now,
+            })
+            .unwrap();
+        let again = container.resolve_trait::<dyn UserRepository>().expect("Failed to resolve");
+        assert_eq!(again.get_by_id(id).unwrap().unwrap().name, "Ada");
+        assert!(container.resolve_trait::<dyn crate::core::domain::traits::ConfigRepository>().is_err());
+    }
+
+    #[test]
+    fn test_named_registrations() {
+        let container = Container::new();
+        container.register(String::from("default")).expect("Failed to register");
+        container.register_named("main", String::from("app.db")).expect("Failed to register");
+        container
+            .register_named_factory("archive", |c: &Container| {
+                Ok(c.resolve_named::<String>("main")?.replace("app", "archive"))
+            })
+            .expect("Failed to register");
+
+        assert_eq!(container.resolve::<String>().unwrap(), "default");
+        assert_eq!(container.resolve_named::<String>("archive").unwrap(), "archive.db");
+        assert_eq!(container.names::<String>().unwrap(), vec!["archive", "main"]);
+        assert!(container.has_named::<String>("main").unwrap());
+
+        let err = container.resolve_named::<String>("backup").unwrap_err();
+        assert!(err.to_string().contains("[\"backup\"]"));
+    }
+
+    #[test]
+    fn test_scopes_inherit_and_isolate() {
+        #[derive(Clone)]
+        struct Session(String);
+        struct Greeting(String);
+
+        let root = Arc::new(Container::new());
+        root.register(String::from("shared")).expect("Failed to register");
+        root.register_scoped(|scope: &Container| {
+            Ok(Greeting(format!("Hello, {}", scope.resolve::<Session>()?.0)))
+        })
+        .expect("Failed to register");
+
+        let window = root.create_scope();
+        window.register(Session("Ada".into())).expect("Failed to register");
+        let request = window.create_scope();
+        assert_eq!(request.resolve::<String>().unwrap(), "shared");
+        assert_eq!(request.lifetime::<Greeting>().unwrap(), Some(Lifetime::Scoped));
+
+        // Built once per scope, with the resolving scope's session
+        let first: Arc<Greeting> = window.resolve_arc().expect("Failed to resolve");
+        let again: Arc<Greeting> = window.resolve_arc().expect("Failed to resolve");
+        let inner: Arc<Greeting> = request.resolve_arc().expect("Failed to resolve");
+        assert_eq!(first.0, "Hello, Ada");
+        assert!(Arc::ptr_eq(&first, &again));
+        assert!(!Arc::ptr_eq(&first, &inner));
+
+        // Scope registrations never leak into the root
+        assert!(!root.has::<Session>().unwrap());
+        assert!(root.resolve_arc::<Greeting>().is_err());
+
+        let weak = Arc::downgrade(&inner);
+        drop((inner, request));
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_describe_lists_registrations() {
+        let root = Arc::new(Container::new());
+        root.register(String::from("app.db")).unwrap();
+        root.register_factory(|c: &Container| Ok(c.resolve::<String>()?.len() as u64)).unwrap();
+        root.declare_dependencies::<u64>(vec![Dependency::on::<String>()]).unwrap();
+        root.register_transient(|_: &Container| Ok(0u8)).unwrap();
+        let scope = root.create_scope();
+        scope.register_named("main", 1i32).unwrap();
+        assert!(root.declare_dependencies::<i8>(vec![]).is_err());
+
+        let lazy = |infos: &[ServiceInfo]| infos.iter().find(|i| i.type_name == "u64").unwrap().clone();
+        assert!(!lazy(&root.describe().unwrap()).instantiated);
+        root.resolve::<u64>().unwrap();
+        let infos = scope.describe().unwrap();
+        let built = lazy(&infos);
+        assert!(built.instantiated);
+        assert_eq!(built.lifetime, Lifetime::Lazy);
+        assert_eq!(built.dependencies, vec!["alloc::string::String"]);
+        assert_eq!(built.depth, 1);
+        assert_eq!(infos[0].type_name, "i32");
+        assert_eq!(infos[0].name.as_deref(), Some("main"));
+        assert!(!infos.iter().find(|i| i.type_name == "u8").unwrap().instantiated);
+    }
+
+    #[test]
+    fn test_validate_reports_missing_services_and_cycles() {
+        let container = Container::new();
+        container.register(1u8).unwrap();
+        container.register_factory(|c: &Container| Ok(c.resolve::<u8>()? as u16)).unwrap();
+        container.declare_dependencies::<u16>(vec![Dependency::on::<u8>()]).unwrap();
+        assert!(container.validate().is_ok());
+
+        container.register_factory(|c: &Container| Ok(c.resolve::<u64>()? as u32)).unwrap();
+        container.register_factory(|c: &Container| Ok(c.resolve::<u32>()? as u64)).unwrap();
+        container.declare_dependencies::<u32>(vec![Dependency::on::<u64>()]).unwrap();
+        container
+            .declare_dependencies::<u64>(vec![Dependency::on::<u32>(), Dependency::named::<String>("dsn")])
+            .unwrap();
+
+        match container.validate() {
+            Err(AppError::DependencyInjection(e)) => {
+                assert_eq!(e.code, ErrorCode::ValidationFailed);
+                let context = e.context.unwrap();
+                assert_eq!(context["missing"], "u64 needs alloc::string::String[\"dsn\"]");
+                assert_eq!(context["cycles"], "u32 -> u64 -> u32");
+            }
+            other => panic!("Expected a graph error, got {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,78 @@
+// src/core/infrastructure/event_bus/dead_letter.rs
+// Events whose listener failed or panicked, kept for inspection
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::EventData;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub event: EventData,
+    pub subscription_id: u64,
+    /// Topic or pattern the failing listener subscribed to
+    pub subscription: String,
+    pub error: String,
+    pub panicked: bool,
+    /// Milliseconds since the Unix epoch
+    pub failed_at: i64,
+}
+
+impl DeadLetter {
+    pub fn new(event: EventData, subscription_id: u64, subscription: &str, error: String, panicked: bool) -> Self {
+        Self {
+            event,
+            subscription_id,
+            subscription: subscription.to_string(),
+            error,
+            panicked,
+            failed_at: Utc::now().timestamp_millis(),
+        }
+    }
+}
+
+/// Ring of the most recent dead letters
+pub(super) struct DeadLetterQueue {
+    letters: Mutex<VecDeque<DeadLetter>>,
+    capacity: usize,
+}
+
+impl DeadLetterQueue {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            letters: Mutex::new(VecDeque::new()),
+            capacity,
+        }
+    }
+
+    pub fn push(&self, letter: DeadLetter) {
+        if let Ok(mut letters) = self.letters.lock() {
+            letters.push_back(letter);
+            while letters.len() > self.capacity {
+                letters.pop_front();
+            }
+        }
+    }
+
+    /// Dead letters, oldest first
+    pub fn list(&self) -> Vec<DeadLetter> {
+        self.letters
+            .lock()
+            .map(|letters| letters.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.letters.lock().map(|letters| letters.len()).unwrap_or(0)
+    }
+
+    pub fn clear(&self) -> usize {
+        self.letters
+            .lock()
+            .map(|mut letters| letters.drain(..).count())
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,153 @@
+// src/core/infrastructure/event_bus/history.rs
+// Bounded event history with a per-type index
+
+use std::collections::{HashMap, VecDeque};
+
+use super::topic;
+use super::EventData;
+
+/// Ring of recent events; `by_type` holds the sequence numbers of each event type
+pub(super) struct EventHistory {
+    events: VecDeque<EventData>,
+    /// Sequence number of `events[0]`
+    first_seq: u64,
+    by_type: HashMap<String, VecDeque<u64>>,
+    capacity: usize,
+}
+
+impl EventHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::new(),
+            first_seq: 0,
+            by_type: HashMap::new(),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, event: EventData) {
+        let seq = self.first_seq + self.events.len() as u64;
+        self.by_type
+            .entry(event.event_type.clone())
+            .or_default()
+            .push_back(seq);
+        self.events.push_back(event);
+
+        while self.events.len() > self.capacity {
+            self.evict_oldest();
+        }
+    }
+
+    fn evict_oldest(&mut self) {
+        let Some(oldest) = self.events.pop_front() else {
+            return;
+        };
+        // The oldest event is always at the front of its type's index
+        if let Some(seqs) = self.by_type.get_mut(&oldest.event_type) {
+            seqs.pop_front();
+            if seqs.is_empty() {
+                self.by_type.remove(&oldest.event_type);
+            }
+        }
+        self.first_seq += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.first_seq += self.events.len() as u64;
+        self.events.clear();
+        self.by_type.clear();
+    }
+
+    /// Events matching an exact type or a topic pattern (`None` matches all)
+    ///
+    /// With a limit, the newest events come first; without one, events are in
+    /// publish order.
+    pub fn query(&self, pattern: Option<&str>, limit: Option<usize>) -> Vec<EventData> {
+        let limit = limit.unwrap_or(usize::MAX);
+        let newest_first = limit != usize::MAX;
+
+        let mut seqs: Vec<u64> = match pattern {
+            None | Some("#") => {
+                let start = self.events.len().saturating_sub(limit);
+                (start..self.events.len())
+                    .map(|i| self.first_seq + i as u64)
+                    .collect()
+            }
+            Some(pattern) if !topic::is_pattern(pattern) => self
+                .by_type
+                .get(pattern)
+                .map(|seqs| {
+                    let start = seqs.len().saturating_sub(limit);
+                    seqs.range(start..).copied().collect()
+                })
+                .unwrap_or_default(),
+            Some(pattern) => {
+                let mut seqs: Vec<u64> = self
+                    .by_type
+                    .iter()
+                    .filter(|(event_type, _)| topic::matches(pattern, event_type))
+                    .flat_map(|(_, seqs)| seqs.iter().rev().take(limit).copied())
+                    .collect();
+                seqs.sort_unstable();
+                let start = seqs.len().saturating_sub(limit);
+                seqs.split_off(start)
+            }
+        };
+
+        if newest_first {
+            seqs.reverse();
+        }
+        seqs.into_iter()
+            .filter_map(|seq| self.events.get((seq - self.first_seq) as usize).cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history_with(types: &[&str], capacity: usize) -> EventHistory {
+        let mut history = EventHistory::new(capacity);
+        for (i, event_type) in types.iter().enumerate() {
+            history.push(EventData::new(*event_type, serde_json::json!({ "n": i })));
+        }
+        history
+    }
+
+    fn numbers(events: &[EventData]) -> Vec<i64> {
+        events.iter().map(|e| e.payload["n"].as_i64().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_query_by_type_and_pattern() {
+        let history = history_with(
+            &["user.created", "order.created", "user.deleted", "user.profile.updated"],
+            10,
+        );
+
+        assert_eq!(numbers(&history.query(Some("user.created"), None)), vec![0]);
+        assert_eq!(numbers(&history.query(Some("user.*"), None)), vec![0, 2]);
+        assert_eq!(numbers(&history.query(Some("user.#"), None)), vec![0, 2, 3]);
+        assert_eq!(numbers(&history.query(Some("*.created"), Some(1))), vec![1]);
+        assert_eq!(numbers(&history.query(None, Some(2))), vec![3, 2]);
+        assert_eq!(numbers(&history.query(Some("#"), None)), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_eviction_keeps_index_consistent() {
+        let mut history = history_with(&["a", "b", "a", "b", "a"], 3);
+        assert_eq!(history.len(), 3);
+        assert_eq!(numbers(&history.query(Some("a"), None)), vec![2, 4]);
+        assert_eq!(numbers(&history.query(Some("b"), None)), vec![3]);
+
+        history.clear();
+        history.push(EventData::new("a", serde_json::json!({ "n": 9 })));
+        assert_eq!(numbers(&history.query(Some("a"), None)), vec![9]);
+        assert!(history.query(Some("b"), None).is_empty());
+    }
+}
@@ -0,0 +1,1325 @@
+#![allow(dead_code)]
+// src/core/infrastructure/event_bus/mod.rs
+// In-process pub/sub with hierarchical topics, listeners and an indexed history
+
+mod dead_letter;
+mod history;
+mod queue;
+mod request;
+mod schema;
+mod store;
+pub mod topic;
+mod trace;
+
+pub use dead_letter::DeadLetter;
+pub use queue::{OverflowPolicy, QueueOptions};
+pub use request::REPLY_SUFFIX;
+pub use schema::EventSchemaInfo;
+pub use store::{EventQuery, EventStore, RetentionPolicy};
+pub use trace::{ImportMode, TraceFormat};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, OnceLock, RwLock, Weak};
+use std::thread;
+use std::time::Duration;
+
+use crate::core::error::{AppError, AppResult, ErrorValue, ErrorCode};
+use dead_letter::DeadLetterQueue;
+use history::EventHistory;
+use queue::BoundedQueue;
+use request::PendingRequests;
+use schema::SchemaRegistry;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EventData {
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub timestamp: i64,
+    pub source: Option<String>,
+    /// Endpoint the event is addressed to; only its listeners receive it
+    pub target: Option<String>,
+    /// Links a request to its reply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+impl EventData {
+    pub fn new(event_type: impl Into<String>, payload: serde_json::Value) -> Self {
+        Self {
+            event_type: event_type.into(),
+            payload,
+            timestamp: Utc::now().timestamp_millis(),
+            source: None,
+            target: None,
+            correlation_id: None,
+        }
+    }
+
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EventBusStats {
+    pub total_listeners: usize,
+    pub event_types: Vec<EventTypeInfo>,
+    /// Listener calls that returned normally
+    pub delivered: u64,
+    /// Listener calls that returned an error
+    pub failed: u64,
+    /// Listener calls that panicked
+    pub panicked: u64,
+    /// Events discarded by full subscriber queues
+    pub dropped: u64,
+    /// Events waiting in subscriber queues
+    pub queued: usize,
+    /// Entries currently in the dead-letter queue
+    pub dead_letters: usize,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EventTypeInfo {
+    pub event_type: String,
+    pub listener_count: usize,
+}
+
+/// Callback invoked for every event after it is recorded (e.g. transport push)
+pub type EventObserver = Arc<dyn Fn(&EventData) + Send + Sync>;
+
+/// Rust listener subscribed to one event type
+///
+/// Implemented for every `Fn(&EventData) + Send + Sync` closure; wrap fallible
+/// closures with `fallible` so their errors reach the dead-letter queue.
+pub trait EventListener: Send + Sync {
+    fn on_event(&self, event: &EventData);
+
+    /// Called by the bus; an error sends the event to the dead-letter queue
+    fn handle(&self, event: &EventData) -> AppResult<()> {
+        self.on_event(event);
+        Ok(())
+    }
+}
+
+impl<F> EventListener for F
+where
+    F: Fn(&EventData) + Send + Sync,
+{
+    fn on_event(&self, event: &EventData) {
+        self(event)
+    }
+}
+
+/// Listener built from a closure returning `AppResult<()>`
+pub struct FallibleListener<F>(F);
+
+impl<F> EventListener for FallibleListener<F>
+where
+    F: Fn(&EventData) -> AppResult<()> + Send + Sync,
+{
+    fn on_event(&self, event: &EventData) {
+        if let Err(e) = (self.0)(event) {
+            log::error!("Listener for {} failed: {}", event.event_type, e);
+        }
+    }
+
+    fn handle(&self, event: &EventData) -> AppResult<()> {
+        (self.0)(event)
+    }
+}
+
+pub fn fallible<F>(listener: F) -> FallibleListener<F>
+where
+    F: Fn(&EventData) -> AppResult<()> + Send + Sync,
+{
+    FallibleListener(listener)
+}
+
+/// Where a listener runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DispatchMode {
+    /// On the publishing thread, before `publish` returns
+    Sync,
+    /// On the shared bus worker thread, in publish order
+    Worker,
+    /// On the subscriber's own thread, through a bounded queue
+    Queued(QueueOptions),
+}
+
+/// Delivery counters and dead letters, shared with worker threads
+struct DispatchMetrics {
+    delivered: AtomicU64,
+    failed: AtomicU64,
+    panicked: AtomicU64,
+    dropped: AtomicU64,
+    dead_letters: DeadLetterQueue,
+}
+
+impl DispatchMetrics {
+    fn new() -> Self {
+        Self {
+            delivered: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+            panicked: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            dead_letters: DeadLetterQueue::new(DEAD_LETTER_CAPACITY),
+        }
+    }
+}
+
+const DEAD_LETTER_CAPACITY: usize = 100;
+
+/// Run a listener, isolating panics; failures become dead letters
+fn run_listener(metrics: &DispatchMetrics, target: &DeliveryTarget, event: &EventData) {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| target.listener.handle(event)));
+    let (error, panicked) = match outcome {
+        Ok(Ok(())) => {
+            metrics.delivered.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        Ok(Err(e)) => {
+            metrics.failed.fetch_add(1, Ordering::Relaxed);
+            (e.to_string(), false)
+        }
+        Err(payload) => {
+            metrics.panicked.fetch_add(1, Ordering::Relaxed);
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "listener panicked".to_string());
+            (message, true)
+        }
+    };
+    log::error!(
+        "Listener {} ({}) failed on {}: {}",
+        target.id,
+        target.topic,
+        event.event_type,
+        error
+    );
+    metrics.dead_letters.push(DeadLetter::new(
+        event.clone(),
+        target.id,
+        &target.topic,
+        error,
+        panicked,
+    ));
+}
+
+/// Everything needed to run one subscriber outside the table lock
+#[derive(Clone)]
+struct DeliveryTarget {
+    id: u64,
+    topic: String,
+    mode: DispatchMode,
+    listener: Arc<dyn EventListener>,
+    queue: Option<Arc<BoundedQueue>>,
+}
+
+struct Subscriber {
+    target: DeliveryTarget,
+    /// Endpoint the listener answers for; targeted events only reach matching endpoints
+    endpoint: Option<String>,
+}
+
+impl Subscriber {
+    fn id(&self) -> u64 {
+        self.target.id
+    }
+
+    fn accepts(&self, event: &EventData) -> bool {
+        endpoint_accepts(self.endpoint.as_deref(), event)
+    }
+}
+
+/// Untargeted events reach every endpoint; targeted ones only the matching one
+fn endpoint_accepts(endpoint: Option<&str>, event: &EventData) -> bool {
+    event.target.as_deref().is_none_or(|target| endpoint == Some(target))
+}
+
+impl Drop for Subscriber {
+    fn drop(&mut self) {
+        // Lets the queue thread drain and exit once the subscriber is removed
+        if let Some(queue) = &self.target.queue {
+            queue.close();
+        }
+    }
+}
+
+type SubscriberTable = RwLock<HashMap<String, Vec<Subscriber>>>;
+
+type WorkerJob = (DeliveryTarget, EventData);
+
+/// Handle returned by `EventBus::subscribe`
+///
+/// Dropping the handle leaves the listener subscribed; call `unsubscribe` to remove it.
+#[derive(Debug)]
+pub struct Subscription {
+    id: u64,
+    event_type: String,
+    subscribers: Weak<SubscriberTable>,
+}
+
+impl Subscription {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    /// Remove the listener; returns false if the bus is gone or it was already removed
+    pub fn unsubscribe(self) -> bool {
+        match self.subscribers.upgrade() {
+            Some(subscribers) => remove_subscriber(&subscribers, &self.event_type, self.id),
+            None => false,
+        }
+    }
+}
+
+fn remove_subscriber(subscribers: &SubscriberTable, event_type: &str, id: u64) -> bool {
+    let Ok(mut table) = subscribers.write() else {
+        return false;
+    };
+    let Some(list) = table.get_mut(event_type) else {
+        return false;
+    };
+    let before = list.len();
+    list.retain(|s| s.id() != id);
+    let removed = list.len() < before;
+    if list.is_empty() {
+        table.remove(event_type);
+    }
+    removed
+}
+
+/// Subscribers whose topic (exact key or pattern) matches a concrete event type
+fn matching_subscribers<'a>(
+    table: &'a HashMap<String, Vec<Subscriber>>,
+    event_type: &'a str,
+) -> impl Iterator<Item = &'a Subscriber> {
+    let exact = table.get(event_type).into_iter().flatten();
+    let patterns = table
+        .iter()
+        .filter(move |(key, _)| topic::is_pattern(key) && topic::matches(key, event_type))
+        .flat_map(|(_, list)| list);
+    exact.chain(patterns)
+}
+
+pub struct EventBus {
+    history: Mutex<EventHistory>,
+    max_history: usize,
+    observers: RwLock<Vec<EventObserver>>,
+    subscribers: Arc<SubscriberTable>,
+    next_subscriber_id: AtomicU64,
+    worker: OnceLock<Sender<WorkerJob>>,
+    store: RwLock<Option<Arc<EventStore>>>,
+    requests: PendingRequests,
+    schemas: SchemaRegistry,
+    metrics: Arc<DispatchMetrics>,
+}
+
+impl EventBus {
+    pub fn new(max_history: usize) -> Self {
+        Self {
+            history: Mutex::new(EventHistory::new(max_history)),
+            max_history,
+            observers: RwLock::new(Vec::new()),
+            subscribers: Arc::new(RwLock::new(HashMap::new())),
+            next_subscriber_id: AtomicU64::new(1),
+            worker: OnceLock::new(),
+            store: RwLock::new(None),
+            requests: PendingRequests::new(),
+            schemas: SchemaRegistry::new(),
+            metrics: Arc::new(DispatchMetrics::new()),
+        }
+    }
+
+    /// Persist every published event to a durable store from now on
+    pub fn attach_store(&self, store: Arc<EventStore>) {
+        if let Ok(mut slot) = self.store.write() {
+            *slot = Some(store);
+        }
+    }
+
+    pub fn store(&self) -> Option<Arc<EventStore>> {
+        self.store.read().ok().and_then(|slot| slot.clone())
+    }
+
+    pub fn add_observer(&self, observer: EventObserver) {
+        if let Ok(mut observers) = self.observers.write() {
+            observers.push(observer);
+        }
+    }
+
+    /// Subscribe a listener to an event type or topic pattern (`user.*`, `user.#`, `#`)
+    pub fn subscribe(
+        &self,
+        event_type: &str,
+        mode: DispatchMode,
+        listener: impl EventListener + 'static,
+    ) -> AppResult<Subscription> {
+        self.subscribe_arc(event_type, mode, Arc::new(listener))
+    }
+
+    /// Subscribe a shared trait object to an event type
+    pub fn subscribe_arc(
+        &self,
+        event_type: &str,
+        mode: DispatchMode,
+        listener: Arc<dyn EventListener>,
+    ) -> AppResult<Subscription> {
+        self.insert_subscriber(event_type, None, mode, listener)
+    }
+
+    /// Subscribe on behalf of an endpoint such as `window:3` or `user-service`
+    ///
+    /// The listener receives broadcast events plus events targeted at `endpoint`;
+    /// events targeted elsewhere skip it.
+    pub fn subscribe_endpoint(
+        &self,
+        endpoint: &str,
+        event_type: &str,
+        mode: DispatchMode,
+        listener: impl EventListener + 'static,
+    ) -> AppResult<Subscription> {
+        self.insert_subscriber(event_type, Some(endpoint.to_string()), mode, Arc::new(listener))
+    }
+
+    fn insert_subscriber(
+        &self,
+        event_type: &str,
+        endpoint: Option<String>,
+        mode: DispatchMode,
+        listener: Arc<dyn EventListener>,
+    ) -> AppResult<Subscription> {
+        topic::validate_pattern(event_type)?;
+        let id = self.next_subscriber_id.fetch_add(1, Ordering::SeqCst);
+        let queue = match mode {
+            DispatchMode::Queued(options) => {
+                Some(self.spawn_queue(id, event_type, options, Arc::clone(&listener))?)
+            }
+            _ => None,
+        };
+        let mut table = self.subscribers.write().map_err(|e| {
+            if let Some(queue) = &queue {
+                queue.close();
+            }
+            AppError::LockPoisoned(
+                ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire event bus lock")
+                    .with_cause(e.to_string())
+                    .with_context("operation", "subscribe"),
+            )
+        })?;
+        table.entry(event_type.to_string()).or_default().push(Subscriber {
+            target: DeliveryTarget {
+                id,
+                topic: event_type.to_string(),
+                mode,
+                listener,
+                queue,
+            },
+            endpoint,
+        });
+
+        Ok(Subscription {
+            id,
+            event_type: event_type.to_string(),
+            subscribers: Arc::downgrade(&self.subscribers),
+        })
+    }
+
+    /// Remove a listener by subscription id
+    pub fn unsubscribe(&self, subscription_id: u64) -> bool {
+        let event_type = self.subscribers.read().ok().and_then(|table| {
+            table
+                .iter()
+                .find(|(_, list)| list.iter().any(|s| s.id() == subscription_id))
+                .map(|(event_type, _)| event_type.clone())
+        });
+        match event_type {
+            Some(event_type) => remove_subscriber(&self.subscribers, &event_type, subscription_id),
+            None => false,
+        }
+    }
+
+    pub fn emit(&self, event_type: &str, payload: serde_json::Value) {
+        let event = EventData::new(event_type, payload);
+        if let Err(e) = self.publish(event) {
+            log::warn!("Dropped {} event: {}", event_type, e);
+        }
+    }
+
+    pub fn emit_with_source(&self, event_type: &str, payload: serde_json::Value, source: &str) {
+        let event = EventData::new(event_type, payload).with_source(source);
+        if let Err(e) = self.publish(event) {
+            log::warn!("Dropped {} event from {}: {}", event_type, source, e);
+        }
+    }
+
+    /// Record an event and deliver it to observers and subscribed listeners
+    ///
+    /// Events with a `target` only reach listeners subscribed for that endpoint;
+    /// observers see every event. Payloads of event types with a registered
+    /// schema are validated first. Returns the number of listeners the event was
+    /// dispatched to.
+    pub fn publish(&self, event: EventData) -> AppResult<usize> {
+        topic::validate_topic(&event.event_type)?;
+        self.schemas.validate(&event)?;
+        self.store_events([event.clone()])?;
+        if let Some(store) = self.store() {
+            // A failing store must not stop in-process delivery
+            if let Err(e) = store.append(&event) {
+                log::error!("Failed to persist {} event: {}", event.event_type, e);
+            }
+        }
+        self.notify_observers(&event);
+        Ok(self.dispatch(&event))
+    }
+
+    /// Append to the in-memory history under one lock
+    fn store_events(&self, events: impl IntoIterator<Item = EventData>) -> AppResult<()> {
+        let mut history = self
+            .history
+            .lock()
+            .map_err(|e| {
+                AppError::LockPoisoned(
+                    ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire event bus lock")
+                        .with_cause(e.to_string())
+                        .with_context("operation", "store_event")
+                )
+            })?;
+        for event in events {
+            history.push(event);
+        }
+        Ok(())
+    }
+
+    fn notify_observers(&self, event: &EventData) {
+        let observers = match self.observers.read() {
+            Ok(observers) => observers.clone(),
+            Err(_) => return,
+        };
+        for observer in observers {
+            observer(event);
+        }
+    }
+
+    fn dispatch(&self, event: &EventData) -> usize {
+        // Copy the matching listeners so none runs under the table lock
+        let targets: Vec<DeliveryTarget> = match self.subscribers.read() {
+            Ok(table) => matching_subscribers(&table, &event.event_type)
+                .filter(|s| s.accepts(event))
+                .map(|s| s.target.clone())
+                .collect(),
+            Err(_) => return 0,
+        };
+
+        for target in &targets {
+            self.deliver(target, event);
+        }
+        targets.len()
+    }
+
+    fn deliver(&self, target: &DeliveryTarget, event: &EventData) {
+        match (&target.mode, &target.queue) {
+            (DispatchMode::Queued(_), Some(queue)) => {
+                let dropped = queue.push(event.clone());
+                if dropped > 0 {
+                    self.metrics.dropped.fetch_add(dropped as u64, Ordering::Relaxed);
+                }
+            }
+            (DispatchMode::Worker, _) => {
+                if self.worker().send((target.clone(), event.clone())).is_err() {
+                    log::error!("Event bus worker stopped; dropped {} event", event.event_type);
+                    self.metrics.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            _ => run_listener(&self.metrics, target, event),
+        }
+    }
+
+    /// Start the thread draining a `Queued` subscriber's queue
+    fn spawn_queue(
+        &self,
+        id: u64,
+        topic: &str,
+        options: QueueOptions,
+        listener: Arc<dyn EventListener>,
+    ) -> AppResult<Arc<BoundedQueue>> {
+        let queue = Arc::new(BoundedQueue::new(options));
+        let metrics = Arc::clone(&self.metrics);
+        let target = DeliveryTarget {
+            id,
+            topic: topic.to_string(),
+            mode: DispatchMode::Sync,
+            listener,
+            queue: None,
+        };
+        let consumer = Arc::clone(&queue);
+        thread::Builder::new()
+            .name(format!("event-bus-queue-{}", id))
+            .spawn(move || {
+                while let Some(event) = consumer.pop() {
+                    run_listener(&metrics, &target, &event);
+                }
+            })
+            .map_err(|e| {
+                AppError::EventBus(
+                    ErrorValue::new(ErrorCode::InternalError, "Failed to start subscriber queue")
+                        .with_cause(e.to_string())
+                        .with_context("event_type", topic),
+                )
+            })?;
+        Ok(queue)
+    }
+
+    /// Publish a request and wait for its reply
+    ///
+    /// A correlation id is assigned unless the event already has one. Exactly one
+    /// reply is accepted; later replies are dropped. Fails when no listener
+    /// receives the request or no reply arrives within `timeout`. Do not call
+    /// this from a `Worker` listener: the reply may be queued behind it.
+    pub fn request(&self, mut event: EventData, timeout: Duration) -> AppResult<EventData> {
+        let correlation_id = match &event.correlation_id {
+            Some(id) => id.clone(),
+            None => self.requests.next_correlation_id(),
+        };
+        event.correlation_id = Some(correlation_id.clone());
+        let receiver = self.requests.register(&correlation_id, &event).ok_or_else(|| {
+            AppError::Validation(
+                ErrorValue::new(ErrorCode::InvalidFieldValue, "Correlation id is already waiting for a reply")
+                    .with_field("correlation_id")
+                    .with_context("correlation_id", correlation_id.clone()),
+            )
+        })?;
+
+        let request_error = |code: ErrorCode, message: &str| {
+            ErrorValue::new(code, message)
+                .with_context("event_type", event.event_type.clone())
+                .with_context("target", event.target.clone().unwrap_or_default())
+                .with_context("correlation_id", correlation_id.clone())
+        };
+
+        let listeners = match self.publish(event.clone()) {
+            Ok(listeners) => listeners,
+            Err(e) => {
+                self.requests.take(&correlation_id);
+                return Err(e);
+            }
+        };
+        if listeners == 0 {
+            self.requests.take(&correlation_id);
+            return Err(AppError::NotFound(request_error(
+                ErrorCode::ResourceNotFound,
+                "No listener received the request",
+            )));
+        }
+
+        receiver.recv_timeout(timeout).map_err(|_| {
+            self.requests.take(&correlation_id);
+            AppError::EventBus(
+                request_error(ErrorCode::Timeout, "Request timed out waiting for a reply")
+                    .with_context("timeout_ms", timeout.as_millis().to_string()),
+            )
+        })
+    }
+
+    /// Answer a pending request; returns false if it was already answered or timed out
+    ///
+    /// The reply is published as `<request type>.reply`, targeted at the
+    /// request's source, carrying the same correlation id.
+    pub fn reply(&self, correlation_id: &str, payload: serde_json::Value) -> AppResult<bool> {
+        self.send_reply(correlation_id, payload, None)
+    }
+
+    pub fn reply_from(
+        &self,
+        source: &str,
+        correlation_id: &str,
+        payload: serde_json::Value,
+    ) -> AppResult<bool> {
+        self.send_reply(correlation_id, payload, Some(source))
+    }
+
+    fn send_reply(
+        &self,
+        correlation_id: &str,
+        payload: serde_json::Value,
+        source: Option<&str>,
+    ) -> AppResult<bool> {
+        let Some(pending) = self.requests.take(correlation_id) else {
+            return Ok(false);
+        };
+        let mut reply = EventData::new(format!("{}.{}", pending.event_type, REPLY_SUFFIX), payload)
+            .with_correlation_id(correlation_id);
+        reply.source = source.map(str::to_string);
+        reply.target = pending.requester.clone();
+
+        // Recorded before the requester wakes, so its history already holds the reply
+        self.publish(reply.clone())?;
+        Ok(pending.complete(reply))
+    }
+
+    /// Requests still waiting for a reply
+    pub fn pending_requests(&self) -> usize {
+        self.requests.len()
+    }
+
+    fn worker(&self) -> &Sender<WorkerJob> {
+        self.worker.get_or_init(|| {
+            let (sender, receiver) = mpsc::channel::<WorkerJob>();
+            let metrics = Arc::clone(&self.metrics);
+            let spawned = thread::Builder::new()
+                .name("event-bus-worker".to_string())
+                .spawn(move || {
+                    for (target, event) in receiver {
+                        run_listener(&metrics, &target, &event);
+                    }
+                });
+            if let Err(e) = spawned {
+                log::error!("Failed to start event bus worker: {}", e);
+            }
+            sender
+        })
+    }
+
+    /// Recent events of an exact type or topic pattern, via the per-type index
+    pub fn get_history(
+        &self,
+        event_type: Option<&str>,
+        limit: Option<usize>,
+    ) -> AppResult<Vec<EventData>> {
+        if let Some(pattern) = event_type {
+            topic::validate_pattern(pattern)?;
+        }
+        let history = self
+            .history
+            .lock()
+            .map_err(|e| {
+                AppError::LockPoisoned(
+                    ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire event bus lock")
+                        .with_cause(e.to_string())
+                        .with_context("operation", "get_history")
+                )
+            })?;
+
+        Ok(history.query(event_type, limit))
+    }
+
+    /// Events matching a query, oldest first; from the durable store when attached
+    pub fn query(&self, query: &EventQuery) -> AppResult<Vec<EventData>> {
+        if let Some(pattern) = query.event_type.as_deref() {
+            topic::validate_pattern(pattern)?;
+        }
+        if let Some(store) = self.store() {
+            return Ok(store.query(query)?.into_iter().map(EventData::from).collect());
+        }
+
+        let mut events: Vec<EventData> = self
+            .get_history(query.event_type.as_deref(), None)?
+            .into_iter()
+            .filter(|event| query.matches(event))
+            .collect();
+        if let Some(limit) = query.limit {
+            events.drain(..events.len().saturating_sub(limit));
+        }
+        Ok(events)
+    }
+
+    /// Deliver past events of the subscription's topic to its listener, oldest first
+    ///
+    /// `query` narrows the events further (its `event_type` is replaced by the
+    /// subscription's). Events published while replaying may be delivered twice.
+    pub fn replay(&self, subscription: &Subscription, query: EventQuery) -> AppResult<usize> {
+        let (target, endpoint) = self
+            .subscribers
+            .read()
+            .ok()
+            .and_then(|table| {
+                table
+                    .get(subscription.event_type())?
+                    .iter()
+                    .find(|s| s.id() == subscription.id())
+                    .map(|s| (s.target.clone(), s.endpoint.clone()))
+            })
+            .ok_or_else(|| {
+                AppError::NotFound(
+                    ErrorValue::new(ErrorCode::ResourceNotFound, "Subscription not found")
+                        .with_context("subscription_id", subscription.id().to_string()),
+                )
+            })?;
+
+        let events = self.query(&EventQuery {
+            event_type: Some(subscription.event_type().to_string()),
+            ..query
+        })?;
+        // The same target filter as `publish`: events meant for another endpoint are skipped
+        let mut delivered = 0;
+        for event in events.iter().filter(|event| endpoint_accepts(endpoint.as_deref(), event)) {
+            self.deliver(&target, event);
+            delivered += 1;
+        }
+        Ok(delivered)
+    }
+
+    /// Write the events matching `query` to a trace file, oldest first
+    ///
+    /// Reads from the durable store when one is attached, like `query`.
+    /// Returns the number of events written.
+    pub fn export_trace(
+        &self,
+        path: &Path,
+        query: &EventQuery,
+        format: TraceFormat,
+        compress: bool,
+    ) -> AppResult<usize> {
+        let events = self.query(query)?;
+        let bytes = trace::encode(&events, format, compress)?;
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|e| trace_io_error("export", path, e))?;
+        }
+        fs::write(path, bytes).map_err(|e| trace_io_error("export", path, e))?;
+        Ok(events.len())
+    }
+
+    /// Read a trace file written by `export_trace`, possibly on another machine
+    ///
+    /// Without a format, it is taken from the file name (`.jsonl`, `.cbor`,
+    /// optionally `.gz`) or detected from the contents. Returns the number of
+    /// events imported.
+    pub fn import_trace(
+        &self,
+        path: &Path,
+        format: Option<TraceFormat>,
+        mode: ImportMode,
+    ) -> AppResult<usize> {
+        let bytes = fs::read(path).map_err(|e| trace_io_error("import", path, e))?;
+        let format = format
+            .or_else(|| TraceFormat::from_path(path).map(|(format, _)| format))
+            .unwrap_or_else(|| trace::detect_format(&bytes));
+        let events = trace::decode(&bytes, format)?;
+        // Check every event first, so a bad one leaves nothing half imported
+        for event in &events {
+            topic::validate_topic(&event.event_type)?;
+            if mode == ImportMode::Replay {
+                self.schemas.validate(event)?;
+            }
+        }
+        let count = events.len();
+        match mode {
+            ImportMode::Load => self.store_events(events)?,
+            ImportMode::Replay => {
+                for event in events {
+                    self.publish(event)?;
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    pub fn clear_history(&self) -> AppResult<()> {
+        let mut history = self
+            .history
+            .lock()
+            .map_err(|e| {
+                AppError::LockPoisoned(
+                    ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire event bus lock")
+                        .with_cause(e.to_string())
+                        .with_context("operation", "clear_history")
+                )
+            })?;
+        history.clear();
+        Ok(())
+    }
+
+    /// Listeners an event of this type would be dispatched to, including patterns
+    pub fn listener_count(&self, event_type: &str) -> usize {
+        self.subscribers
+            .read()
+            .map(|table| matching_subscribers(&table, event_type).count())
+            .unwrap_or(0)
+    }
+
+    pub fn total_listeners(&self) -> usize {
+        self.subscribers
+            .read()
+            .map(|table| table.values().map(Vec::len).sum())
+            .unwrap_or(0)
+    }
+
+    pub fn get_stats(&self) -> EventBusStats {
+        let mut event_types: Vec<EventTypeInfo> = self
+            .subscribers
+            .read()
+            .map(|table| {
+                table
+                    .iter()
+                    .map(|(event_type, list)| EventTypeInfo {
+                        event_type: event_type.clone(),
+                        listener_count: list.len(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        event_types.sort_by(|a, b| a.event_type.cmp(&b.event_type));
+        let queued = self
+            .subscribers
+            .read()
+            .map(|table| {
+                table
+                    .values()
+                    .flatten()
+                    .filter_map(|s| s.target.queue.as_ref())
+                    .map(|queue| queue.len())
+                    .sum()
+            })
+            .unwrap_or(0);
+
+        EventBusStats {
+            total_listeners: event_types.iter().map(|t| t.listener_count).sum(),
+            event_types,
+            delivered: self.metrics.delivered.load(Ordering::Relaxed),
+            failed: self.metrics.failed.load(Ordering::Relaxed),
+            panicked: self.metrics.panicked.load(Ordering::Relaxed),
+            dropped: self.metrics.dropped.load(Ordering::Relaxed),
+            queued,
+            dead_letters: self.metrics.dead_letters.len(),
+        }
+    }
+
+    /// Events whose listener failed or panicked, oldest first (the last 100 are kept)
+    pub fn dead_letters(&self) -> Vec<DeadLetter> {
+        self.metrics.dead_letters.list()
+    }
+
+    pub fn clear_dead_letters(&self) -> usize {
+        self.metrics.dead_letters.clear()
+    }
+
+    /// Document an event type with a JSON schema its payloads must match
+    ///
+    /// Supports the `type`, `enum`, `const`, `properties`, `required`,
+    /// `additionalProperties: false`, `items` and length/range keywords.
+    pub fn register_schema(
+        &self,
+        event_type: &str,
+        schema: serde_json::Value,
+        description: Option<String>,
+    ) -> AppResult<()> {
+        self.schemas.register_json(event_type, schema, description)
+    }
+
+    /// Document an event type with a Rust type its payloads must deserialize into
+    pub fn register_payload_type<T: serde::de::DeserializeOwned>(
+        &self,
+        event_type: &str,
+        description: Option<String>,
+    ) -> AppResult<()> {
+        self.schemas.register_type::<T>(event_type, description)
+    }
+
+    pub fn unregister_schema(&self, event_type: &str) -> bool {
+        self.schemas.unregister(event_type)
+    }
+
+    /// The documented event catalogue, sorted by event type
+    pub fn schemas(&self) -> Vec<EventSchemaInfo> {
+        self.schemas.catalogue()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+fn trace_io_error(operation: &str, path: &Path, error: std::io::Error) -> AppError {
+    AppError::EventBus(
+        ErrorValue::new(ErrorCode::InternalError, format!("Failed to {} event trace", operation))
+            .with_cause(error.to_string())
+            .with_context("path", path.display().to_string()),
+    )
+}
+
+static GLOBAL_EVENT_BUS: OnceLock<EventBus> = OnceLock::new();
+
+pub fn get_global_event_bus() -> &'static EventBus {
+    GLOBAL_EVENT_BUS.get_or_init(|| EventBus::new(100))
+}
+
+#[macro_export]
+macro_rules! event_publish {
+    ($event_type:expr, $payload:expr) => {
+        $crate::core::infrastructure::event_bus::get_global_event_bus().emit($event_type, $payload)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    #[test]
+    fn test_sync_listener_and_unsubscribe() {
+        let bus = EventBus::new(10);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let subscription = bus
+            .subscribe("user.created", DispatchMode::Sync, move |event: &EventData| {
+                assert_eq!(event.payload["id"], 7);
+                counter.fetch_add(1, Ordering::SeqCst);
+            })
+            .unwrap();
+
+        assert_eq!(bus.publish(EventData::new("user.created", serde_json::json!({"id": 7}))).unwrap(), 1);
+        bus.emit("user.deleted", serde_json::json!({"id": 7}));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        assert!(subscription.unsubscribe());
+        bus.emit("user.created", serde_json::json!({"id": 7}));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(bus.total_listeners(), 0);
+    }
+
+    struct Forwarder(Mutex<mpsc::Sender<String>>);
+
+    impl EventListener for Forwarder {
+        fn on_event(&self, event: &EventData) {
+            let _ = self.0.lock().unwrap().send(event.event_type.clone());
+        }
+    }
+
+    #[test]
+    fn test_worker_listener_runs_off_thread() {
+        let bus = EventBus::new(10);
+        let (sender, receiver) = mpsc::channel();
+        bus.subscribe("job.done", DispatchMode::Worker, Forwarder(Mutex::new(sender)))
+            .unwrap();
+
+        bus.emit("job.done", serde_json::json!({}));
+        let received = receiver.recv_timeout(Duration::from_secs(2)).unwrap();
+        assert_eq!(received, "job.done");
+    }
+
+    #[test]
+    fn test_pattern_subscriptions_and_history() {
+        let bus = EventBus::new(10);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        bus.subscribe("user.*", DispatchMode::Sync, move |_: &EventData| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        bus.subscribe("#", DispatchMode::Sync, |_: &EventData| {}).unwrap();
+
+        assert_eq!(bus.publish(EventData::new("user.created", serde_json::json!({}))).unwrap(), 2);
+        assert_eq!(bus.publish(EventData::new("order.created", serde_json::json!({}))).unwrap(), 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(bus.listener_count("user.deleted"), 2);
+
+        assert_eq!(bus.get_history(Some("user.*"), None).unwrap().len(), 1);
+        assert_eq!(bus.get_history(Some("*.created"), None).unwrap().len(), 2);
+        assert!(bus.get_history(Some("user*"), None).is_err());
+        assert!(bus.publish(EventData::new("user.*", serde_json::json!({}))).is_err());
+    }
+
+    #[test]
+    fn test_replay_from_store() {
+        use crate::core::infrastructure::database::Database;
+
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        let store = EventStore::open(Arc::clone(&db), RetentionPolicy::default()).unwrap();
+        let bus = EventBus::new(1);
+        bus.attach_store(Arc::new(store));
+
+        bus.emit("audit.login", serde_json::json!({"user": "ada"}));
+        bus.emit("audit.logout", serde_json::json!({"user": "ada"}));
+        bus.emit("user.created", serde_json::json!({"id": 1}));
+        // Only the last event is still in memory
+        assert_eq!(bus.get_history(None, None).unwrap().len(), 1);
+        assert_eq!(bus.query(&EventQuery::for_type("audit.*")).unwrap().len(), 2);
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let subscription = bus
+            .subscribe("audit.#", DispatchMode::Sync, move |event: &EventData| {
+                sink.lock().unwrap().push(event.event_type.clone());
+            })
+            .unwrap();
+
+        assert_eq!(bus.replay(&subscription, EventQuery::default()).unwrap(), 2);
+        assert_eq!(*seen.lock().unwrap(), vec!["audit.login", "audit.logout"]);
+    }
+
+    #[test]
+    fn test_replay_skips_other_endpoints() {
+        let bus = EventBus::new(10);
+        bus.publish(EventData::new("ping", serde_json::json!(1)).with_target("a")).unwrap();
+        bus.publish(EventData::new("ping", serde_json::json!(2)).with_target("b")).unwrap();
+        bus.emit("ping", serde_json::json!(3));
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let subscription = bus
+            .subscribe_endpoint("b", "ping", DispatchMode::Sync, move |event: &EventData| {
+                sink.lock().unwrap().push(event.payload.clone());
+            })
+            .unwrap();
+
+        assert_eq!(bus.replay(&subscription, EventQuery::default()).unwrap(), 2);
+        assert_eq!(*seen.lock().unwrap(), vec![serde_json::json!(2), serde_json::json!(3)]);
+    }
+
+    #[test]
+    fn test_targeted_delivery() {
+        let bus = EventBus::new(10);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        for endpoint in ["a", "b"] {
+            let sink = Arc::clone(&seen);
+            bus.subscribe_endpoint(endpoint, "ping", DispatchMode::Sync, move |_: &EventData| {
+                sink.lock().unwrap().push(endpoint);
+            })
+            .unwrap();
+        }
+        bus.subscribe("ping", DispatchMode::Sync, |_: &EventData| {}).unwrap();
+
+        assert_eq!(bus.publish(EventData::new("ping", serde_json::json!({}))).unwrap(), 3);
+        assert_eq!(bus.publish(EventData::new("ping", serde_json::json!({})).with_target("b")).unwrap(), 1);
+        assert_eq!(bus.publish(EventData::new("ping", serde_json::json!({})).with_target("c")).unwrap(), 0);
+        assert_eq!(*seen.lock().unwrap(), vec!["a", "b", "b"]);
+    }
+
+    #[test]
+    fn test_request_reply() {
+        let bus = Arc::new(EventBus::new(10));
+        let responder = Arc::clone(&bus);
+        bus.subscribe_endpoint("math", "math.double", DispatchMode::Worker, move |event: &EventData| {
+            let id = event.correlation_id.clone().unwrap();
+            let value = event.payload["n"].as_i64().unwrap() * 2;
+            assert!(responder.reply_from("math", &id, serde_json::json!({ "n": value })).unwrap());
+            assert!(!responder.reply_from("math", &id, serde_json::json!({ "n": 0 })).unwrap());
+        })
+        .unwrap();
+
+        let request = EventData::new("math.double", serde_json::json!({ "n": 21 }))
+            .with_source("caller")
+            .with_target("math");
+        let reply = bus.request(request, Duration::from_secs(2)).unwrap();
+        assert_eq!(reply.event_type, "math.double.reply");
+        assert_eq!(reply.payload["n"], 42);
+        assert_eq!(reply.target.as_deref(), Some("caller"));
+        assert_eq!(reply.source.as_deref(), Some("math"));
+        assert_eq!(bus.pending_requests(), 0);
+        assert_eq!(bus.get_history(Some("math.double.reply"), None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_request_without_reply() {
+        let bus = EventBus::new(10);
+        let err = bus
+            .request(EventData::new("nobody.home", serde_json::json!({})), Duration::from_millis(10))
+            .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+
+        bus.subscribe("silent.request", DispatchMode::Sync, |_: &EventData| {}).unwrap();
+        let err = bus
+            .request(EventData::new("silent.request", serde_json::json!({})), Duration::from_millis(20))
+            .unwrap_err();
+        assert_eq!(err.to_value().code, ErrorCode::Timeout);
+        assert_eq!(bus.pending_requests(), 0);
+        assert!(!bus.reply("req-2", serde_json::json!({})).unwrap());
+    }
+
+    fn wait_until(condition: impl Fn() -> bool) {
+        for _ in 0..200 {
+            if condition() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("condition not reached");
+    }
+
+    #[test]
+    fn test_failures_go_to_dead_letters() {
+        let bus = EventBus::new(10);
+        bus.subscribe("job.run", DispatchMode::Worker, |event: &EventData| {
+            if event.payload["n"] == 1 {
+                panic!("boom");
+            }
+        })
+        .unwrap();
+        bus.subscribe(
+            "job.run",
+            DispatchMode::Sync,
+            fallible(|event: &EventData| match event.payload["n"].as_i64() {
+                Some(2) => Err(AppError::Validation(ErrorValue::new(
+                    ErrorCode::ValidationFailed,
+                    "bad job",
+                ))),
+                _ => Ok(()),
+            }),
+        )
+        .unwrap();
+
+        for n in 1..=3 {
+            bus.emit("job.run", serde_json::json!({ "n": n }));
+        }
+        // The worker survives the panic and keeps delivering
+        wait_until(|| bus.get_stats().delivered == 4);
+
+        let stats = bus.get_stats();
+        assert_eq!((stats.failed, stats.panicked, stats.dead_letters), (1, 1, 2));
+        let letters = bus.dead_letters();
+        let panicked = letters.iter().find(|l| l.panicked).unwrap();
+        assert_eq!(panicked.error, "boom");
+        assert_eq!(panicked.event.payload["n"], 1);
+        assert!(letters.iter().any(|l| !l.panicked && l.error.contains("bad job")));
+
+        assert_eq!(bus.clear_dead_letters(), 2);
+        assert_eq!(bus.get_stats().dead_letters, 0);
+    }
+
+    #[test]
+    fn test_queued_listener_drops_on_overflow() {
+        let bus = EventBus::new(10);
+        let (gate_tx, gate_rx) = mpsc::channel::<()>();
+        let gate = Mutex::new(gate_rx);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let options = QueueOptions { capacity: 2, overflow: OverflowPolicy::DropOldest };
+        let subscription = bus
+            .subscribe("tick", DispatchMode::Queued(options), move |event: &EventData| {
+                // Hold the first event until the test has filled the queue
+                let _ = gate.lock().unwrap().recv();
+                sink.lock().unwrap().push(event.payload["n"].as_i64().unwrap());
+            })
+            .unwrap();
+
+        bus.emit("tick", serde_json::json!({ "n": 0 }));
+        wait_until(|| bus.get_stats().queued == 0);
+        for n in 1..=4 {
+            bus.emit("tick", serde_json::json!({ "n": n }));
+        }
+        let stats = bus.get_stats();
+        assert_eq!((stats.queued, stats.dropped), (2, 2));
+
+        for _ in 0..3 {
+            gate_tx.send(()).unwrap();
+        }
+        wait_until(|| seen.lock().unwrap().len() == 3);
+        assert_eq!(*seen.lock().unwrap(), vec![0, 3, 4]);
+        assert!(subscription.unsubscribe());
+    }
+
+    #[test]
+    fn test_publish_rejects_invalid_payloads() {
+        let bus = EventBus::new(10);
+        bus.register_schema(
+            "user.created",
+            serde_json::json!({ "type": "object", "required": ["id"] }),
+            None,
+        )
+        .unwrap();
+        let received = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&received);
+        bus.subscribe("user.#", DispatchMode::Sync, move |_: &EventData| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+        let err = bus
+            .publish(EventData::new("user.created", serde_json::json!({ "name": "Ada" })))
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+        assert_eq!(bus.publish(EventData::new("user.created", serde_json::json!({ "id": 1 }))).unwrap(), 1);
+        assert_eq!(received.load(Ordering::SeqCst), 1);
+        assert_eq!(bus.get_history(None, None).unwrap().len(), 1);
+        assert_eq!(bus.schemas()[0].event_type, "user.created");
+    }
+
+    #[test]
+    fn test_export_and_import_trace() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bug-42").join("trace.cbor.gz");
+        let bus = EventBus::new(10);
+        bus.emit("user.created", serde_json::json!({ "id": 1 }));
+        bus.emit("order.placed", serde_json::json!({ "id": 7 }));
+        bus.emit_with_source("user.deleted", serde_json::json!({ "id": 1 }), "admin");
+
+        let exported = bus
+            .export_trace(&path, &EventQuery::for_type("user.*"), TraceFormat::Cbor, true)
+            .unwrap();
+        assert_eq!(exported, 2);
+
+        let other = EventBus::new(10);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        other
+            .subscribe("#", DispatchMode::Sync, move |event: &EventData| {
+                sink.lock().unwrap().push(event.event_type.clone());
+            })
+            .unwrap();
+        assert_eq!(other.import_trace(&path, None, ImportMode::Load).unwrap(), 2);
+        assert!(seen.lock().unwrap().is_empty());
+        assert_eq!(other.import_trace(&path, None, ImportMode::Replay).unwrap(), 2);
+        assert_eq!(*seen.lock().unwrap(), vec!["user.created", "user.deleted"]);
+
+        let history = other.get_history(None, None).unwrap();
+        assert_eq!(history.len(), 4);
+        assert_eq!(history[3].source.as_deref(), Some("admin"));
+        assert!(other.import_trace(&dir.path().join("missing.jsonl"), None, ImportMode::Load).is_err());
+
+        // One bad event rejects the whole file
+        let broken = dir.path().join("broken.jsonl");
+        let mut lines = String::new();
+        for event_type in ["user.created", "user.*"] {
+            lines.push_str(&serde_json::to_string(&EventData::new(event_type, serde_json::json!({}))).unwrap());
+            lines.push('\n');
+        }
+        std::fs::write(&broken, lines).unwrap();
+        assert!(other.import_trace(&broken, None, ImportMode::Load).is_err());
+        assert_eq!(other.get_history(None, None).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_stats_report_listener_counts() {
+        let bus = EventBus::new(10);
+        let a = bus.subscribe("b.event", DispatchMode::Sync, |_: &EventData| {}).unwrap();
+        bus.subscribe("b.event", DispatchMode::Worker, |_: &EventData| {}).unwrap();
+        bus.subscribe("a.event", DispatchMode::Sync, |_: &EventData| {}).unwrap();
+
+        let stats = bus.get_stats();
+        assert_eq!(stats.total_listeners, 3);
+        assert_eq!(stats.event_types[0].event_type, "a.event");
+        assert_eq!(bus.listener_count("b.event"), 2);
+
+        assert!(bus.unsubscribe(a.id()));
+        assert!(!bus.unsubscribe(a.id()));
+        assert_eq!(bus.listener_count("b.event"), 1);
+    }
+}
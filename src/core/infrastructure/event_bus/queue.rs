@@ -0,0 +1,179 @@
+// src/core/infrastructure/event_bus/queue.rs
+// Bounded per-subscriber queues for queued dispatch
+
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use super::EventData;
+
+/// What a full queue does with a new event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Discard the oldest queued event to make room
+    #[default]
+    DropOldest,
+    /// Discard the new event
+    DropNew,
+    /// Wait on the publishing thread until the listener catches up
+    Block,
+}
+
+impl OverflowPolicy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "drop_oldest" => Some(Self::DropOldest),
+            "drop_new" => Some(Self::DropNew),
+            "block" => Some(Self::Block),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueOptions {
+    pub capacity: usize,
+    pub overflow: OverflowPolicy,
+}
+
+impl Default for QueueOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            overflow: OverflowPolicy::DropOldest,
+        }
+    }
+}
+
+struct QueueState {
+    events: VecDeque<EventData>,
+    closed: bool,
+}
+
+pub(super) struct BoundedQueue {
+    state: Mutex<QueueState>,
+    /// Signalled when an event is queued or the queue is closed
+    not_empty: Condvar,
+    /// Signalled when an event is taken, for `OverflowPolicy::Block`
+    not_full: Condvar,
+    options: QueueOptions,
+}
+
+impl BoundedQueue {
+    pub fn new(options: QueueOptions) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                events: VecDeque::new(),
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            options: QueueOptions {
+                capacity: options.capacity.max(1),
+                ..options
+            },
+        }
+    }
+
+    /// Queue an event; returns the number of events dropped (0 or 1)
+    pub fn push(&self, event: EventData) -> usize {
+        let Ok(mut state) = self.state.lock() else {
+            return 1;
+        };
+        let mut dropped = 0;
+        while !state.closed && state.events.len() >= self.options.capacity {
+            match self.options.overflow {
+                OverflowPolicy::DropOldest => {
+                    state.events.pop_front();
+                    dropped += 1;
+                }
+                OverflowPolicy::DropNew => return 1,
+                OverflowPolicy::Block => match self.not_full.wait(state) {
+                    Ok(guard) => state = guard,
+                    Err(_) => return 1,
+                },
+            }
+        }
+        if state.closed {
+            return 1;
+        }
+        state.events.push_back(event);
+        self.not_empty.notify_one();
+        dropped
+    }
+
+    /// Next event, waiting if needed; `None` once closed and drained
+    pub fn pop(&self) -> Option<EventData> {
+        let mut state = self.state.lock().ok()?;
+        loop {
+            if let Some(event) = state.events.pop_front() {
+                self.not_full.notify_one();
+                return Some(event);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.not_empty.wait(state).ok()?;
+        }
+    }
+
+    /// Stop accepting events; the worker exits after draining the rest
+    pub fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().map(|state| state.events.len()).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(n: i64) -> EventData {
+        EventData::new("q", serde_json::json!({ "n": n }))
+    }
+
+    fn drain(queue: &BoundedQueue) -> Vec<i64> {
+        queue.close();
+        std::iter::from_fn(|| queue.pop())
+            .map(|e| e.payload["n"].as_i64().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_overflow_policies() {
+        let oldest = BoundedQueue::new(QueueOptions { capacity: 2, overflow: OverflowPolicy::DropOldest });
+        assert_eq!((1..=3).map(|n| oldest.push(event(n))).sum::<usize>(), 1);
+        assert_eq!(drain(&oldest), vec![2, 3]);
+
+        let newest = BoundedQueue::new(QueueOptions { capacity: 2, overflow: OverflowPolicy::DropNew });
+        assert_eq!((1..=3).map(|n| newest.push(event(n))).sum::<usize>(), 1);
+        assert_eq!(drain(&newest), vec![1, 2]);
+        assert_eq!(OverflowPolicy::from_name("Block"), Some(OverflowPolicy::Block));
+        assert_eq!(OverflowPolicy::from_name("spill"), None);
+    }
+
+    #[test]
+    fn test_block_waits_for_room() {
+        let queue = std::sync::Arc::new(BoundedQueue::new(QueueOptions {
+            capacity: 1,
+            overflow: OverflowPolicy::Block,
+        }));
+        queue.push(event(1));
+        let producer = {
+            let queue = std::sync::Arc::clone(&queue);
+            std::thread::spawn(move || queue.push(event(2)))
+        };
+        assert_eq!(queue.pop().unwrap().payload["n"], 1);
+        assert_eq!(producer.join().unwrap(), 0);
+        assert_eq!(drain(&queue), vec![2]);
+    }
+}
@@ -0,0 +1,72 @@
+// src/core/infrastructure/event_bus/request.rs
+// Requests waiting for their reply, keyed by correlation id
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::Mutex;
+
+use super::EventData;
+
+/// Suffix appended to a request's event type to form its reply type
+pub const REPLY_SUFFIX: &str = "reply";
+
+pub(super) struct PendingRequest {
+    /// Event type of the request, used to name the reply
+    pub event_type: String,
+    /// Source of the request; the reply is targeted back at it
+    pub requester: Option<String>,
+    reply: SyncSender<EventData>,
+}
+
+impl PendingRequest {
+    /// Hand the reply to the waiting requester; false if it stopped waiting
+    pub fn complete(self, reply: EventData) -> bool {
+        self.reply.try_send(reply).is_ok()
+    }
+}
+
+pub(super) struct PendingRequests {
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<String, PendingRequest>>,
+}
+
+impl PendingRequests {
+    pub fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(1),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn next_correlation_id(&self) -> String {
+        format!("req-{}", self.next_id.fetch_add(1, Ordering::SeqCst))
+    }
+
+    /// Start waiting for a reply; returns `None` if the id is already in use
+    pub fn register(&self, correlation_id: &str, request: &EventData) -> Option<Receiver<EventData>> {
+        let mut pending = self.pending.lock().ok()?;
+        if pending.contains_key(correlation_id) {
+            return None;
+        }
+        let (sender, receiver) = mpsc::sync_channel(1);
+        pending.insert(
+            correlation_id.to_string(),
+            PendingRequest {
+                event_type: request.event_type.clone(),
+                requester: request.source.clone(),
+                reply: sender,
+            },
+        );
+        Some(receiver)
+    }
+
+    /// Remove a request so only the first reply is accepted
+    pub fn take(&self, correlation_id: &str) -> Option<PendingRequest> {
+        self.pending.lock().ok()?.remove(correlation_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.lock().map(|pending| pending.len()).unwrap_or(0)
+    }
+}
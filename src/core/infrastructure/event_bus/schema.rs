@@ -0,0 +1,404 @@
+// src/core/infrastructure/event_bus/schema.rs
+// Payload schemas per event type: a JSON Schema subset or a Rust type
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{topic, EventData};
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+
+/// Root of violation paths (`payload.items[0].name`)
+const ROOT: &str = "payload";
+
+const TYPES: [&str; 7] = ["object", "array", "string", "number", "integer", "boolean", "null"];
+
+/// One mismatch between a payload and its schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaKind {
+    JsonSchema,
+    RustType,
+}
+
+/// Catalogue entry for one documented event type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventSchemaInfo {
+    pub event_type: String,
+    pub kind: SchemaKind,
+    pub description: Option<String>,
+    /// The JSON schema, for `JsonSchema` entries
+    pub schema: Option<Value>,
+    /// Fully qualified type name, for `RustType` entries
+    pub rust_type: Option<String>,
+}
+
+type Validator = Arc<dyn Fn(&Value) -> Vec<SchemaViolation> + Send + Sync>;
+
+struct RegisteredSchema {
+    info: EventSchemaInfo,
+    validate: Validator,
+}
+
+/// Schemas keyed by exact event type; types without one are not checked
+pub(super) struct SchemaRegistry {
+    schemas: RwLock<HashMap<String, RegisteredSchema>>,
+}
+
+impl SchemaRegistry {
+    pub fn new() -> Self {
+        Self {
+            schemas: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn register_json(&self, event_type: &str, schema: Value, description: Option<String>) -> AppResult<()> {
+        check_schema(&schema, "schema")?;
+        let rules = schema.clone();
+        self.insert(
+            EventSchemaInfo {
+                event_type: event_type.to_string(),
+                kind: SchemaKind::JsonSchema,
+                description,
+                schema: Some(schema),
+                rust_type: None,
+            },
+            Arc::new(move |payload| {
+                let mut violations = Vec::new();
+                check_value(&rules, payload, ROOT, &mut violations);
+                violations
+            }),
+        )
+    }
+
+    pub fn register_type<T: DeserializeOwned>(&self, event_type: &str, description: Option<String>) -> AppResult<()> {
+        self.insert(
+            EventSchemaInfo {
+                event_type: event_type.to_string(),
+                kind: SchemaKind::RustType,
+                description,
+                schema: None,
+                rust_type: Some(std::any::type_name::<T>().to_string()),
+            },
+            Arc::new(|payload| match serde_json::from_value::<T>(payload.clone()) {
+                Ok(_) => Vec::new(),
+                Err(e) => {
+                    let message = e.to_string();
+                    let path = field_from_message(&message)
+                        .map(|field| format!("{}.{}", ROOT, field))
+                        .unwrap_or_else(|| ROOT.to_string());
+                    vec![SchemaViolation { path, message }]
+                }
+            }),
+        )
+    }
+
+    fn insert(&self, info: EventSchemaInfo, validate: Validator) -> AppResult<()> {
+        topic::validate_topic(&info.event_type)?;
+        let mut schemas = self.schemas.write().map_err(|e| {
+            AppError::LockPoisoned(
+                ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire schema registry lock")
+                    .with_cause(e.to_string()),
+            )
+        })?;
+        schemas.insert(info.event_type.clone(), RegisteredSchema { info, validate });
+        Ok(())
+    }
+
+    pub fn unregister(&self, event_type: &str) -> bool {
+        self.schemas
+            .write()
+            .map(|mut schemas| schemas.remove(event_type).is_some())
+            .unwrap_or(false)
+    }
+
+    /// Reject an event whose payload does not match its registered schema
+    pub fn validate(&self, event: &EventData) -> AppResult<()> {
+        let Some(validate) = self
+            .schemas
+            .read()
+            .ok()
+            .and_then(|schemas| schemas.get(&event.event_type).map(|s| Arc::clone(&s.validate)))
+        else {
+            return Ok(());
+        };
+        let violations = validate(&event.payload);
+        let Some(first) = violations.first() else {
+            return Ok(());
+        };
+        Err(AppError::Validation(
+            ErrorValue::new(
+                ErrorCode::ValidationFailed,
+                format!("Invalid {} payload: {} {}", event.event_type, first.path, first.message),
+            )
+            .with_field(first.path.clone())
+            .with_details(
+                violations
+                    .iter()
+                    .map(|v| format!("{}: {}", v.path, v.message))
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            )
+            .with_context("event_type", event.event_type.clone())
+            .with_context("violations", violations.len().to_string()),
+        ))
+    }
+
+    /// Documented event types, sorted
+    pub fn catalogue(&self) -> Vec<EventSchemaInfo> {
+        let mut entries: Vec<EventSchemaInfo> = self
+            .schemas
+            .read()
+            .map(|schemas| schemas.values().map(|s| s.info.clone()).collect())
+            .unwrap_or_default();
+        entries.sort_by(|a, b| a.event_type.cmp(&b.event_type));
+        entries
+    }
+}
+
+/// Extract the field name serde reports as "missing field `x`" / "unknown field `x`"
+fn field_from_message(message: &str) -> Option<&str> {
+    let start = message.find('`')? + 1;
+    let len = message[start..].find('`')?;
+    Some(&message[start..start + len])
+}
+
+/// Reject schemas using keywords with values this validator cannot apply
+fn check_schema(schema: &Value, path: &str) -> AppResult<()> {
+    let invalid = |message: &str| {
+        Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::InvalidFieldValue, format!("Invalid event schema: {}", message))
+                .with_field("schema")
+                .with_context("path", path),
+        ))
+    };
+    let Some(object) = schema.as_object() else {
+        return invalid("a schema must be an object");
+    };
+    match object.get("type") {
+        None => {}
+        Some(Value::String(name)) if TYPES.contains(&name.as_str()) => {}
+        Some(Value::Array(names))
+            if names.iter().all(|n| n.as_str().is_some_and(|n| TYPES.contains(&n))) => {}
+        Some(_) => return invalid("unknown type"),
+    }
+    if let Some(properties) = object.get("properties") {
+        let Some(properties) = properties.as_object() else {
+            return invalid("properties must be an object");
+        };
+        for (name, property) in properties {
+            check_schema(property, &format!("{}.properties.{}", path, name))?;
+        }
+    }
+    if let Some(required) = object.get("required") {
+        if !required.as_array().is_some_and(|r| r.iter().all(Value::is_string)) {
+            return invalid("required must be a list of property names");
+        }
+    }
+    if let Some(items) = object.get("items") {
+        check_schema(items, &format!("{}.items", path))?;
+    }
+    if object.get("enum").is_some_and(|e| !e.is_array()) {
+        return invalid("enum must be a list");
+    }
+    Ok(())
+}
+
+fn type_matches(name: &str, value: &Value) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => false,
+    }
+}
+
+/// Check `value` against the supported keywords: type, enum, const, properties,
+/// required, additionalProperties (false), items, minItems/maxItems,
+/// minLength/maxLength and minimum/maximum. Other keywords are ignored.
+fn check_value(schema: &Value, value: &Value, path: &str, out: &mut Vec<SchemaViolation>) {
+    let mut violation = |message: String| {
+        out.push(SchemaViolation {
+            path: path.to_string(),
+            message,
+        })
+    };
+
+    match schema.get("type") {
+        Some(Value::String(name)) if !type_matches(name, value) => {
+            return violation(format!("must be of type {}", name));
+        }
+        Some(Value::Array(names))
+            if !names.iter().filter_map(Value::as_str).any(|n| type_matches(n, value)) =>
+        {
+            return violation(format!("must be one of the types {}", Value::Array(names.clone())));
+        }
+        _ => {}
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            violation(format!("must be one of {}", Value::Array(allowed.clone())));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            violation(format!("must equal {}", expected));
+        }
+    }
+    if let Some(text) = value.as_str() {
+        let len = text.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(Value::as_u64).filter(|min| len < *min) {
+            violation(format!("must be at least {} characters", min));
+        }
+        if let Some(max) = schema.get("maxLength").and_then(Value::as_u64).filter(|max| len > *max) {
+            violation(format!("must be at most {} characters", max));
+        }
+    }
+    if let Some(number) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(Value::as_f64).filter(|min| number < *min) {
+            violation(format!("must be >= {}", min));
+        }
+        if let Some(max) = schema.get("maximum").and_then(Value::as_f64).filter(|max| number > *max) {
+            violation(format!("must be <= {}", max));
+        }
+    }
+
+    if let Some(items) = value.as_array() {
+        let len = items.len() as u64;
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64).filter(|min| len < *min) {
+            violation(format!("must have at least {} items", min));
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64).filter(|max| len > *max) {
+            violation(format!("must have at most {} items", max));
+        }
+        if let Some(item_schema) = schema.get("items") {
+            for (i, item) in items.iter().enumerate() {
+                check_value(item_schema, item, &format!("{}[{}]", path, i), out);
+            }
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for name in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+            if let Some(name) = name.as_str().filter(|name| !object.contains_key(*name)) {
+                out.push(SchemaViolation {
+                    path: format!("{}.{}", path, name),
+                    message: "is required".to_string(),
+                });
+            }
+        }
+        for (name, field) in object {
+            let field_path = format!("{}.{}", path, name);
+            match properties.and_then(|p| p.get(name)) {
+                Some(field_schema) => check_value(field_schema, field, &field_path, out),
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    out.push(SchemaViolation {
+                        path: field_path,
+                        message: "is not allowed".to_string(),
+                    });
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn user_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["id", "name"],
+            "additionalProperties": false,
+            "properties": {
+                "id": { "type": "integer", "minimum": 1 },
+                "name": { "type": "string", "minLength": 1 },
+                "role": { "enum": ["admin", "user"] },
+                "tags": { "type": "array", "items": { "type": "string" }, "maxItems": 2 }
+            }
+        })
+    }
+
+    fn violations(schema: &Value, payload: Value) -> Vec<String> {
+        let mut out = Vec::new();
+        check_value(schema, &payload, ROOT, &mut out);
+        out.into_iter().map(|v| format!("{} {}", v.path, v.message)).collect()
+    }
+
+    #[test]
+    fn test_json_schema_subset() {
+        let schema = user_schema();
+        assert!(violations(&schema, json!({ "id": 1, "name": "Ada", "tags": ["x"] })).is_empty());
+        assert_eq!(
+            violations(&schema, json!({ "id": 0, "nmae": "Ada" })),
+            vec![
+                "payload.name is required",
+                "payload.id must be >= 1",
+                "payload.nmae is not allowed"
+            ]
+        );
+        assert_eq!(
+            violations(&schema, json!({ "id": 2, "name": "", "role": "root", "tags": [1, "a", "b"] })),
+            vec![
+                "payload.name must be at least 1 characters",
+                "payload.role must be one of [\"admin\",\"user\"]",
+                "payload.tags must have at most 2 items",
+                "payload.tags[0] must be of type string"
+            ]
+        );
+        assert_eq!(violations(&schema, json!([])), vec!["payload must be of type object"]);
+    }
+
+    #[test]
+    fn test_registry_validates_and_lists() {
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Ping {
+            seq: u32,
+        }
+
+        let registry = SchemaRegistry::new();
+        registry.register_json("user.created", user_schema(), Some("A user was added".into())).unwrap();
+        registry.register_type::<Ping>("ping", None).unwrap();
+        assert!(registry.register_json("bad", json!({ "type": "text" }), None).is_err());
+        assert!(registry.register_json("user.*", json!({}), None).is_err());
+
+        let err = registry
+            .validate(&EventData::new("user.created", json!({ "id": 1 })))
+            .unwrap_err();
+        match err {
+            AppError::Validation(e) => {
+                assert_eq!(e.code, ErrorCode::ValidationFailed);
+                assert_eq!(e.field.as_deref(), Some("payload.name"));
+            }
+            other => panic!("Expected validation error, got {:?}", other),
+        }
+        let err = registry.validate(&EventData::new("ping", json!({}))).unwrap_err();
+        assert_eq!(err.to_value().field.as_deref(), Some("payload.seq"));
+        assert!(registry.validate(&EventData::new("ping", json!({ "seq": 3 }))).is_ok());
+        assert!(registry.validate(&EventData::new("unknown", json!(null))).is_ok());
+
+        let catalogue = registry.catalogue();
+        assert_eq!(catalogue.len(), 2);
+        assert_eq!(catalogue[0].event_type, "ping");
+        assert_eq!(catalogue[0].kind, SchemaKind::RustType);
+        assert!(registry.unregister("ping"));
+    }
+}
@@ -0,0 +1,181 @@
+// src/core/infrastructure/event_bus/store.rs
+// Durable event log in the app database, with retention
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use chrono::Utc;
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use super::{topic, EventData};
+use crate::core::error::AppResult;
+use crate::core::infrastructure::database::models::StoredEvent;
+use crate::core::infrastructure::database::Database;
+
+/// Appends between retention passes
+const PRUNE_EVERY: u64 = 500;
+
+const MS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+
+/// Filter for stored and in-memory events; all fields are optional and combined with AND
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventQuery {
+    /// Exact type or topic pattern such as `user.*`
+    pub event_type: Option<String>,
+    pub source: Option<String>,
+    pub target: Option<String>,
+    /// Inclusive lower bound, milliseconds since the Unix epoch
+    pub since: Option<i64>,
+    /// Inclusive upper bound, milliseconds since the Unix epoch
+    pub until: Option<i64>,
+    /// Keep only the newest N matches
+    pub limit: Option<usize>,
+}
+
+impl EventQuery {
+    pub fn for_type(event_type: impl Into<String>) -> Self {
+        Self {
+            event_type: Some(event_type.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn matches(&self, event: &EventData) -> bool {
+        self.event_type
+            .as_deref()
+            .is_none_or(|pattern| topic::matches(pattern, &event.event_type))
+            && self.source.as_deref().is_none_or(|s| event.source.as_deref() == Some(s))
+            && self.target.as_deref().is_none_or(|t| event.target.as_deref() == Some(t))
+            && self.since.is_none_or(|since| event.timestamp >= since)
+            && self.until.is_none_or(|until| event.timestamp <= until)
+    }
+}
+
+/// How long stored events are kept; `None` disables a limit
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct RetentionPolicy {
+    pub max_age_days: Option<u32>,
+    pub max_events: Option<u64>,
+}
+
+impl RetentionPolicy {
+    /// Build from config values where 0 means unlimited
+    pub fn from_config(max_age_days: u32, max_events: u64) -> Self {
+        Self {
+            max_age_days: (max_age_days > 0).then_some(max_age_days),
+            max_events: (max_events > 0).then_some(max_events),
+        }
+    }
+}
+
+impl From<StoredEvent> for EventData {
+    fn from(stored: StoredEvent) -> Self {
+        Self {
+            event_type: stored.event_type,
+            payload: stored.payload,
+            timestamp: stored.timestamp,
+            source: stored.source,
+            target: stored.target,
+            correlation_id: stored.correlation_id,
+        }
+    }
+}
+
+/// Event log table plus its retention policy
+pub struct EventStore {
+    db: Arc<Database>,
+    retention: RetentionPolicy,
+    appended: AtomicU64,
+}
+
+impl EventStore {
+    /// Create the table if needed and apply retention once
+    pub fn open(db: Arc<Database>, retention: RetentionPolicy) -> AppResult<Self> {
+        db.init_event_log()?;
+        let store = Self {
+            db,
+            retention,
+            appended: AtomicU64::new(0),
+        };
+        let pruned = store.prune()?;
+        info!(
+            "Event store opened ({} events, {} pruned, retention {:?})",
+            store.count()?,
+            pruned,
+            retention
+        );
+        Ok(store)
+    }
+
+    pub fn append(&self, event: &EventData) -> AppResult<i64> {
+        let id = self.db.insert_event(event)?;
+        if (self.appended.fetch_add(1, Ordering::Relaxed) + 1) % PRUNE_EVERY == 0 {
+            self.prune()?;
+        }
+        Ok(id)
+    }
+
+    pub fn query(&self, query: &EventQuery) -> AppResult<Vec<StoredEvent>> {
+        self.db.query_events(query)
+    }
+
+    /// Delete events outside the retention policy
+    pub fn prune(&self) -> AppResult<usize> {
+        let cutoff = self
+            .retention
+            .max_age_days
+            .map(|days| Utc::now().timestamp_millis() - i64::from(days) * MS_PER_DAY);
+        self.db.prune_events(cutoff, self.retention.max_events)
+    }
+
+    pub fn count(&self) -> AppResult<u64> {
+        self.db.count_events()
+    }
+
+    pub fn retention(&self) -> RetentionPolicy {
+        self.retention
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_matches_in_memory_events() {
+        let event = EventData::new("user.created", serde_json::json!({}))
+            .with_source("frontend")
+            .with_target("window-1");
+
+        assert!(EventQuery::for_type("user.*").matches(&event));
+        assert!(!EventQuery::for_type("order.*").matches(&event));
+        assert!(EventQuery {
+            source: Some("frontend".into()),
+            target: Some("window-1".into()),
+            since: Some(event.timestamp),
+            ..Default::default()
+        }
+        .matches(&event));
+        assert!(!EventQuery {
+            until: Some(event.timestamp - 1),
+            ..Default::default()
+        }
+        .matches(&event));
+    }
+
+    #[test]
+    fn test_retention_applied_on_open() {
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        db.init_event_log().unwrap();
+        let mut old = EventData::new("a", serde_json::json!({}));
+        old.timestamp -= 10 * MS_PER_DAY;
+        db.insert_event(&old).unwrap();
+        db.insert_event(&EventData::new("b", serde_json::json!({}))).unwrap();
+
+        let store = EventStore::open(db, RetentionPolicy::from_config(7, 0)).unwrap();
+        let remaining = store.query(&EventQuery::default()).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].event_type, "b");
+    }
+}
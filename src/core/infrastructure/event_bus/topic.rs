@@ -0,0 +1,113 @@
+// src/core/infrastructure/event_bus/topic.rs
+// Hierarchical topics: `.`-separated segments, `*` matches one segment, `#` matches any number
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+
+const SEPARATOR: char = '.';
+const ONE: &str = "*";
+const ANY: &str = "#";
+
+/// True if the pattern contains `*` or `#` segments
+pub fn is_pattern(pattern: &str) -> bool {
+    pattern.split(SEPARATOR).any(|segment| segment == ONE || segment == ANY)
+}
+
+/// Check that wildcards only appear as whole segments (`user.*`, not `user*`)
+pub fn validate_pattern(pattern: &str) -> AppResult<()> {
+    let invalid = pattern.is_empty()
+        || pattern.split(SEPARATOR).any(|segment| {
+            segment.is_empty()
+                || (segment != ONE && segment != ANY && segment.contains(['*', '#']))
+        });
+    if invalid {
+        return Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::InvalidFieldValue, "Invalid event topic pattern")
+                .with_field("event_type")
+                .with_context("pattern", pattern)
+                .with_context("expected", "dot-separated segments; '*' or '#' as whole segments"),
+        ));
+    }
+    Ok(())
+}
+
+/// Published event types must be concrete topics
+pub fn validate_topic(topic: &str) -> AppResult<()> {
+    if topic.contains(['*', '#']) {
+        return Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::InvalidFieldValue, "Event types cannot contain wildcards")
+                .with_field("event_type")
+                .with_context("event_type", topic),
+        ));
+    }
+    Ok(())
+}
+
+/// Leading literal segments of a pattern (`user` for `user.*`), for narrowing lookups
+pub fn literal_prefix(pattern: &str) -> String {
+    pattern
+        .split(SEPARATOR)
+        .take_while(|segment| *segment != ONE && *segment != ANY)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Match a concrete topic against a pattern
+pub fn matches(pattern: &str, topic: &str) -> bool {
+    if !is_pattern(pattern) {
+        return pattern == topic;
+    }
+    let pattern: Vec<&str> = pattern.split(SEPARATOR).collect();
+    let topic: Vec<&str> = topic.split(SEPARATOR).collect();
+    matches_segments(&pattern, &topic)
+}
+
+fn matches_segments(pattern: &[&str], topic: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => topic.is_empty(),
+        Some((&ANY, rest)) => (0..=topic.len()).any(|skip| matches_segments(rest, &topic[skip..])),
+        Some((&head, rest)) => match topic.split_first() {
+            Some((segment, topic_rest)) => {
+                (head == ONE || head == *segment) && matches_segments(rest, topic_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_segment_wildcard() {
+        assert!(matches("user.*", "user.created"));
+        assert!(!matches("user.*", "user"));
+        assert!(!matches("user.*", "user.profile.updated"));
+        assert!(matches("*.created", "order.created"));
+        assert!(matches("user.created", "user.created"));
+        assert!(!matches("user.created", "user.deleted"));
+    }
+
+    #[test]
+    fn test_multi_segment_wildcard() {
+        assert!(matches("#", "user.created"));
+        assert!(matches("#", "ping"));
+        assert!(matches("user.#", "user"));
+        assert!(matches("user.#", "user.profile.updated"));
+        assert!(matches("#.updated", "user.profile.updated"));
+        assert!(!matches("user.#", "order.created"));
+    }
+
+    #[test]
+    fn test_pattern_validation() {
+        assert!(validate_pattern("user.*").is_ok());
+        assert!(validate_pattern("#").is_ok());
+        assert!(validate_pattern("user*").is_err());
+        assert!(validate_pattern("user..created").is_err());
+        assert!(validate_pattern("").is_err());
+        assert!(validate_topic("user.*").is_err());
+        assert!(validate_topic("devtools:event-added").is_ok());
+        assert_eq!(literal_prefix("user.profile.*"), "user.profile");
+        assert_eq!(literal_prefix("#.updated"), "");
+    }
+}
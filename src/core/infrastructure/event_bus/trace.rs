@@ -0,0 +1,198 @@
+// src/core/infrastructure/event_bus/trace.rs
+// Event traces: history slices written to JSON-lines or CBOR files, optionally gzipped
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::EventData;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::utils::compression::CompressionUtils;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceFormat {
+    /// One JSON event per line
+    #[default]
+    JsonLines,
+    /// A CBOR array of events
+    Cbor,
+}
+
+impl TraceFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "jsonl" | "json_lines" | "ndjson" => Some(Self::JsonLines),
+            "cbor" => Some(Self::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Detect the format and compression from `trace.jsonl`, `trace.cbor.gz`, ...
+    pub fn from_path(path: &Path) -> Option<(Self, bool)> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        let (name, compressed) = match name.strip_suffix(".gz") {
+            Some(stem) => (stem.to_string(), true),
+            None => (name, false),
+        };
+        let format = Self::from_name(name.rsplit_once('.')?.1)?;
+        Some((format, compressed))
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::JsonLines => "jsonl",
+            Self::Cbor => "cbor",
+        }
+    }
+}
+
+/// What importing a trace does with its events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// Add the events to the in-memory history without dispatching them
+    #[default]
+    Load,
+    /// Publish the events again, in order, to observers and listeners
+    Replay,
+}
+
+/// Encode events oldest first
+pub fn encode(events: &[EventData], format: TraceFormat, compress: bool) -> AppResult<Vec<u8>> {
+    let bytes = match format {
+        TraceFormat::JsonLines => {
+            let mut bytes = Vec::new();
+            for event in events {
+                serde_json::to_writer(&mut bytes, event)?;
+                bytes.push(b'\n');
+            }
+            bytes
+        }
+        TraceFormat::Cbor => serde_cbor::to_vec(&events).map_err(|e| {
+            AppError::Serialization(
+                ErrorValue::new(ErrorCode::SerializationFailed, "Failed to encode event trace")
+                    .with_cause(e.to_string())
+                    .with_context("format", "cbor"),
+            )
+        })?,
+    };
+    if !compress {
+        return Ok(bytes);
+    }
+    CompressionUtils::compress_gzip(&bytes).map_err(|e| {
+        AppError::Serialization(
+            ErrorValue::new(ErrorCode::SerializationFailed, "Failed to compress event trace")
+                .with_cause(e),
+        )
+    })
+}
+
+/// Decode a trace; gzip input is detected and decompressed first
+pub fn decode(bytes: &[u8], format: TraceFormat) -> AppResult<Vec<EventData>> {
+    let decompressed;
+    let bytes = if bytes.starts_with(&GZIP_MAGIC) {
+        decompressed = CompressionUtils::decompress_gzip(bytes).map_err(|e| {
+            AppError::Serialization(
+                ErrorValue::new(ErrorCode::InvalidFormat, "Failed to decompress event trace")
+                    .with_cause(e),
+            )
+        })?;
+        decompressed.as_slice()
+    } else {
+        bytes
+    };
+
+    match format {
+        TraceFormat::JsonLines => std::str::from_utf8(bytes)
+            .map_err(|e| invalid_trace(e.to_string(), None))?
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|e| invalid_trace(e.to_string(), Some(i + 1)))
+            })
+            .collect(),
+        TraceFormat::Cbor => {
+            serde_cbor::from_slice(bytes).map_err(|e| invalid_trace(e.to_string(), None))
+        }
+    }
+}
+
+/// Guess the format of an undecorated file: JSON lines start with `{`
+pub fn detect_format(bytes: &[u8]) -> TraceFormat {
+    let first = if bytes.starts_with(&GZIP_MAGIC) {
+        CompressionUtils::decompress_gzip(bytes)
+            .ok()
+            .and_then(|bytes| bytes.iter().copied().find(|b| !b.is_ascii_whitespace()))
+    } else {
+        bytes.iter().copied().find(|b| !b.is_ascii_whitespace())
+    };
+    match first {
+        Some(b'{') => TraceFormat::JsonLines,
+        _ => TraceFormat::Cbor,
+    }
+}
+
+fn invalid_trace(cause: String, line: Option<usize>) -> AppError {
+    let mut error = ErrorValue::new(ErrorCode::DeserializationFailed, "Invalid event trace")
+        .with_cause(cause);
+    if let Some(line) = line {
+        error = error.with_context("line", line.to_string());
+    }
+    AppError::Serialization(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn events() -> Vec<EventData> {
+        vec![
+            EventData::new("user.created", json!({ "id": 1 })).with_source("frontend"),
+            EventData::new("user.lookup", json!({ "id": 1 }))
+                .with_target("user-service")
+                .with_correlation_id("req-1"),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip_formats() {
+        let events = events();
+        for format in [TraceFormat::JsonLines, TraceFormat::Cbor] {
+            for compress in [false, true] {
+                let bytes = encode(&events, format, compress).unwrap();
+                assert_eq!(bytes.starts_with(&GZIP_MAGIC), compress);
+                let decoded = decode(&bytes, format).unwrap();
+                assert_eq!(decoded.len(), 2);
+                assert_eq!(decoded[0].source.as_deref(), Some("frontend"));
+                assert_eq!(decoded[1].correlation_id.as_deref(), Some("req-1"));
+                assert_eq!(decoded[1].timestamp, events[1].timestamp);
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_from_path_and_bad_input() {
+        assert_eq!(
+            TraceFormat::from_path(Path::new("bug-42/trace.CBOR.gz")),
+            Some((TraceFormat::Cbor, true))
+        );
+        assert_eq!(
+            TraceFormat::from_path(Path::new("trace.ndjson")),
+            Some((TraceFormat::JsonLines, false))
+        );
+        assert_eq!(TraceFormat::from_path(Path::new("trace.txt")), None);
+        let cbor = encode(&events(), TraceFormat::Cbor, true).unwrap();
+        assert_eq!(detect_format(&cbor), TraceFormat::Cbor);
+        assert_eq!(detect_format(b"\n{}"), TraceFormat::JsonLines);
+
+        let err = decode(b"{\"event_type\":\"a\",\"payload\":1,\"timestamp\":1}\nnot json\n", TraceFormat::JsonLines)
+            .unwrap_err();
+        assert_eq!(err.to_value().code, ErrorCode::DeserializationFailed);
+        assert_eq!(err.to_value().context.as_ref().unwrap()["line"], "2");
+    }
+}
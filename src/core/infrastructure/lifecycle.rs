@@ -0,0 +1,477 @@
+// src/core/infrastructure/lifecycle.rs
+// Service lifecycle: dependency-ordered startup, reverse-ordered shutdown, health
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+
+/// A long-lived component with a start/stop lifecycle
+pub trait Service: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Names of services that must be started before this one and stopped after it
+    fn dependencies(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn start(&self) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn stop(&self) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn health(&self) -> ServiceHealth {
+        ServiceHealth::Healthy
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", content = "reason", rename_all = "snake_case")]
+pub enum ServiceHealth {
+    Healthy,
+    Degraded(String),
+    Unhealthy(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceState {
+    Registered,
+    Running,
+    Stopped,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceStatus {
+    pub name: String,
+    pub state: ServiceState,
+    pub health: ServiceHealth,
+    pub dependencies: Vec<String>,
+}
+
+struct Entry {
+    service: Arc<dyn Service>,
+    state: ServiceState,
+}
+
+/// Owns the registered services and the order they were started in
+pub struct ServiceManager {
+    services: Mutex<Vec<Entry>>,
+    /// Indices into `services`, in the order they were started
+    started: Mutex<Vec<usize>>,
+    shut_down: AtomicBool,
+}
+
+impl Default for ServiceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServiceManager {
+    pub fn new() -> Self {
+        Self {
+            services: Mutex::new(Vec::new()),
+            started: Mutex::new(Vec::new()),
+            shut_down: AtomicBool::new(false),
+        }
+    }
+
+    pub fn register(&self, service: Arc<dyn Service>) -> AppResult<()> {
+        let mut services = lock(&self.services, "register")?;
+        if services.iter().any(|e| e.service.name() == service.name()) {
+            return Err(AppError::Validation(
+                ErrorValue::new(ErrorCode::ValidationFailed, "Service already registered")
+                    .with_field("name")
+                    .with_context("service", service.name()),
+            ));
+        }
+        services.push(Entry {
+            service,
+            state: ServiceState::Registered,
+        });
+        Ok(())
+    }
+
+    /// Start every registered service that is not running, dependencies first.
+    /// If one fails, those started by this call are stopped again in reverse.
+    pub fn start_all(&self) -> AppResult<()> {
+        let pending: Vec<(usize, Arc<dyn Service>)> = {
+            let services = lock(&self.services, "start_all")?;
+            let order = start_order(&services)?;
+            order
+                .into_iter()
+                .filter(|&i| services[i].state != ServiceState::Running)
+                .map(|i| (i, Arc::clone(&services[i].service)))
+                .collect()
+        };
+
+        let mut started_now = Vec::new();
+        for (index, service) in pending {
+            info!("Starting service: {}", service.name());
+            match service.start() {
+                Ok(()) => {
+                    self.set_state(index, ServiceState::Running);
+                    lock(&self.started, "start_all")?.push(index);
+                    started_now.push(index);
+                }
+                Err(e) => {
+                    error!("Service {} failed to start: {}", service.name(), e);
+                    self.set_state(index, ServiceState::Failed);
+                    self.stop_indices(&started_now);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Stop running services in reverse start order; errors are logged, not returned
+    pub fn stop_all(&self) {
+        let started = match self.started.lock() {
+            Ok(started) => started.clone(),
+            Err(_) => return,
+        };
+        self.stop_indices(&started);
+    }
+
+    /// Stop all services and flush the logger; only the first call does anything
+    pub fn shutdown(&self) {
+        if self.shut_down.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        info!("Shutting down services...");
+        self.stop_all();
+        info!("Shutdown complete");
+        log::logger().flush();
+    }
+
+    pub fn statuses(&self) -> Vec<ServiceStatus> {
+        let Ok(services) = self.services.lock() else {
+            return Vec::new();
+        };
+        services
+            .iter()
+            .map(|entry| ServiceStatus {
+                name: entry.service.name().to_string(),
+                state: entry.state,
+                health: match entry.state {
+                    ServiceState::Running => entry.service.health(),
+                    ServiceState::Failed => ServiceHealth::Unhealthy("failed to start".to_string()),
+                    _ => ServiceHealth::Unhealthy("not running".to_string()),
+                },
+                dependencies: entry.service.dependencies(),
+            })
+            .collect()
+    }
+
+    /// True when every running service reports healthy
+    pub fn is_healthy(&self) -> bool {
+        self.statuses()
+            .iter()
+            .filter(|s| s.state == ServiceState::Running)
+            .all(|s| s.health == ServiceHealth::Healthy)
+    }
+
+    fn stop_indices(&self, indices: &[usize]) {
+        for &index in indices.iter().rev() {
+            let service = match self.services.lock() {
+                Ok(services) if services[index].state == ServiceState::Running => {
+                    Arc::clone(&services[index].service)
+                }
+                _ => continue,
+            };
+            info!("Stopping service: {}", service.name());
+            if let Err(e) = service.stop() {
+                warn!("Service {} did not stop cleanly: {}", service.name(), e);
+            }
+            self.set_state(index, ServiceState::Stopped);
+            if let Ok(mut started) = self.started.lock() {
+                started.retain(|&i| i != index);
+            }
+        }
+    }
+
+    fn set_state(&self, index: usize, state: ServiceState) {
+        if let Ok(mut services) = self.services.lock() {
+            services[index].state = state;
+        }
+    }
+}
+
+fn lock<'a, T>(mutex: &'a Mutex<T>, operation: &str) -> AppResult<MutexGuard<'a, T>> {
+    mutex.lock().map_err(|e| {
+        AppError::LockPoisoned(
+            ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire service manager lock")
+                .with_cause(e.to_string())
+                .with_context("operation", operation),
+        )
+    })
+}
+
+/// Dependencies before dependents; otherwise registration order
+fn start_order(services: &[Entry]) -> AppResult<Vec<usize>> {
+    let index: HashMap<&str, usize> = services
+        .iter()
+        .enumerate()
+        .map(|(i, e)| (e.service.name(), i))
+        .collect();
+
+    // 0 = unvisited, 1 = visiting, 2 = done
+    let mut marks = vec![0u8; services.len()];
+    let mut order = Vec::with_capacity(services.len());
+    let mut path = Vec::new();
+
+    fn visit(
+        i: usize,
+        services: &[Entry],
+        index: &HashMap<&str, usize>,
+        marks: &mut [u8],
+        order: &mut Vec<usize>,
+        path: &mut Vec<usize>,
+    ) -> AppResult<()> {
+        match marks[i] {
+            2 => return Ok(()),
+            1 => {
+                let start = path.iter().position(|&p| p == i).unwrap_or(0);
+                let cycle: Vec<&str> = path[start..]
+                    .iter()
+                    .chain(std::iter::once(&i))
+                    .map(|&p| services[p].service.name())
+                    .collect();
+                return Err(AppError::DependencyInjection(
+                    ErrorValue::new(ErrorCode::ValidationFailed, "Circular service dependency")
+                        .with_details(cycle.join(" -> ")),
+                ));
+            }
+            _ => {}
+        }
+        marks[i] = 1;
+        path.push(i);
+        for dependency in services[i].service.dependencies() {
+            let Some(&d) = index.get(dependency.as_str()) else {
+                return Err(AppError::DependencyInjection(
+                    ErrorValue::new(ErrorCode::ResourceNotFound, "Unknown service dependency")
+                        .with_context("service", services[i].service.name())
+                        .with_context("dependency", dependency),
+                ));
+            };
+            visit(d, services, index, marks, order, path)?;
+        }
+        path.pop();
+        marks[i] = 2;
+        order.push(i);
+        Ok(())
+    }
+
+    for i in 0..services.len() {
+        visit(i, services, &index, &mut marks, &mut order, &mut path)?;
+    }
+    Ok(order)
+}
+
+/// A component that stops by being dropped, like the transport servers
+pub struct OwnedService<T: Send> {
+    name: String,
+    dependencies: Vec<String>,
+    inner: Mutex<Option<T>>,
+}
+
+impl<T: Send> OwnedService<T> {
+    pub fn new(name: impl Into<String>, inner: T) -> Self {
+        Self {
+            name: name.into(),
+            dependencies: Vec::new(),
+            inner: Mutex::new(Some(inner)),
+        }
+    }
+
+    pub fn depends_on(mut self, name: impl Into<String>) -> Self {
+        self.dependencies.push(name.into());
+        self
+    }
+
+    /// Borrow the component while it is running
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.inner.lock().ok()?.as_ref().map(f)
+    }
+}
+
+impl<T: Send> Service for OwnedService<T> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn dependencies(&self) -> Vec<String> {
+        self.dependencies.clone()
+    }
+
+    fn stop(&self) -> AppResult<()> {
+        // Take it out first so the component's Drop runs without the lock held
+        let inner = lock(&self.inner, "stop")?.take();
+        drop(inner);
+        Ok(())
+    }
+
+    fn health(&self) -> ServiceHealth {
+        match self.with(|_| ()) {
+            Some(()) => ServiceHealth::Healthy,
+            None => ServiceHealth::Unhealthy("stopped".to_string()),
+        }
+    }
+}
+
+static SERVICE_MANAGER: OnceLock<ServiceManager> = OnceLock::new();
+
+pub fn get_service_manager() -> &'static ServiceManager {
+    SERVICE_MANAGER.get_or_init(ServiceManager::new)
+}
+
+/// Run `on_signal` on SIGINT/SIGTERM (Ctrl+C on Windows) so the caller can leave
+/// its event loop and shut down; a second signal exits immediately
+pub fn install_signal_handler<F>(on_signal: F) -> AppResult<()>
+where
+    F: Fn() + Send + 'static,
+{
+    let signals = AtomicUsize::new(0);
+    ctrlc::set_handler(move || {
+        if signals.fetch_add(1, Ordering::SeqCst) == 0 {
+            info!("Termination signal received, shutting down...");
+            on_signal();
+        } else {
+            warn!("Second termination signal, exiting without cleanup");
+            log::logger().flush();
+            std::process::exit(130);
+        }
+    })
+    .map_err(|e| {
+        AppError::Configuration(
+            ErrorValue::new(ErrorCode::InternalError, "Failed to install signal handler")
+                .with_cause(e.to_string()),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Recorder {
+        name: &'static str,
+        dependencies: Vec<&'static str>,
+        log: Arc<Mutex<Vec<String>>>,
+        fail_start: bool,
+    }
+
+    impl Recorder {
+        fn new(name: &'static str, dependencies: &[&'static str], log: &Arc<Mutex<Vec<String>>>) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                dependencies: dependencies.to_vec(),
+                log: Arc::clone(log),
+                fail_start: false,
+            })
+        }
+    }
+
+    impl Service for Recorder {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn dependencies(&self) -> Vec<String> {
+            self.dependencies.iter().map(|d| d.to_string()).collect()
+        }
+
+        fn start(&self) -> AppResult<()> {
+            if self.fail_start {
+                return Err(AppError::Configuration(ErrorValue::new(ErrorCode::InternalError, "boom")));
+            }
+            self.log.lock().unwrap().push(format!("start {}", self.name));
+            Ok(())
+        }
+
+        fn stop(&self) -> AppResult<()> {
+            self.log.lock().unwrap().push(format!("stop {}", self.name));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_ordered_start_and_reverse_shutdown() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let manager = ServiceManager::new();
+        manager.register(Recorder::new("http", &["database"], &log)).unwrap();
+        manager.register(Recorder::new("metrics", &[], &log)).unwrap();
+        manager.register(Recorder::new("database", &[], &log)).unwrap();
+        assert!(manager.register(Recorder::new("metrics", &[], &log)).is_err());
+
+        manager.start_all().unwrap();
+        assert!(manager.is_healthy());
+        manager.shutdown();
+        manager.shutdown();
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "start database", "start http", "start metrics",
+                "stop metrics", "stop http", "stop database",
+            ]
+        );
+        assert!(manager.statuses().iter().all(|s| s.state == ServiceState::Stopped));
+    }
+
+    #[test]
+    fn test_failed_start_rolls_back() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let manager = ServiceManager::new();
+        manager.register(Recorder::new("database", &[], &log)).unwrap();
+        manager
+            .register(Arc::new(Recorder {
+                fail_start: true,
+                ..Arc::into_inner(Recorder::new("http", &["database"], &log)).unwrap()
+            }))
+            .unwrap();
+
+        assert!(manager.start_all().is_err());
+        assert_eq!(*log.lock().unwrap(), vec!["start database", "stop database"]);
+        let http = manager.statuses().into_iter().find(|s| s.name == "http").unwrap();
+        assert_eq!(http.state, ServiceState::Failed);
+    }
+
+    #[test]
+    fn test_bad_dependencies() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let manager = ServiceManager::new();
+        manager.register(Recorder::new("a", &["b"], &log)).unwrap();
+        manager.register(Recorder::new("b", &["a"], &log)).unwrap();
+        let err = manager.start_all().unwrap_err();
+        assert_eq!(err.to_value().details.as_deref(), Some("a -> b -> a"));
+
+        let manager = ServiceManager::new();
+        manager.register(Recorder::new("a", &["missing"], &log)).unwrap();
+        assert_eq!(manager.start_all().unwrap_err().to_value().code, ErrorCode::ResourceNotFound);
+        assert!(log.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_owned_service_drops_on_stop() {
+        let manager = ServiceManager::new();
+        let service = Arc::new(OwnedService::new("server", String::from("running")).depends_on("db"));
+        manager.register(Arc::new(OwnedService::new("db", ()))).unwrap();
+        manager.register(Arc::clone(&service) as Arc<dyn Service>).unwrap();
+        manager.start_all().unwrap();
+        assert_eq!(service.with(|s| s.len()), Some(7));
+
+        manager.stop_all();
+        assert_eq!(service.with(|s| s.len()), None);
+        assert!(matches!(service.health(), ServiceHealth::Unhealthy(_)));
+    }
+}
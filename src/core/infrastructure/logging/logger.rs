@@ -2,13 +2,62 @@
 // Logger implementation
 
 use log::{Metadata, Record};
+use serde::Serialize;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 
 use super::formatter::LogFormatter;
 
+/// Structured log record handed to log sinks
+#[derive(Debug, Clone, Serialize)]
+pub struct LogLine {
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    pub timestamp: i64,
+}
+
+impl LogLine {
+    fn from_record(record: &Record) -> Self {
+        Self {
+            level: record.level().to_string(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+}
+
+/// Extra destination for log lines (e.g. WebSocket push); must not log itself
+pub type LogSink = Arc<dyn Fn(&LogLine) + Send + Sync>;
+
+static LOG_SINKS: OnceLock<RwLock<Vec<LogSink>>> = OnceLock::new();
+
+fn log_sinks() -> &'static RwLock<Vec<LogSink>> {
+    LOG_SINKS.get_or_init(|| RwLock::new(Vec::new()))
+}
+
+pub fn add_log_sink(sink: LogSink) {
+    if let Ok(mut sinks) = log_sinks().write() {
+        sinks.push(sink);
+    }
+}
+
+fn forward_to_sinks(record: &Record) {
+    let Ok(sinks) = log_sinks().read() else {
+        return;
+    };
+    if sinks.is_empty() {
+        return;
+    }
+    let line = LogLine::from_record(record);
+    for sink in sinks.iter() {
+        sink(&line);
+    }
+}
+
 pub struct Logger {
     file_path: Mutex<PathBuf>,
     max_file_size: u64,
@@ -143,8 +192,12 @@ impl log::Log for Logger {
             }
 
             self.write_to_file(&json_msg);
+            forward_to_sinks(record);
         }
     }
 
-    fn flush(&self) {}
+    /// File writes are flushed per line; this catches buffered console output
+    fn flush(&self) {
+        let _ = std::io::stdout().flush();
+    }
 }
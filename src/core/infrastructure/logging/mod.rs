@@ -7,7 +7,7 @@ pub mod formatter;
 pub mod logger;
 
 pub use config::LoggingConfig;
-pub use logger::Logger;
+pub use logger::{add_log_sink, Logger};
 
 /// Initialize logging with default configuration
 pub fn init_logging() -> Result<(), Box<dyn std::error::Error>> {
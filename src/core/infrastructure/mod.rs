@@ -1,8 +1,9 @@
 // src/core/infrastructure/mod.rs
-// Infrastructure services - database, config, logging, DI, event bus
+// Infrastructure services - database, config, logging, DI, event bus, lifecycle
 
 pub mod config;
 pub mod database;
 pub mod di;
 pub mod event_bus;
+pub mod lifecycle;
 pub mod logging;
@@ -0,0 +1,10 @@
+// src/core/presentation/http/mod.rs
+// HTTP/REST transport - loopback server backed by the RPC registry
+
+#![allow(dead_code)]
+
+pub mod request;
+pub mod routes;
+pub mod server;
+
+pub use server::HttpServer;
@@ -0,0 +1,257 @@
+// src/core/presentation/http/request.rs
+// Minimal HTTP/1.1 request parsing and response writing for the loopback REST server
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::utils::serialization::SerializationFormat;
+
+/// Largest request body the server accepts
+pub const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Largest request head (request line + headers) the server accepts
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// Header names are stored lowercase
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Read one request from the stream
+    pub fn read_from<R: Read>(stream: R) -> AppResult<Self> {
+        let mut reader = BufReader::new(stream);
+        let mut head_bytes = 0usize;
+
+        let request_line = read_line(&mut reader, &mut head_bytes)?;
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_uppercase();
+        let target = parts.next().unwrap_or_default().to_string();
+        if method.is_empty() || !target.starts_with('/') {
+            return Err(bad_request("Malformed HTTP request line", &request_line));
+        }
+
+        let mut headers = HashMap::new();
+        loop {
+            let line = read_line(&mut reader, &mut head_bytes)?;
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+            }
+        }
+
+        let content_length = match headers.get("content-length") {
+            Some(value) => value
+                .parse::<usize>()
+                .map_err(|_| bad_request("Invalid Content-Length header", value))?,
+            None => 0,
+        };
+        if content_length > MAX_BODY_BYTES {
+            return Err(AppError::Validation(
+                ErrorValue::new(ErrorCode::InvalidFieldValue, "Request body too large")
+                    .with_field("content-length")
+                    .with_context("limit", MAX_BODY_BYTES.to_string()),
+            ));
+        }
+
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body)?;
+
+        let (path, query_string) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), query.to_string()),
+            None => (target, String::new()),
+        };
+        let query = url::form_urlencoded::parse(query_string.as_bytes())
+            .into_owned()
+            .collect();
+
+        Ok(Self {
+            method,
+            path,
+            query,
+            headers,
+            body,
+        })
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    /// Request body as UTF-8 text, `None` when empty
+    pub fn body_text(&self) -> AppResult<Option<String>> {
+        if self.body.is_empty() {
+            return Ok(None);
+        }
+        String::from_utf8(self.body.clone()).map(Some).map_err(|e| {
+            AppError::Serialization(
+                ErrorValue::new(ErrorCode::InvalidFormat, "Request body is not valid UTF-8")
+                    .with_cause(e.to_string()),
+            )
+        })
+    }
+
+    /// Format of the request body: msgpack/cbor by Content-Type, JSON otherwise
+    pub fn content_format(&self) -> SerializationFormat {
+        self.header("content-type")
+            .and_then(SerializationFormat::from_mime_type)
+            .unwrap_or(SerializationFormat::Json)
+    }
+
+    /// Response format: the first known type in Accept, else JSON
+    ///
+    /// Binary bodies go only to clients that ask for them, so `*/*` from curl
+    /// or fetch gets JSON whatever the configured format.
+    pub fn accept_format(&self) -> SerializationFormat {
+        self.header("accept")
+            .and_then(|accept| accept.split(',').find_map(SerializationFormat::from_mime_type))
+            .unwrap_or(SerializationFormat::Json)
+    }
+
+    /// Path split into non-empty segments
+    pub fn segments(&self) -> Vec<&str> {
+        self.path.split('/').filter(|s| !s.is_empty()).collect()
+    }
+}
+
+fn read_line<R: BufRead>(reader: &mut R, head_bytes: &mut usize) -> AppResult<String> {
+    let mut line = String::new();
+    let read = reader.read_line(&mut line)?;
+    *head_bytes += read;
+    if read == 0 {
+        return Err(bad_request("Connection closed before request was complete", ""));
+    }
+    if *head_bytes > MAX_HEAD_BYTES {
+        return Err(bad_request("Request headers too large", ""));
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+fn bad_request(message: &str, value: &str) -> AppError {
+    AppError::Validation(
+        ErrorValue::new(ErrorCode::InvalidFormat, message).with_context("value", value),
+    )
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn json(status: u16, body: &serde_json::Value) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string().into_bytes(),
+        }
+    }
+
+    pub fn bytes(status: u16, content_type: &str, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body,
+        }
+    }
+
+    pub fn empty(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn write_to<W: Write>(&self, mut stream: W) -> std::io::Result<()> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason_phrase(self.status));
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!("Content-Length: {}\r\n", self.body.len()));
+        head.push_str("Connection: close\r\n\r\n");
+
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(&self.body)?;
+        stream.flush()
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        422 => "Unprocessable Entity",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_with_body_and_query() {
+        let raw = "POST /api/users?limit=5&q=a%20b HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: 13\r\n\r\n{\"name\":\"x\"}\n";
+        let req = HttpRequest::read_from(raw.as_bytes()).expect("Failed to parse request");
+
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/api/users");
+        assert_eq!(req.query.get("q"), Some(&"a b".to_string()));
+        assert_eq!(req.header("content-type"), Some("application/json"));
+        assert_eq!(req.segments(), vec!["api", "users"]);
+        assert_eq!(req.body_text().unwrap().as_deref(), Some("{\"name\":\"x\"}\n"));
+    }
+
+    #[test]
+    fn test_negotiated_formats() {
+        let raw = "POST /api/rpc/x HTTP/1.1\r\nContent-Type: application/cbor\r\nAccept: text/html, application/msgpack\r\n\r\n";
+        let req = HttpRequest::read_from(raw.as_bytes()).unwrap();
+        assert_eq!(req.content_format(), SerializationFormat::Cbor);
+        assert_eq!(req.accept_format(), SerializationFormat::MessagePack);
+
+        let raw = "GET /api/users HTTP/1.1\r\nAccept: */*\r\n\r\n";
+        let req = HttpRequest::read_from(raw.as_bytes()).unwrap();
+        assert_eq!(req.accept_format(), SerializationFormat::Json);
+    }
+
+    #[test]
+    fn test_parse_malformed_request() {
+        assert!(HttpRequest::read_from("garbage\r\n\r\n".as_bytes()).is_err());
+        assert!(HttpRequest::read_from("".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_write_response() {
+        let mut out = Vec::new();
+        HttpResponse::json(404, &serde_json::json!({"ok": false}))
+            .write_to(&mut out)
+            .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(text.ends_with("{\"ok\":false}"));
+    }
+}
@@ -0,0 +1,293 @@
+// src/core/presentation/http/routes.rs
+// REST routes mapped onto the RPC methods exposed through WebUI binds
+
+use crate::core::error::{AppError, ErrorCode, ErrorValue};
+use crate::core::presentation::rpc;
+
+use super::request::HttpRequest;
+
+/// RPC call resolved from a REST request
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteTarget {
+    pub rpc_method: String,
+    pub payload: Option<String>,
+    /// Status used for a successful response
+    pub success_status: u16,
+}
+
+impl RouteTarget {
+    fn new(rpc_method: &str, payload: Option<String>) -> Self {
+        Self {
+            rpc_method: rpc_method.to_string(),
+            payload,
+            success_status: 200,
+        }
+    }
+
+    fn created(mut self) -> Self {
+        self.success_status = 201;
+        self
+    }
+}
+
+/// Routing failure with the HTTP status it should be answered with
+#[derive(Debug, Clone)]
+pub struct RouteError {
+    pub status: u16,
+    pub error: AppError,
+}
+
+impl From<AppError> for RouteError {
+    fn from(error: AppError) -> Self {
+        Self {
+            status: error.to_value().code.http_status(),
+            error,
+        }
+    }
+}
+
+/// Resolve a REST request to an RPC call
+///
+/// Bodies are transcoded to JSON first, so the table below works the same for
+/// JSON, MessagePack and CBOR requests.
+///
+/// | Method | Path                        | RPC method             |
+/// |--------|-----------------------------|------------------------|
+/// | GET    | /api/users                  | get_users              |
+/// | POST   | /api/users                  | create_user            |
+/// | PUT    | /api/users/{id}             | update_user            |
+/// | DELETE | /api/users/{id}             | delete_user            |
+/// | GET    | /api/sysinfo/{section}      | get_{section}_info     |
+/// | GET    | /api/logs                   | get_logs               |
+/// | GET    | /api/events                 | event:history          |
+/// | POST   | /api/events                 | event:publish          |
+/// | DELETE | /api/events                 | event:clear_history    |
+/// | GET    | /api/events/stats           | event:stats            |
+/// | GET    | /api/events/query           | event:query            |
+/// | GET    | /api/events/dead-letters    | event:dead_letters     |
+/// | DELETE | /api/events/dead-letters    | event:clear_dead_letters |
+/// | GET    | /api/events/schemas         | event:schemas          |
+/// | POST   | /api/events/export          | event:export           |
+/// | POST   | /api/events/import          | event:import           |
+/// | POST   | /api/events/schemas         | event:register_schema  |
+/// | GET    | /api/di/services            | di:services            |
+/// | GET    | /api/di/validate            | di:validate            |
+/// | GET    | /api/settings               | settings:get           |
+/// | PUT    | /api/settings               | settings:update        |
+/// | POST   | /api/rpc/{method}           | {method}               |
+pub fn resolve(req: &HttpRequest) -> Result<RouteTarget, RouteError> {
+    let segments = req.segments();
+    let body = rpc::decode_payload(&req.body, req.content_format())?;
+    let method = req.method.as_str();
+
+    let target = match (method, segments.as_slice()) {
+        ("GET", ["api", "users"]) => RouteTarget::new("get_users", None),
+        ("POST", ["api", "users"]) => RouteTarget::new("create_user", body).created(),
+        ("PUT" | "PATCH", ["api", "users", id]) => {
+            RouteTarget::new("update_user", Some(with_id(body, id)))
+        }
+        ("DELETE", ["api", "users", id]) => {
+            RouteTarget::new("delete_user", Some(with_id(None, id)))
+        }
+        ("GET", ["api", "sysinfo", section]) => match *section {
+            "system" | "memory" | "process" | "network" | "database" | "config" | "services" => {
+                RouteTarget::new(&format!("get_{}_info", section), None)
+            }
+            _ => return Err(not_found(req).into()),
+        },
+        ("GET", ["api", "logs"]) => RouteTarget::new("get_logs", None),
+        ("GET", ["api", "events"]) => RouteTarget::new(
+            "event:history",
+            Some(query_object(req, &["event_type"], &["limit"])),
+        ),
+        ("POST", ["api", "events"]) => RouteTarget::new("event:publish", body).created(),
+        ("DELETE", ["api", "events"]) => RouteTarget::new("event:clear_history", None),
+        ("GET", ["api", "events", "stats"]) => RouteTarget::new("event:stats", None),
+        ("GET", ["api", "events", "query"]) => RouteTarget::new(
+            "event:query",
+            Some(query_object(
+                req,
+                &["event_type", "source", "target"],
+                &["since", "until", "limit"],
+            )),
+        ),
+        ("GET", ["api", "events", "dead-letters"]) => RouteTarget::new("event:dead_letters", None),
+        ("DELETE", ["api", "events", "dead-letters"]) => {
+            RouteTarget::new("event:clear_dead_letters", None)
+        }
+        ("GET", ["api", "events", "schemas"]) => RouteTarget::new("event:schemas", None),
+        ("POST", ["api", "events", "schemas"]) => {
+            RouteTarget::new("event:register_schema", body).created()
+        }
+        ("POST", ["api", "events", "export"]) => RouteTarget::new("event:export", body),
+        ("POST", ["api", "events", "import"]) => RouteTarget::new("event:import", body),
+        ("GET", ["api", "di", "services"]) => RouteTarget::new("di:services", None),
+        ("GET", ["api", "di", "validate"]) => RouteTarget::new("di:validate", None),
+        ("GET", ["api", "settings"]) => RouteTarget::new("settings:get", None),
+        ("PUT", ["api", "settings"]) => RouteTarget::new("settings:update", body),
+        ("POST", ["api", "rpc", rpc_method]) => RouteTarget::new(rpc_method, body),
+        (_, ["api", "users"])
+        | (_, ["api", "users", _])
+        | (_, ["api", "events"])
+        | (_, ["api", "events", "stats"])
+        | (_, ["api", "events", "query"])
+        | (_, ["api", "events", "dead-letters"])
+        | (_, ["api", "events", "schemas"])
+        | (_, ["api", "events", "export" | "import"])
+        | (_, ["api", "logs"])
+        | (_, ["api", "sysinfo", _])
+        | (_, ["api", "di", "services" | "validate"])
+        | (_, ["api", "settings"])
+        | (_, ["api", "rpc", _]) => return Err(method_not_allowed(req)),
+        _ => return Err(not_found(req).into()),
+    };
+
+    Ok(target)
+}
+
+/// Merge the path id into a JSON object body
+///
+/// Non-numeric ids are passed as strings so the RPC dispatcher reports them as
+/// invalid field values; non-object bodies are passed through untouched.
+fn with_id(body: Option<String>, id: &str) -> String {
+    let id_value = id
+        .parse::<i64>()
+        .map(serde_json::Value::from)
+        .unwrap_or_else(|_| serde_json::Value::from(id));
+
+    let mut object = match body.as_deref().map(serde_json::from_str::<serde_json::Value>) {
+        None => serde_json::Map::new(),
+        Some(Ok(serde_json::Value::Object(map))) => map,
+        Some(_) => return body.unwrap_or_default(),
+    };
+    object.insert("id".to_string(), id_value);
+    serde_json::Value::Object(object).to_string()
+}
+
+/// Build a JSON request object from query parameters
+///
+/// Numeric keys that fail to parse are passed as strings so the RPC dispatcher
+/// reports them as invalid field values.
+fn query_object(req: &HttpRequest, string_keys: &[&str], number_keys: &[&str]) -> String {
+    let mut object = serde_json::Map::new();
+    for key in string_keys {
+        if let Some(value) = req.query.get(*key) {
+            object.insert(key.to_string(), serde_json::json!(value));
+        }
+    }
+    for key in number_keys {
+        if let Some(raw) = req.query.get(*key) {
+            let value = raw
+                .parse::<i64>()
+                .map(serde_json::Value::from)
+                .unwrap_or_else(|_| serde_json::Value::from(raw.as_str()));
+            object.insert(key.to_string(), value);
+        }
+    }
+    serde_json::Value::Object(object).to_string()
+}
+
+fn not_found(req: &HttpRequest) -> AppError {
+    AppError::NotFound(
+        ErrorValue::new(ErrorCode::ResourceNotFound, format!("No route for {}", req.path))
+            .with_context("method", req.method.as_str())
+            .with_context("path", req.path.as_str()),
+    )
+}
+
+fn method_not_allowed(req: &HttpRequest) -> RouteError {
+    RouteError {
+        status: 405,
+        error: AppError::Validation(
+            ErrorValue::new(
+                ErrorCode::InvalidFormat,
+                format!("Method {} not allowed on {}", req.method, req.path),
+            )
+            .with_context("method", req.method.as_str())
+            .with_context("path", req.path.as_str()),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, target: &str, body: &str) -> HttpRequest {
+        let raw = format!(
+            "{} {} HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            target,
+            body.len(),
+            body
+        );
+        HttpRequest::read_from(raw.as_bytes()).expect("Failed to build request")
+    }
+
+    #[test]
+    fn test_resolve_user_routes() {
+        let target = resolve(&request("GET", "/api/users", "")).unwrap();
+        assert_eq!(target.rpc_method, "get_users");
+
+        let target = resolve(&request("POST", "/api/users", r#"{"name":"a"}"#)).unwrap();
+        assert_eq!(target.rpc_method, "create_user");
+        assert_eq!(target.success_status, 201);
+
+        let target = resolve(&request("PUT", "/api/users/7", r#"{"name":"b"}"#)).unwrap();
+        let payload: serde_json::Value =
+            serde_json::from_str(target.payload.as_deref().unwrap()).unwrap();
+        assert_eq!(payload["id"], 7);
+        assert_eq!(payload["name"], "b");
+
+        let target = resolve(&request("DELETE", "/api/users/abc", "")).unwrap();
+        assert_eq!(target.payload.as_deref(), Some(r#"{"id":"abc"}"#));
+    }
+
+    #[test]
+    fn test_resolve_event_and_sysinfo_routes() {
+        let target = resolve(&request("GET", "/api/events?event_type=user.created&limit=5", "")).unwrap();
+        assert_eq!(target.rpc_method, "event:history");
+        let payload: serde_json::Value =
+            serde_json::from_str(target.payload.as_deref().unwrap()).unwrap();
+        assert_eq!(payload["limit"], 5);
+
+        let target = resolve(&request("GET", "/api/events/query?source=frontend&since=100", "")).unwrap();
+        assert_eq!(target.rpc_method, "event:query");
+        let payload: serde_json::Value =
+            serde_json::from_str(target.payload.as_deref().unwrap()).unwrap();
+        assert_eq!(payload["source"], "frontend");
+        assert_eq!(payload["since"], 100);
+
+        let target = resolve(&request("DELETE", "/api/events/dead-letters", "")).unwrap();
+        assert_eq!(target.rpc_method, "event:clear_dead_letters");
+
+        let target = resolve(&request("POST", "/api/events/schemas", r#"{"event_type":"ping"}"#)).unwrap();
+        assert_eq!(target.rpc_method, "event:register_schema");
+        assert_eq!(target.success_status, 201);
+
+        let target = resolve(&request("POST", "/api/events/export", r#"{"path":"bug.jsonl.gz"}"#)).unwrap();
+        assert_eq!(target.rpc_method, "event:export");
+        assert_eq!(resolve(&request("GET", "/api/events/import", "")).unwrap_err().status, 405);
+
+        let target = resolve(&request("GET", "/api/sysinfo/memory", "")).unwrap();
+        assert_eq!(target.rpc_method, "get_memory_info");
+
+        let target = resolve(&request("GET", "/api/di/services", "")).unwrap();
+        assert_eq!(target.rpc_method, "di:services");
+        assert_eq!(resolve(&request("POST", "/api/di/validate", "")).unwrap_err().status, 405);
+
+        let target = resolve(&request("PUT", "/api/settings", r#"{"log_level":"warn"}"#)).unwrap();
+        assert_eq!(target.rpc_method, "settings:update");
+        assert_eq!(resolve(&request("DELETE", "/api/settings", "")).unwrap_err().status, 405);
+    }
+
+    #[test]
+    fn test_resolve_unknown_routes() {
+        let err = resolve(&request("GET", "/api/nothing", "")).unwrap_err();
+        assert_eq!(err.status, 404);
+        assert!(matches!(err.error, AppError::NotFound(_)));
+
+        let err = resolve(&request("PATCH", "/api/events", "")).unwrap_err();
+        assert_eq!(err.status, 405);
+    }
+}
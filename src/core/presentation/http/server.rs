@@ -0,0 +1,384 @@
+// src/core/presentation/http/server.rs
+// Loopback HTTP server exposing the RPC registry as a REST API
+
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use log::{debug, error, info, warn};
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::lifecycle::get_service_manager;
+use crate::core::presentation::origin::{is_loopback_host, is_loopback_origin};
+use crate::core::presentation::rpc::{self, RpcContext};
+use crate::utils::serialization::SerializationFormat;
+
+use super::request::{HttpRequest, HttpResponse};
+use super::routes::{self, RouteError};
+
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Running REST server; stopped when dropped
+pub struct HttpServer {
+    addr: SocketAddr,
+    running: Arc<AtomicBool>,
+    acceptor: Option<JoinHandle<()>>,
+}
+
+impl HttpServer {
+    /// Bind to a loopback address (use port 0 for a random port) and start serving
+    pub fn start(bind_addr: &str) -> AppResult<Self> {
+        let listener = TcpListener::bind(bind_addr).map_err(|e| {
+            AppError::Configuration(
+                ErrorValue::new(ErrorCode::ConfigInvalid, "Failed to bind HTTP server")
+                    .with_cause(e.to_string())
+                    .with_context("address", bind_addr),
+            )
+        })?;
+        let addr = listener.local_addr()?;
+
+        if !addr.ip().is_loopback() {
+            return Err(AppError::Configuration(
+                ErrorValue::new(ErrorCode::ConfigInvalid, "HTTP server must bind to a loopback address")
+                    .with_field("communication.http_port")
+                    .with_context("address", addr.to_string()),
+            ));
+        }
+
+        let running = Arc::new(AtomicBool::new(true));
+        let acceptor_running = Arc::clone(&running);
+        let acceptor = thread::Builder::new()
+            .name("http-acceptor".to_string())
+            .spawn(move || accept_loop(listener, acceptor_running))?;
+
+        info!("HTTP REST server listening on http://{}", addr);
+
+        Ok(Self {
+            addr,
+            running,
+            acceptor: Some(acceptor),
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Stop accepting connections and wait for the acceptor thread
+    pub fn stop(&mut self) {
+        if !self.running.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        // Wake the blocking accept() so the loop can observe the flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.acceptor.take() {
+            let _ = handle.join();
+        }
+        info!("HTTP REST server stopped");
+    }
+}
+
+impl Drop for HttpServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn accept_loop(listener: TcpListener, running: Arc<AtomicBool>) {
+    for stream in listener.incoming() {
+        if !running.load(Ordering::SeqCst) {
+            break;
+        }
+        match stream {
+            Ok(stream) => {
+                let spawned = thread::Builder::new()
+                    .name("http-connection".to_string())
+                    .spawn(move || handle_connection(stream));
+                if let Err(e) = spawned {
+                    error!("Failed to spawn HTTP connection thread: {}", e);
+                }
+            }
+            Err(e) => warn!("HTTP accept failed: {}", e),
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let port = stream.local_addr().map(|addr| addr.port()).unwrap_or_default();
+
+    let response = match HttpRequest::read_from(&stream) {
+        Ok(req) => {
+            let origin = req.header("origin").map(str::to_string);
+            with_cors(handle_request(&req, port), origin.as_deref())
+        }
+        Err(e) => {
+            debug!("Rejected malformed HTTP request: {}", e);
+            HttpResponse::json(400, &rpc::error_envelope(None, &e))
+        }
+    };
+
+    if let Err(e) = response.write_to(&stream) {
+        debug!("Failed to write HTTP response: {}", e);
+    }
+}
+
+/// Encode an envelope in the format the client accepts, falling back to JSON
+fn envelope_response(req: &HttpRequest, status: u16, envelope: &serde_json::Value) -> HttpResponse {
+    let format = req.accept_format();
+    if format.is_binary() {
+        match rpc::encode_envelope(envelope, format) {
+            Ok(bytes) => return HttpResponse::bytes(status, format.mime_type(), bytes),
+            Err(e) => error!("Falling back to JSON response: {}", e),
+        }
+    }
+    HttpResponse::json(status, envelope)
+}
+
+/// Route a parsed request, received on `port`, through the RPC registry
+pub fn handle_request(req: &HttpRequest, port: u16) -> HttpResponse {
+    let request_id = req.header("x-request-id").map(str::to_string);
+
+    if let Err(rejected) = check_request(req, port) {
+        debug!("Rejected HTTP {} {}: {}", req.method, req.path, rejected.error);
+        let body = rpc::error_envelope(request_id.as_deref(), &rejected.error);
+        return envelope_response(req, rejected.status, &body);
+    }
+
+    if req.method == "OPTIONS" {
+        return HttpResponse::empty(204);
+    }
+
+    if req.method == "GET" && req.path == "/api/health" {
+        let body = rpc::success_envelope(
+            request_id.as_deref(),
+            serde_json::json!({
+                "status": if get_service_manager().is_healthy() { "ok" } else { "degraded" },
+                "serialization": SerializationFormat::selected().name(),
+                "methods": rpc::registered_methods(),
+            }),
+        );
+        return envelope_response(req, 200, &body);
+    }
+
+    let target = match routes::resolve(req) {
+        Ok(target) => target,
+        Err(route_error) => {
+            let body = rpc::error_envelope(request_id.as_deref(), &route_error.error);
+            return envelope_response(req, route_error.status, &body);
+        }
+    };
+
+    let mut ctx = RpcContext::new(target.rpc_method.clone(), 0);
+    if let Some(ref id) = request_id {
+        ctx = ctx.with_request_id(id.clone());
+    }
+
+    debug!(
+        "[Communication] HTTP {} {} → RPC {} (request {:?})",
+        req.method, req.path, ctx.method, ctx.request_id
+    );
+
+    let result = rpc::dispatch(&ctx, target.payload.as_deref());
+    let status = match result {
+        Ok(_) => target.success_status,
+        Err(ref e) => {
+            error!("HTTP RPC {} failed: {}", ctx.method, e);
+            e.to_value().code.http_status()
+        }
+    };
+
+    let response = envelope_response(req, status, &rpc::envelope_for(&ctx, &result));
+    match request_id {
+        Some(id) => response.with_header("X-Request-Id", id),
+        None => response,
+    }
+}
+
+/// Refuse requests a page on another site could make without a CORS preflight
+///
+/// Browsers send those anyway and only hide the response, so a foreign
+/// `Origin` is refused before anything runs, and a body must be typed as one
+/// of the serialization formats, which browsers never send without asking first.
+/// A `Host` other than this server's loopback address is a DNS-rebinding page.
+fn check_request(req: &HttpRequest, port: u16) -> Result<(), RouteError> {
+    let host = req.header("host");
+    if !host.is_some_and(|h| is_loopback_host(h, port)) {
+        return Err(RouteError {
+            status: 403,
+            error: AppError::Validation(
+                ErrorValue::new(ErrorCode::ValidationFailed, "Host not allowed")
+                    .with_context("host", host.unwrap_or("none")),
+            ),
+        });
+    }
+    if let Some(origin) = req.header("origin").filter(|o| !is_loopback_origin(o)) {
+        return Err(RouteError {
+            status: 403,
+            error: AppError::Validation(
+                ErrorValue::new(ErrorCode::ValidationFailed, "Origin not allowed").with_context("origin", origin),
+            ),
+        });
+    }
+    let content_type = req.header("content-type");
+    if !req.body.is_empty() && content_type.and_then(SerializationFormat::from_mime_type).is_none() {
+        return Err(RouteError {
+            status: 415,
+            error: AppError::Validation(
+                ErrorValue::new(ErrorCode::InvalidFormat, "Unsupported Content-Type")
+                    .with_details("Send application/json, application/msgpack or application/cbor")
+                    .with_context("content_type", content_type.unwrap_or("none")),
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Allow cross-origin calls from loopback pages only (the WebUI window)
+fn with_cors(response: HttpResponse, origin: Option<&str>) -> HttpResponse {
+    let Some(origin) = origin.filter(|o| is_loopback_origin(o)) else {
+        return response;
+    };
+    response
+        .with_header("Access-Control-Allow-Origin", origin)
+        .with_header("Vary", "Origin")
+        .with_header("Access-Control-Allow-Methods", "GET, POST, PUT, PATCH, DELETE, OPTIONS")
+        .with_header("Access-Control-Allow-Headers", "Content-Type, Accept, X-Request-Id")
+        .with_header("Access-Control-Expose-Headers", "X-Request-Id")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::io::{Read, Write};
+    use std::sync::atomic::AtomicUsize;
+
+    #[derive(Debug, Deserialize)]
+    struct AddRequest {
+        a: i64,
+        b: i64,
+    }
+
+    /// Send `raw` with a `Host` header for `addr` after the request line
+    fn send(addr: SocketAddr, raw: &str) -> (u16, serde_json::Value) {
+        let (request_line, rest) = raw.split_once("\r\n").unwrap();
+        let raw = format!("{}\r\nHost: {}\r\n{}", request_line, addr, rest);
+        let mut stream = TcpStream::connect(addr).expect("Failed to connect");
+        stream.write_all(raw.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let status = response[9..12].parse().unwrap();
+        let body = response.split("\r\n\r\n").nth(1).unwrap_or_default();
+        (status, serde_json::from_str(body).unwrap_or(serde_json::Value::Null))
+    }
+
+    #[test]
+    fn test_server_dispatches_rpc() {
+        rpc::register("test_http_add", |_ctx, req: AddRequest| Ok(req.a + req.b));
+        let mut server = HttpServer::start("127.0.0.1:0").expect("Failed to start server");
+        let addr = server.local_addr();
+
+        let body = r#"{"a": 2, "b": 3}"#;
+        let (status, json) = send(
+            addr,
+            &format!(
+                "POST /api/rpc/test_http_add HTTP/1.1\r\nX-Request-Id: r1\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            ),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(json["data"], 5);
+        assert_eq!(json["request_id"], "r1");
+
+        let body = r#"{"a": "two", "b": 3}"#;
+        let (status, json) = send(
+            addr,
+            &format!(
+                "POST /api/rpc/test_http_add HTTP/1.1\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            ),
+        );
+        assert_eq!(status, 422);
+        assert_eq!(json["error"]["code"], "INVALID_FIELD_VALUE");
+
+        let (status, json) = send(addr, "GET /api/missing HTTP/1.1\r\n\r\n");
+        assert_eq!(status, 404);
+        assert_eq!(json["success"], false);
+
+        server.stop();
+    }
+
+    #[test]
+    fn test_binary_request_and_response() {
+        rpc::register("test_http_binary_add", |_ctx, req: AddRequest| Ok(req.a + req.b));
+
+        let body = crate::utils::serialization::to_bytes(
+            &serde_json::json!({"a": 4, "b": 5}),
+            SerializationFormat::MessagePack,
+        )
+        .unwrap();
+        let mut raw = format!(
+            "POST /api/rpc/test_http_binary_add HTTP/1.1\r\nHost: localhost:8080\r\nContent-Type: application/msgpack\r\nAccept: application/cbor\r\nContent-Length: {}\r\n\r\n",
+            body.len()
+        )
+        .into_bytes();
+        raw.extend_from_slice(&body);
+
+        let req = HttpRequest::read_from(raw.as_slice()).unwrap();
+        let response = handle_request(&req, 8080);
+        assert_eq!(response.status, 200);
+        assert!(response
+            .headers
+            .contains(&("Content-Type".to_string(), "application/cbor".to_string())));
+
+        let envelope: serde_json::Value =
+            crate::utils::serialization::from_bytes(&response.body, SerializationFormat::Cbor).unwrap();
+        assert_eq!(envelope["data"], 9);
+    }
+
+    #[test]
+    fn test_cross_site_requests_do_not_run() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        rpc::register("test_http_side_effect", |_ctx, _req: serde_json::Value| {
+            Ok(CALLS.fetch_add(1, Ordering::SeqCst))
+        });
+        let request = |headers: &str| {
+            let raw = format!(
+                "POST /api/rpc/test_http_side_effect HTTP/1.1\r\n{}Content-Length: 2\r\n\r\n{{}}",
+                headers
+            );
+            handle_request(&HttpRequest::read_from(raw.as_bytes()).unwrap(), 8080)
+        };
+        let local_json = "Host: 127.0.0.1:8080\r\nContent-Type: application/json\r\n";
+
+        let foreign = request(&format!("Origin: https://example.com\r\n{}", local_json));
+        assert_eq!(foreign.status, 403);
+        let rebound = request("Host: attacker.example:8080\r\nContent-Type: application/json\r\n");
+        assert_eq!(rebound.status, 403);
+        assert_eq!(request("Content-Type: application/json\r\n").status, 403);
+        let simple = request("Origin: http://localhost:4200\r\nHost: localhost:8080\r\nContent-Type: text/plain\r\n");
+        assert_eq!(simple.status, 415);
+        let form = request("Host: localhost:8080\r\nContent-Type: application/x-www-form-urlencoded\r\n");
+        assert_eq!(form.status, 415);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+
+        let local = request(&format!("Origin: http://localhost:4200\r\n{}", local_json));
+        assert_eq!(local.status, 200);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_rejects_non_loopback_bind() {
+        assert!(HttpServer::start("0.0.0.0:0").is_err());
+    }
+}
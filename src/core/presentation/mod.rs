@@ -1,6 +1,10 @@
 // views/mod.rs
 // Presentation layer - UI handlers and views
 
+pub mod http;
+pub mod origin;
+pub mod rpc;
+pub mod websocket;
 pub mod webui;
 
-pub use webui::*;
\ No newline at end of file
+pub use webui::*;
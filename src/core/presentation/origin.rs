@@ -0,0 +1,48 @@
+// src/core/presentation/origin.rs
+// Origin and Host checks shared by the loopback HTTP and WebSocket servers
+
+/// Whether a browser `Origin` header names a page served from this machine
+pub fn is_loopback_origin(origin: &str) -> bool {
+    let host = origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+        .unwrap_or_default();
+    let host = host.rsplit_once(':').map(|(h, _)| h).unwrap_or(host);
+    matches!(host, "localhost" | "127.0.0.1" | "[::1]")
+}
+
+/// Whether a `Host` header names this server: `127.0.0.1`, `localhost` or `[::1]` on `port`
+///
+/// A DNS-rebinding page reaches the server under its own name, and same-origin
+/// GETs carry no `Origin`, so only `Host` gives it away.
+pub fn is_loopback_host(host: &str, port: u16) -> bool {
+    let Some((name, host_port)) = host.rsplit_once(':') else {
+        return false;
+    };
+    host_port.parse() == Ok(port) && matches!(name.to_ascii_lowercase().as_str(), "localhost" | "127.0.0.1" | "[::1]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loopback_origin() {
+        assert!(is_loopback_origin("http://localhost:4200"));
+        assert!(is_loopback_origin("http://127.0.0.1:8080"));
+        assert!(is_loopback_origin("http://[::1]:3000"));
+        assert!(!is_loopback_origin("https://example.com"));
+        assert!(!is_loopback_origin("http://localhost.example.com"));
+        assert!(!is_loopback_origin("null"));
+    }
+
+    #[test]
+    fn test_loopback_host() {
+        assert!(is_loopback_host("127.0.0.1:8080", 8080));
+        assert!(is_loopback_host("localhost:8080", 8080));
+        assert!(is_loopback_host("[::1]:8080", 8080));
+        assert!(!is_loopback_host("localhost:9090", 8080));
+        assert!(!is_loopback_host("attacker.example:8080", 8080));
+        assert!(!is_loopback_host("localhost", 8080));
+    }
+}
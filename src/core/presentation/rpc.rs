@@ -0,0 +1,588 @@
+// src/core/presentation/rpc.rs
+// Typed RPC dispatcher - routes bound calls to handlers with serde request/response types
+//
+// Every backend function exposed to the frontend is registered here with:
+// 1. A request type deserialized from the JSON payload (first call argument)
+// 2. A response type serialized into the standard response envelope
+// 3. Structured AppError values for malformed payloads
+//
+// Callers pass a request id as the second argument; it is echoed back as
+// `request_id` in the envelope so concurrent calls can be told apart.
+//
+// Once a window has negotiated a binary serialization format, it passes the
+// format name as the third argument with a base64 payload, and responses are
+// delivered as raw bytes through `webui_send_raw` instead of `run_js`.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use log::{debug, error};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::{webui_interface_get_string_at, webui_send_raw};
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::di::Container;
+use crate::core::presentation::webui::scopes;
+use crate::utils::serialization::{self, SerializationFormat};
+
+/// Frontend function receiving raw (binary) response envelopes
+pub const RAW_RESPONSE_FUNCTION: &str = "__webuiRpcResponse";
+
+/// Call context handed to every RPC handler
+#[derive(Debug, Clone)]
+pub struct RpcContext {
+    /// Name of the bound function that was called
+    pub method: String,
+    /// WebUI window the call originated from
+    pub window_id: usize,
+    /// Caller-supplied correlation id, echoed in the response envelope
+    pub request_id: Option<String>,
+    /// DI scope of this call, a child of the window's scope; dropped with the call
+    pub scope: Arc<Container>,
+}
+
+impl RpcContext {
+    pub fn new(method: impl Into<String>, window_id: usize) -> Self {
+        Self {
+            method: method.into(),
+            window_id,
+            request_id: None,
+            scope: scopes::request_scope(window_id),
+        }
+    }
+
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Scope shared by every call from the window; `None` for REST and WebSocket calls
+    pub fn window_scope(&self) -> Option<&Arc<Container>> {
+        self.scope.parent().filter(|_| self.window_id != 0)
+    }
+}
+
+type ErasedHandler =
+    Arc<dyn Fn(&RpcContext, Option<&str>) -> AppResult<serde_json::Value> + Send + Sync>;
+
+#[derive(Clone)]
+struct RpcRoute {
+    handler: ErasedHandler,
+    /// Notifications (fire-and-forget calls such as log forwarding) send no response
+    respond: bool,
+}
+
+static RPC_ROUTES: OnceLock<RwLock<HashMap<String, RpcRoute>>> = OnceLock::new();
+
+/// Response format each window negotiated; windows default to JSON
+static WINDOW_FORMATS: OnceLock<RwLock<HashMap<usize, SerializationFormat>>> = OnceLock::new();
+
+fn window_formats() -> &'static RwLock<HashMap<usize, SerializationFormat>> {
+    WINDOW_FORMATS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Record the serialization format a window agreed to use
+pub fn set_window_format(window_id: usize, format: SerializationFormat) {
+    if let Ok(mut formats) = window_formats().write() {
+        formats.insert(window_id, format);
+    }
+}
+
+/// Serialization format negotiated by a window (JSON until negotiated)
+pub fn window_format(window_id: usize) -> SerializationFormat {
+    window_formats()
+        .read()
+        .ok()
+        .and_then(|formats| formats.get(&window_id).copied())
+        .unwrap_or(SerializationFormat::Json)
+}
+
+fn routes() -> &'static RwLock<HashMap<String, RpcRoute>> {
+    RPC_ROUTES.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn insert_route(method: &str, route: RpcRoute) {
+    match routes().write() {
+        Ok(mut routes) => {
+            routes.insert(method.to_string(), route);
+        }
+        Err(e) => error!("Failed to register RPC method {}: {}", method, e),
+    }
+}
+
+/// Register a request/response handler
+pub fn register<Req, Res, F>(method: &str, handler: F)
+where
+    Req: DeserializeOwned + 'static,
+    Res: Serialize + 'static,
+    F: Fn(&RpcContext, Req) -> AppResult<Res> + Send + Sync + 'static,
+{
+    let erased: ErasedHandler = Arc::new(move |ctx, payload| {
+        let request = parse_payload::<Req>(payload)?;
+        let response = handler(ctx, request)?;
+        Ok(serde_json::to_value(response)?)
+    });
+    insert_route(method, RpcRoute { handler: erased, respond: true });
+}
+
+/// Register a handler that does not answer the caller
+pub fn register_notification<Req, F>(method: &str, handler: F)
+where
+    Req: DeserializeOwned + 'static,
+    F: Fn(&RpcContext, Req) -> AppResult<()> + Send + Sync + 'static,
+{
+    let erased: ErasedHandler = Arc::new(move |ctx, payload| {
+        let request = parse_payload::<Req>(payload)?;
+        handler(ctx, request)?;
+        Ok(serde_json::Value::Null)
+    });
+    insert_route(method, RpcRoute { handler: erased, respond: false });
+}
+
+/// Register a handler and bind it on the given window
+pub fn bind<Req, Res, F>(window: &mut webui::Window, method: &str, handler: F)
+where
+    Req: DeserializeOwned + 'static,
+    Res: Serialize + 'static,
+    F: Fn(&RpcContext, Req) -> AppResult<Res> + Send + Sync + 'static,
+{
+    register(method, handler);
+    window.bind(method, handle_webui_event);
+}
+
+/// Register a notification handler and bind it on the given window
+pub fn bind_notification<Req, F>(window: &mut webui::Window, method: &str, handler: F)
+where
+    Req: DeserializeOwned + 'static,
+    F: Fn(&RpcContext, Req) -> AppResult<()> + Send + Sync + 'static,
+{
+    register_notification(method, handler);
+    window.bind(method, handle_webui_event);
+}
+
+/// Names of all registered methods, sorted
+pub fn registered_methods() -> Vec<String> {
+    let mut methods: Vec<String> = routes()
+        .read()
+        .map(|routes| routes.keys().cloned().collect())
+        .unwrap_or_default();
+    methods.sort();
+    methods
+}
+
+fn lookup(method: &str) -> AppResult<RpcRoute> {
+    let routes = routes().read().map_err(|e| {
+        AppError::LockPoisoned(
+            ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire RPC registry lock")
+                .with_cause(e.to_string())
+                .with_context("operation", "dispatch"),
+        )
+    })?;
+
+    routes.get(method).cloned().ok_or_else(|| {
+        AppError::NotFound(
+            ErrorValue::new(ErrorCode::ResourceNotFound, format!("Unknown RPC method: {}", method))
+                .with_context("method", method),
+        )
+    })
+}
+
+/// Run the handler registered for `ctx.method` against a raw JSON payload
+pub fn dispatch(ctx: &RpcContext, payload: Option<&str>) -> AppResult<serde_json::Value> {
+    let route = lookup(&ctx.method)?;
+    (route.handler)(ctx, payload)
+}
+
+/// Deserialize a request payload, mapping failures to structured errors
+///
+/// An absent or empty payload is treated as JSON `null`, so handlers taking
+/// `()` or `Option<T>` can be called without arguments.
+pub fn parse_payload<Req: DeserializeOwned>(payload: Option<&str>) -> AppResult<Req> {
+    let raw = payload.map(str::trim).filter(|p| !p.is_empty());
+
+    match raw {
+        Some(raw) => serde_json::from_str(raw).map_err(deserialization_error),
+        None => serde_json::from_value(serde_json::Value::Null).map_err(|e| {
+            AppError::Validation(
+                ErrorValue::new(ErrorCode::MissingRequiredField, "Request payload is required")
+                    .with_cause(e.to_string()),
+            )
+        }),
+    }
+}
+
+fn deserialization_error(err: serde_json::Error) -> AppError {
+    use serde_json::error::Category;
+
+    let message = err.to_string();
+    match err.classify() {
+        Category::Data => {
+            let mut value = ErrorValue::new(
+                ErrorCode::InvalidFieldValue,
+                format!("Invalid request payload: {}", message),
+            )
+            .with_context("line", err.line().to_string())
+            .with_context("column", err.column().to_string());
+            if let Some(field) = field_from_message(&message) {
+                value = value.with_field(field);
+            }
+            AppError::Validation(value)
+        }
+        _ => AppError::Serialization(
+            ErrorValue::new(ErrorCode::DeserializationFailed, "Malformed request payload")
+                .with_cause(message)
+                .with_context("line", err.line().to_string())
+                .with_context("column", err.column().to_string()),
+        ),
+    }
+}
+
+/// Extract the field name serde reports as "missing field `x`" / "unknown field `x`"
+fn field_from_message(message: &str) -> Option<&str> {
+    let start = message.find('`')? + 1;
+    let len = message[start..].find('`')?;
+    Some(&message[start..start + len])
+}
+
+/// Transcode a raw request body into the JSON payload handlers parse
+///
+/// Empty bodies, or blank JSON ones, yield `None` so the usual empty-payload
+/// rules apply; a binary `0x09` is the integer 9, not whitespace.
+pub fn decode_payload(bytes: &[u8], format: SerializationFormat) -> AppResult<Option<String>> {
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+    match format {
+        SerializationFormat::Json if bytes.iter().all(u8::is_ascii_whitespace) => Ok(None),
+        SerializationFormat::Json => {
+            let text = String::from_utf8(bytes.to_vec()).map_err(|e| {
+                AppError::Serialization(
+                    ErrorValue::new(ErrorCode::InvalidFormat, "Request body is not valid UTF-8")
+                        .with_cause(e.to_string()),
+                )
+            })?;
+            serialization::record_received(bytes.len(), format);
+            Ok(Some(text))
+        }
+        _ => serialization::from_bytes::<serde_json::Value>(bytes, format)
+            .map(|value| Some(value.to_string()))
+            .map_err(|e| payload_decode_error(e, format)),
+    }
+}
+
+/// Transcode a text argument (base64 for binary formats) into a JSON payload
+pub fn decode_text_payload(text: &str, format: SerializationFormat) -> AppResult<Option<String>> {
+    if !format.is_binary() || text.trim().is_empty() {
+        if !text.trim().is_empty() {
+            serialization::record_received(text.len(), format);
+        }
+        return Ok(Some(text.to_string()));
+    }
+    serialization::deserialize::<serde_json::Value>(text.trim(), format)
+        .map(|value| Some(value.to_string()))
+        .map_err(|e| payload_decode_error(e, format))
+}
+
+fn payload_decode_error(cause: String, format: SerializationFormat) -> AppError {
+    AppError::Serialization(
+        ErrorValue::new(ErrorCode::DeserializationFailed, "Malformed request payload")
+            .with_cause(cause)
+            .with_context("format", format.name()),
+    )
+}
+
+/// Encode a response envelope in the given format
+pub fn encode_envelope(envelope: &serde_json::Value, format: SerializationFormat) -> AppResult<Vec<u8>> {
+    serialization::to_bytes(envelope, format).map_err(|e| {
+        AppError::Serialization(
+            ErrorValue::new(ErrorCode::SerializationFailed, "Failed to encode response")
+                .with_cause(e)
+                .with_context("format", format.name()),
+        )
+    })
+}
+
+/// Standard success envelope
+pub fn success_envelope(request_id: Option<&str>, data: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "request_id": request_id,
+        "success": true,
+        "data": data,
+        "error": null
+    })
+}
+
+/// Standard error envelope using structured error values
+pub fn error_envelope(request_id: Option<&str>, err: &AppError) -> serde_json::Value {
+    serde_json::json!({
+        "request_id": request_id,
+        "success": false,
+        "data": null,
+        "error": err.to_value().to_response()
+    })
+}
+
+/// Build the response envelope for a dispatch result
+pub fn envelope_for(ctx: &RpcContext, result: &AppResult<serde_json::Value>) -> serde_json::Value {
+    match result {
+        Ok(data) => success_envelope(ctx.request_id.as_deref(), data.clone()),
+        Err(e) => error_envelope(ctx.request_id.as_deref(), e),
+    }
+}
+
+/// Name of the window event a method answers on
+pub fn response_event_name(method: &str) -> String {
+    format!("{}_response", method)
+}
+
+fn read_argument(event: &webui::Event, index: usize) -> Option<String> {
+    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, index) };
+    if ptr.is_null() {
+        return None;
+    }
+    Some(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+}
+
+fn send_response(
+    window_id: usize,
+    method: &str,
+    envelope: &serde_json::Value,
+    format: SerializationFormat,
+) {
+    if format.is_binary() {
+        let mut envelope = envelope.clone();
+        envelope["method"] = serde_json::json!(method);
+        match encode_envelope(&envelope, format) {
+            Ok(bytes) => return send_raw(window_id, &bytes),
+            Err(e) => error!("Falling back to JSON response for {}: {}", method, e),
+        }
+    }
+    dispatch_window_event(window_id, &response_event_name(method), envelope);
+}
+
+/// Push backend-initiated data to a window as a `CustomEvent` named `event_name`
+///
+/// Windows on a binary format receive `{ push, data }` through the raw response
+/// function instead; the frontend re-dispatches it under the same event name.
+pub fn push_to_window(window_id: usize, event_name: &str, data: &serde_json::Value) {
+    let format = window_format(window_id);
+    if format.is_binary() {
+        let envelope = serde_json::json!({ "push": event_name, "data": data });
+        match encode_envelope(&envelope, format) {
+            Ok(bytes) => return send_raw(window_id, &bytes),
+            Err(e) => error!("Falling back to JSON push for {}: {}", event_name, e),
+        }
+    }
+    dispatch_window_event(window_id, event_name, data);
+}
+
+fn send_raw(window_id: usize, bytes: &[u8]) {
+    let function = CString::new(RAW_RESPONSE_FUNCTION).unwrap_or_default();
+    unsafe {
+        webui_send_raw(window_id, function.as_ptr(), bytes.as_ptr().cast(), bytes.len());
+    }
+}
+
+fn dispatch_window_event(window_id: usize, event_name: &str, detail: &serde_json::Value) {
+    let detail = match encode_envelope(detail, SerializationFormat::Json) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(e) => {
+            error!("Failed to encode {} for window {}: {}", event_name, window_id, e);
+            return;
+        }
+    };
+    let js = format!(
+        "window.dispatchEvent(new CustomEvent('{}', {{ detail: {} }}))",
+        event_name, detail
+    );
+    webui::Window::from_id(window_id).run_js(&js);
+}
+
+/// Shared WebUI callback for every bound RPC method
+fn handle_webui_event(event: webui::Event) {
+    let method = unsafe { CStr::from_ptr(event.element).to_string_lossy().into_owned() };
+    let mut ctx = RpcContext::new(method, event.window);
+    if let Some(request_id) = read_argument(&event, 1).filter(|id| !id.is_empty()) {
+        ctx = ctx.with_request_id(request_id);
+    }
+    // Captured before the handler runs so a negotiation call is answered in the old format
+    let response_format = window_format(ctx.window_id);
+    let request_format = read_argument(&event, 2)
+        .and_then(|name| SerializationFormat::from_name(&name))
+        .unwrap_or(SerializationFormat::Json);
+
+    debug!(
+        "[Communication] Frontend → Backend ({}, request {:?}, {}): payload received",
+        ctx.method, ctx.request_id, request_format
+    );
+
+    let route = lookup(&ctx.method).and_then(|route| {
+        let payload = match read_argument(&event, 0) {
+            Some(text) => decode_text_payload(&text, request_format)?,
+            None => None,
+        };
+        Ok((route, payload))
+    });
+    let (route, payload) = match route {
+        Ok(found) => found,
+        Err(e) => {
+            error!("RPC dispatch failed: {}", e);
+            let envelope = error_envelope(ctx.request_id.as_deref(), &e);
+            send_response(ctx.window_id, &ctx.method, &envelope, response_format);
+            return;
+        }
+    };
+
+    let result = (route.handler)(&ctx, payload.as_deref());
+
+    if let Err(ref e) = result {
+        error!("RPC method {} (request {:?}) failed: {}", ctx.method, ctx.request_id, e);
+    }
+
+    if route.respond {
+        let envelope = envelope_for(&ctx, &result);
+        debug!(
+            "[Communication] Backend → Frontend ({}, request {:?}): response sent",
+            ctx.method, ctx.request_id
+        );
+        send_response(ctx.window_id, &ctx.method, &envelope, response_format);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::error::errors;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct EchoRequest {
+        id: i64,
+        name: String,
+    }
+
+    #[test]
+    fn test_parse_payload_with_colons() {
+        let req: EchoRequest =
+            parse_payload(Some(r#"{"id": 7, "name": "a:b:c"}"#)).expect("Failed to parse");
+        assert_eq!(req.id, 7);
+        assert_eq!(req.name, "a:b:c");
+    }
+
+    #[test]
+    fn test_parse_payload_malformed() {
+        let result = parse_payload::<EchoRequest>(Some("{not json"));
+        match result {
+            Err(AppError::Serialization(e)) => assert_eq!(e.code, ErrorCode::DeserializationFailed),
+            other => panic!("Expected DeserializationFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_payload_invalid_field() {
+        let result = parse_payload::<EchoRequest>(Some(r#"{"id": "abc", "name": "x"}"#));
+        match result {
+            Err(AppError::Validation(e)) => assert_eq!(e.code, ErrorCode::InvalidFieldValue),
+            other => panic!("Expected InvalidFieldValue, got {:?}", other),
+        }
+
+        let result = parse_payload::<EchoRequest>(Some(r#"{"name": "x"}"#));
+        match result {
+            Err(AppError::Validation(e)) => assert_eq!(e.field, Some("id".to_string())),
+            other => panic!("Expected missing field error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_empty_payload() {
+        assert!(parse_payload::<()>(None).is_ok());
+        assert!(parse_payload::<Option<EchoRequest>>(Some("")).unwrap().is_none());
+        assert!(matches!(
+            parse_payload::<EchoRequest>(None),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_dispatch_registered_method() {
+        register("test_rpc_echo", |_ctx, req: EchoRequest| Ok(format!("{}:{}", req.id, req.name)));
+
+        let ctx = RpcContext::new("test_rpc_echo", 0);
+        let value = dispatch(&ctx, Some(r#"{"id": 1, "name": "x"}"#)).expect("Dispatch failed");
+        assert_eq!(value, serde_json::json!("1:x"));
+
+        let unknown = RpcContext::new("test_rpc_missing", 0);
+        assert!(matches!(dispatch(&unknown, None), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_handlers_reach_their_window_scope() {
+        #[derive(Clone)]
+        struct Session(String);
+
+        register("test_rpc_login", |ctx, name: String| {
+            ctx.window_scope().expect("No window scope").register(Session(name))
+        });
+        register("test_rpc_whoami", |ctx, _req: ()| Ok(ctx.scope.resolve::<Session>()?.0));
+
+        // A local table, so the test neither asks WebUI about windows nor shares the global one
+        let windows = scopes::WindowScopes::new(Arc::new(Container::new()), |_| true);
+        let call = |method: &str, window_id: usize| RpcContext {
+            window_id,
+            scope: windows.scope(window_id).create_scope(),
+            ..RpcContext::new(method, 0)
+        };
+
+        dispatch(&call("test_rpc_login", 1), Some(r#""ada""#)).expect("Dispatch failed");
+        let whoami = dispatch(&call("test_rpc_whoami", 1), None).expect("Dispatch failed");
+        assert_eq!(whoami, serde_json::json!("ada"));
+        assert!(dispatch(&call("test_rpc_whoami", 2), None).is_err());
+        assert!(RpcContext::new("test_rpc_whoami", 0).window_scope().is_none());
+        assert!(windows.drop_scope(1));
+        assert!(dispatch(&call("test_rpc_whoami", 1), None).is_err());
+    }
+
+    #[test]
+    fn test_envelope_echoes_request_id() {
+        let ctx = RpcContext::new("get_users", 1).with_request_id("req-42");
+
+        let ok = envelope_for(&ctx, &Ok(serde_json::json!([1, 2])));
+        assert_eq!(ok["request_id"], "req-42");
+        assert_eq!(ok["success"], true);
+
+        let err = envelope_for(&ctx, &Err(errors::validation_failed("id", "bad id")));
+        assert_eq!(err["request_id"], "req-42");
+        assert_eq!(err["error"]["code"], "VALIDATION_FAILED");
+
+        let anonymous = envelope_for(&RpcContext::new("get_users", 1), &Ok(serde_json::Value::Null));
+        assert!(anonymous["request_id"].is_null());
+    }
+
+    #[test]
+    fn test_binary_payloads_transcode_to_json() {
+        let request = serde_json::json!({"id": 3, "name": "x"});
+        for format in [SerializationFormat::MessagePack, SerializationFormat::Cbor] {
+            let bytes = serialization::to_bytes(&request, format).unwrap();
+            let json = decode_payload(&bytes, format).unwrap().unwrap();
+            let req: EchoRequest = parse_payload(Some(&json)).unwrap();
+            assert_eq!(req.id, 3);
+
+            let text = serialization::serialize(&request, format).unwrap();
+            assert_eq!(decode_text_payload(&text, format).unwrap(), Some(json));
+        }
+
+        assert!(decode_payload(b"", SerializationFormat::Cbor).unwrap().is_none());
+        assert!(decode_payload(b" \n", SerializationFormat::Json).unwrap().is_none());
+        let nine = serialization::to_bytes(&9u8, SerializationFormat::MessagePack).unwrap();
+        assert_eq!(decode_payload(&nine, SerializationFormat::MessagePack).unwrap().as_deref(), Some("9"));
+        assert!(matches!(
+            decode_payload(&[0xc1], SerializationFormat::MessagePack),
+            Err(AppError::Serialization(_))
+        ));
+    }
+}
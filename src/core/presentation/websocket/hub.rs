@@ -0,0 +1,280 @@
+// src/core/presentation/websocket/hub.rs
+// Connected WebSocket clients and their push channel subscriptions
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Once, OnceLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::infrastructure::event_bus::get_global_event_bus;
+use crate::core::infrastructure::logging::add_log_sink;
+use crate::utils::serialization::{self, SerializationFormat};
+
+/// Frames queued per client before new pushes are dropped
+const CLIENT_QUEUE_SIZE: usize = 256;
+
+/// Server push streams a client can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PushChannel {
+    Events,
+    Logs,
+    Metrics,
+}
+
+/// Outgoing frame: JSON text or a binary-format encoding
+#[derive(Debug, Clone, PartialEq)]
+pub enum WsFrame {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl WsFrame {
+    /// Encode a frame object in the given format (binary frames for MessagePack/CBOR)
+    pub fn encode(value: &serde_json::Value, format: SerializationFormat) -> Option<WsFrame> {
+        let bytes = serialization::to_bytes(value, format).ok()?;
+        if format.is_binary() {
+            Some(WsFrame::Binary(bytes))
+        } else {
+            String::from_utf8(bytes).ok().map(WsFrame::Text)
+        }
+    }
+}
+
+struct Client {
+    sender: SyncSender<WsFrame>,
+    channels: HashSet<PushChannel>,
+    /// Format of the last frame the client sent; pushes use the same one
+    format: SerializationFormat,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HubStats {
+    pub clients: usize,
+    pub pushed: u64,
+    pub dropped: u64,
+}
+
+/// Registry of connected clients; pushes are fanned out through per-client queues
+pub struct WsHub {
+    clients: RwLock<HashMap<u64, Client>>,
+    next_id: AtomicU64,
+    pushed: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl WsHub {
+    pub fn new() -> Self {
+        Self {
+            clients: RwLock::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            pushed: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Add a client and return its id with the queue its connection drains
+    pub fn register(&self) -> (u64, Receiver<WsFrame>) {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (sender, receiver) = mpsc::sync_channel(CLIENT_QUEUE_SIZE);
+        if let Ok(mut clients) = self.clients.write() {
+            clients.insert(
+                id,
+                Client {
+                    sender,
+                    channels: HashSet::new(),
+                    format: SerializationFormat::Json,
+                },
+            );
+        }
+        (id, receiver)
+    }
+
+    pub fn unregister(&self, client_id: u64) {
+        if let Ok(mut clients) = self.clients.write() {
+            clients.remove(&client_id);
+        }
+    }
+
+    pub fn set_format(&self, client_id: u64, format: SerializationFormat) {
+        if let Ok(mut clients) = self.clients.write() {
+            if let Some(client) = clients.get_mut(&client_id) {
+                client.format = format;
+            }
+        }
+    }
+
+    pub fn subscribe(&self, client_id: u64, channels: &[PushChannel]) -> Vec<PushChannel> {
+        self.update_channels(client_id, |set| set.extend(channels.iter().copied()))
+    }
+
+    pub fn unsubscribe(&self, client_id: u64, channels: &[PushChannel]) -> Vec<PushChannel> {
+        self.update_channels(client_id, |set| {
+            for channel in channels {
+                set.remove(channel);
+            }
+        })
+    }
+
+    fn update_channels(
+        &self,
+        client_id: u64,
+        update: impl FnOnce(&mut HashSet<PushChannel>),
+    ) -> Vec<PushChannel> {
+        let Ok(mut clients) = self.clients.write() else {
+            return Vec::new();
+        };
+        match clients.get_mut(&client_id) {
+            Some(client) => {
+                update(&mut client.channels);
+                client.channels.iter().copied().collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    pub fn has_subscribers(&self, channel: PushChannel) -> bool {
+        self.clients
+            .read()
+            .map(|clients| clients.values().any(|c| c.channels.contains(&channel)))
+            .unwrap_or(false)
+    }
+
+    /// Queue a push frame for every client subscribed to the channel
+    ///
+    /// Never logs: it runs inside the log sink.
+    pub fn publish(&self, channel: PushChannel, data: serde_json::Value) {
+        let Ok(clients) = self.clients.read() else {
+            return;
+        };
+        let mut frames: HashMap<SerializationFormat, Option<WsFrame>> = HashMap::new();
+        for client in clients.values().filter(|c| c.channels.contains(&channel)) {
+            let frame = frames
+                .entry(client.format)
+                .or_insert_with(|| WsFrame::encode(&push_frame(channel, &data), client.format));
+            let Some(frame) = frame.clone() else {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                continue;
+            };
+            match client.sender.try_send(frame) {
+                Ok(()) => {
+                    self.pushed.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(TrySendError::Full(_)) | Err(TrySendError::Disconnected(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    pub fn stats(&self) -> HubStats {
+        HubStats {
+            clients: self.clients.read().map(|c| c.len()).unwrap_or(0),
+            pushed: self.pushed.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for WsHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn push_frame(channel: PushChannel, data: &serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "type": "push",
+        "channel": channel,
+        "data": data,
+    })
+}
+
+static GLOBAL_HUB: OnceLock<Arc<WsHub>> = OnceLock::new();
+static BRIDGES: Once = Once::new();
+
+pub fn get_hub() -> Arc<WsHub> {
+    Arc::clone(GLOBAL_HUB.get_or_init(|| Arc::new(WsHub::new())))
+}
+
+/// Forward EventBus events and log lines into the hub (installed once)
+pub fn install_bridges() {
+    BRIDGES.call_once(|| {
+        let hub = get_hub();
+        get_global_event_bus().add_observer(Arc::new(move |event| {
+            if let Ok(data) = serde_json::to_value(event) {
+                hub.publish(PushChannel::Events, data);
+            }
+        }));
+
+        let hub = get_hub();
+        add_log_sink(Arc::new(move |line| {
+            // Skip the transport's own records so a push never triggers another push
+            if line.target.contains("presentation::websocket") {
+                return;
+            }
+            if let Ok(data) = serde_json::to_value(line) {
+                hub.publish(PushChannel::Logs, data);
+            }
+        }));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publish_only_reaches_subscribers() {
+        let hub = WsHub::new();
+        let (a, rx_a) = hub.register();
+        let (_b, rx_b) = hub.register();
+
+        hub.subscribe(a, &[PushChannel::Events, PushChannel::Logs]);
+        hub.unsubscribe(a, &[PushChannel::Logs]);
+        assert!(hub.has_subscribers(PushChannel::Events));
+        assert!(!hub.has_subscribers(PushChannel::Logs));
+
+        hub.publish(PushChannel::Events, serde_json::json!({"n": 1}));
+        hub.publish(PushChannel::Logs, serde_json::json!({"n": 2}));
+
+        let WsFrame::Text(text) = rx_a.try_recv().unwrap() else {
+            panic!("Expected a text frame");
+        };
+        let frame: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(frame["channel"], "events");
+        assert_eq!(frame["data"]["n"], 1);
+        assert!(rx_a.try_recv().is_err());
+        assert!(rx_b.try_recv().is_err());
+        assert_eq!(hub.stats().pushed, 1);
+    }
+
+    #[test]
+    fn test_push_uses_client_format() {
+        let hub = WsHub::new();
+        let (id, rx) = hub.register();
+        hub.subscribe(id, &[PushChannel::Logs]);
+        hub.set_format(id, SerializationFormat::Cbor);
+
+        hub.publish(PushChannel::Logs, serde_json::json!({"message": "hi"}));
+        let WsFrame::Binary(bytes) = rx.try_recv().unwrap() else {
+            panic!("Expected a binary frame");
+        };
+        let frame: serde_json::Value = serialization::from_bytes(&bytes, SerializationFormat::Cbor).unwrap();
+        assert_eq!(frame["data"]["message"], "hi");
+    }
+
+    #[test]
+    fn test_unregistered_client_stops_receiving() {
+        let hub = WsHub::new();
+        let (id, rx) = hub.register();
+        hub.subscribe(id, &[PushChannel::Metrics]);
+        hub.unregister(id);
+
+        hub.publish(PushChannel::Metrics, serde_json::json!({}));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(hub.stats().clients, 0);
+    }
+}
@@ -0,0 +1,9 @@
+// src/core/presentation/websocket/mod.rs
+// WebSocket transport - RPC calls plus server push of events, logs and metrics
+
+#![allow(dead_code)]
+
+pub mod hub;
+pub mod server;
+
+pub use server::WebSocketServer;
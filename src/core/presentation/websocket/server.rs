@@ -0,0 +1,448 @@
+// src/core/presentation/websocket/server.rs
+// Loopback WebSocket server carrying RPC calls and server push
+
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use log::{debug, error, info, warn};
+use serde::Deserialize;
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::{Message, WebSocket};
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::presentation::origin::{is_loopback_host, is_loopback_origin};
+use crate::core::presentation::rpc::{self, RpcContext};
+use crate::core::presentation::sysinfo_handlers;
+
+use crate::utils::serialization::{self, SerializationFormat};
+
+use super::hub::{self, PushChannel, WsFrame, WsHub};
+
+/// Path the upgrade request must target
+pub const WS_PATH: &str = "/ws";
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a connection blocks on read before draining its push queue
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+const METRICS_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Frames a client may send
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ClientMessage {
+    Rpc {
+        method: String,
+        #[serde(default)]
+        request_id: Option<String>,
+        #[serde(default)]
+        payload: serde_json::Value,
+    },
+    Subscribe {
+        channels: Vec<PushChannel>,
+    },
+    Unsubscribe {
+        channels: Vec<PushChannel>,
+    },
+}
+
+/// Running WebSocket server; stopped when dropped
+pub struct WebSocketServer {
+    addr: SocketAddr,
+    running: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl WebSocketServer {
+    /// Bind to a loopback address (use port 0 for a random port) and start serving
+    pub fn start(bind_addr: &str) -> AppResult<Self> {
+        let listener = TcpListener::bind(bind_addr).map_err(|e| {
+            AppError::Configuration(
+                ErrorValue::new(ErrorCode::ConfigInvalid, "Failed to bind WebSocket server")
+                    .with_cause(e.to_string())
+                    .with_context("address", bind_addr),
+            )
+        })?;
+        let addr = listener.local_addr()?;
+
+        if !addr.ip().is_loopback() {
+            return Err(AppError::Configuration(
+                ErrorValue::new(ErrorCode::ConfigInvalid, "WebSocket server must bind to a loopback address")
+                    .with_field("communication.http_port")
+                    .with_context("address", addr.to_string()),
+            ));
+        }
+
+        hub::install_bridges();
+
+        let running = Arc::new(AtomicBool::new(true));
+
+        let acceptor_running = Arc::clone(&running);
+        let acceptor = thread::Builder::new()
+            .name("ws-acceptor".to_string())
+            .spawn(move || accept_loop(listener, acceptor_running))?;
+
+        let metrics_running = Arc::clone(&running);
+        let metrics = thread::Builder::new()
+            .name("ws-metrics".to_string())
+            .spawn(move || metrics_loop(metrics_running))?;
+
+        info!("WebSocket server listening on ws://{}{}", addr, WS_PATH);
+
+        Ok(Self {
+            addr,
+            running,
+            threads: vec![acceptor, metrics],
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn url(&self) -> String {
+        format!("ws://{}{}", self.addr, WS_PATH)
+    }
+
+    /// Stop accepting connections; open connections close on their next poll
+    pub fn stop(&mut self) {
+        if !self.running.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        // Wake the blocking accept() so the loop can observe the flag
+        let _ = TcpStream::connect(self.addr);
+        for handle in self.threads.drain(..) {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+        info!("WebSocket server stopped");
+    }
+}
+
+impl Drop for WebSocketServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn accept_loop(listener: TcpListener, running: Arc<AtomicBool>) {
+    for stream in listener.incoming() {
+        if !running.load(Ordering::SeqCst) {
+            break;
+        }
+        match stream {
+            Ok(stream) => {
+                let connection_running = Arc::clone(&running);
+                let spawned = thread::Builder::new()
+                    .name("ws-connection".to_string())
+                    .spawn(move || handle_connection(stream, connection_running));
+                if let Err(e) = spawned {
+                    error!("Failed to spawn WebSocket connection thread: {}", e);
+                }
+            }
+            Err(e) => warn!("WebSocket accept failed: {}", e),
+        }
+    }
+}
+
+fn metrics_loop(running: Arc<AtomicBool>) {
+    let hub = hub::get_hub();
+    while running.load(Ordering::SeqCst) {
+        thread::park_timeout(METRICS_INTERVAL);
+        if running.load(Ordering::SeqCst) && hub.has_subscribers(PushChannel::Metrics) {
+            hub.publish(
+                PushChannel::Metrics,
+                serde_json::json!({
+                    "memory": sysinfo_handlers::get_memory_info(),
+                    "process": sysinfo_handlers::get_process_info(),
+                    "timestamp": chrono::Utc::now().timestamp_millis(),
+                }),
+            );
+        }
+    }
+}
+
+/// Only upgrade `/ws` requests addressed to this server on `port` and coming
+/// from loopback pages (or without an Origin)
+fn check_upgrade(request: &Request, response: Response, port: u16) -> Result<Response, ErrorResponse> {
+    if request.uri().path() != WS_PATH {
+        return Err(reject(404, "Not Found"));
+    }
+    let host = request.headers().get("host").and_then(|value| value.to_str().ok());
+    if !host.is_some_and(|host| is_loopback_host(host, port)) {
+        return Err(reject(403, "Host not allowed"));
+    }
+    let origin = request
+        .headers()
+        .get("origin")
+        .and_then(|value| value.to_str().ok());
+    match origin {
+        Some(origin) if !is_loopback_origin(origin) => Err(reject(403, "Origin not allowed")),
+        _ => Ok(response),
+    }
+}
+
+fn reject(status: u16, reason: &str) -> ErrorResponse {
+    let mut response = ErrorResponse::new(Some(reason.to_string()));
+    *response.status_mut() =
+        tungstenite::http::StatusCode::from_u16(status).unwrap_or(tungstenite::http::StatusCode::FORBIDDEN);
+    response
+}
+
+fn handle_connection(stream: TcpStream, running: Arc<AtomicBool>) {
+    let _ = stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT));
+    let port = stream.local_addr().map(|addr| addr.port()).unwrap_or_default();
+    let upgrade = move |request: &Request, response: Response| check_upgrade(request, response, port);
+    let mut socket = match tungstenite::accept_hdr(stream, upgrade) {
+        Ok(socket) => socket,
+        Err(e) => {
+            debug!("WebSocket handshake failed: {}", e);
+            return;
+        }
+    };
+    let _ = socket.get_ref().set_read_timeout(Some(POLL_INTERVAL));
+
+    let hub = hub::get_hub();
+    let (client_id, outbound) = hub.register();
+    debug!("WebSocket client {} connected", client_id);
+
+    let welcome = serde_json::json!({
+        "type": "welcome",
+        "client_id": client_id,
+        "format": SerializationFormat::selected().name(),
+    });
+    if socket.send(Message::Text(welcome.to_string())).is_ok() {
+        serve_client(&mut socket, &hub, client_id, &outbound, &running);
+    }
+
+    hub.unregister(client_id);
+    let _ = socket.close(None);
+    let _ = socket.flush();
+    debug!("WebSocket client {} disconnected", client_id);
+}
+
+fn serve_client(
+    socket: &mut WebSocket<TcpStream>,
+    hub: &WsHub,
+    client_id: u64,
+    outbound: &Receiver<WsFrame>,
+    running: &AtomicBool,
+) {
+    while running.load(Ordering::SeqCst) {
+        // Text frames carry JSON; binary frames carry the configured binary format
+        let incoming = match socket.read() {
+            Ok(Message::Text(text)) => Some((decode_frame(text.as_bytes(), SerializationFormat::Json), SerializationFormat::Json)),
+            Ok(Message::Binary(bytes)) => {
+                let format = SerializationFormat::selected();
+                Some((decode_frame(&bytes, format), format))
+            }
+            Ok(Message::Close(_)) => return,
+            Ok(_) => None,
+            Err(tungstenite::Error::Io(ref e))
+                if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) =>
+            {
+                None
+            }
+            Err(_) => return,
+        };
+
+        if let Some((message, format)) = incoming {
+            hub.set_format(client_id, format);
+            let reply = handle_message(hub, client_id, message);
+            let Some(frame) = WsFrame::encode(&reply, format) else {
+                return;
+            };
+            if socket.send(to_message(frame)).is_err() {
+                return;
+            }
+        }
+
+        while let Ok(frame) = outbound.try_recv() {
+            if socket.send(to_message(frame)).is_err() {
+                return;
+            }
+        }
+        if let Err(tungstenite::Error::Io(ref e)) = socket.flush() {
+            if !matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) {
+                return;
+            }
+        }
+    }
+}
+
+fn to_message(frame: WsFrame) -> Message {
+    match frame {
+        WsFrame::Text(text) => Message::Text(text),
+        WsFrame::Binary(bytes) => Message::Binary(bytes),
+    }
+}
+
+fn decode_frame(bytes: &[u8], format: SerializationFormat) -> AppResult<ClientMessage> {
+    let value: serde_json::Value = serialization::from_bytes(bytes, format).map_err(|e| {
+        AppError::Serialization(
+            ErrorValue::new(ErrorCode::DeserializationFailed, "Invalid WebSocket message")
+                .with_cause(e)
+                .with_context("format", format.name()),
+        )
+    })?;
+    serde_json::from_value(value).map_err(|e| {
+        AppError::Serialization(
+            ErrorValue::new(ErrorCode::DeserializationFailed, "Invalid WebSocket message")
+                .with_cause(e.to_string()),
+        )
+    })
+}
+
+/// Handle one client frame and build the reply frame object
+fn handle_message(hub: &WsHub, client_id: u64, message: AppResult<ClientMessage>) -> serde_json::Value {
+    let message = match message {
+        Ok(message) => message,
+        Err(error) => {
+            let mut envelope = rpc::error_envelope(None, &error);
+            envelope["type"] = serde_json::json!("error");
+            return envelope;
+        }
+    };
+
+    match message {
+        ClientMessage::Rpc {
+            method,
+            request_id,
+            payload,
+        } => {
+            let mut ctx = RpcContext::new(method, 0);
+            if let Some(id) = request_id {
+                ctx = ctx.with_request_id(id);
+            }
+            let payload = match payload {
+                serde_json::Value::Null => None,
+                serde_json::Value::String(text) => Some(text),
+                other => Some(other.to_string()),
+            };
+            let result = rpc::dispatch(&ctx, payload.as_deref());
+            let mut envelope = rpc::envelope_for(&ctx, &result);
+            envelope["type"] = serde_json::json!("response");
+            envelope["method"] = serde_json::json!(ctx.method);
+            envelope
+        }
+        ClientMessage::Subscribe { channels } => {
+            let active = hub.subscribe(client_id, &channels);
+            serde_json::json!({ "type": "subscribed", "channels": active })
+        }
+        ClientMessage::Unsubscribe { channels } => {
+            let active = hub.unsubscribe(client_id, &channels);
+            serde_json::json!({ "type": "subscribed", "channels": active })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct EchoRequest {
+        text: String,
+    }
+
+    type ClientSocket = WebSocket<tungstenite::stream::MaybeTlsStream<TcpStream>>;
+
+    fn read_frame(socket: &mut ClientSocket) -> serde_json::Value {
+        loop {
+            match socket.read().expect("Failed to read frame") {
+                Message::Text(text) => return serde_json::from_str(&text).unwrap(),
+                Message::Binary(bytes) => {
+                    return serialization::from_bytes(&bytes, SerializationFormat::selected()).unwrap()
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Next reply frame, skipping pushes caused by other tests on the global bus
+    fn read_json(socket: &mut ClientSocket) -> serde_json::Value {
+        loop {
+            let frame = read_frame(socket);
+            if frame["type"] != "push" {
+                return frame;
+            }
+        }
+    }
+
+    #[test]
+    fn test_rpc_and_push_over_websocket() {
+        rpc::register("test_ws_echo", |_ctx, req: EchoRequest| Ok(req.text));
+        let mut server = WebSocketServer::start("127.0.0.1:0").expect("Failed to start server");
+
+        let (mut socket, _) = tungstenite::connect(server.url()).expect("Failed to connect");
+        assert_eq!(read_json(&mut socket)["type"], "welcome");
+
+        let call = r#"{"type":"rpc","method":"test_ws_echo","request_id":"w1","payload":{"text":"hi"}}"#;
+        socket.send(Message::Text(call.to_string())).unwrap();
+        let reply = read_json(&mut socket);
+        assert_eq!(reply["type"], "response");
+        assert_eq!(reply["request_id"], "w1");
+        assert_eq!(reply["data"], "hi");
+
+        socket
+            .send(Message::Text(r#"{"type":"subscribe","channels":["events"]}"#.to_string()))
+            .unwrap();
+        assert_eq!(read_json(&mut socket)["channels"][0], "events");
+
+        crate::core::infrastructure::event_bus::get_global_event_bus()
+            .emit("test.ws.push", serde_json::json!({"ok": true}));
+        loop {
+            let frame = read_frame(&mut socket);
+            if frame["data"]["event_type"] == "test.ws.push" {
+                assert_eq!(frame["channel"], "events");
+                break;
+            }
+        }
+
+        let binary_call = serialization::to_bytes(
+            &serde_json::json!({"type": "rpc", "method": "test_ws_echo", "request_id": "w2", "payload": {"text": "bin"}}),
+            SerializationFormat::selected(),
+        )
+        .unwrap();
+        socket.send(Message::Binary(binary_call)).unwrap();
+        let reply = read_json(&mut socket);
+        assert_eq!(reply["request_id"], "w2");
+        assert_eq!(reply["data"], "bin");
+
+        socket.send(Message::Text("not json".to_string())).unwrap();
+        let reply = read_json(&mut socket);
+        assert_eq!(reply["type"], "error");
+        assert_eq!(reply["error"]["code"], "DESERIALIZATION_FAILED");
+
+        server.stop();
+    }
+
+    #[test]
+    fn test_rejects_foreign_origin_host_and_path() {
+        let server = WebSocketServer::start("127.0.0.1:0").expect("Failed to start server");
+        let upgrade = |host: String, origin: &str| {
+            tungstenite::http::Request::builder()
+                .uri(server.url())
+                .header("Host", host)
+                .header("Origin", origin)
+                .header("Connection", "Upgrade")
+                .header("Upgrade", "websocket")
+                .header("Sec-WebSocket-Version", "13")
+                .header("Sec-WebSocket-Key", tungstenite::handshake::client::generate_key())
+                .body(())
+                .unwrap()
+        };
+
+        let local = server.local_addr().to_string();
+        assert!(tungstenite::connect(upgrade(local.clone(), "https://example.com")).is_err());
+        let rebound = format!("attacker.example:{}", server.local_addr().port());
+        assert!(tungstenite::connect(upgrade(rebound, "http://localhost:4200")).is_err());
+        assert!(tungstenite::connect(upgrade(local, "http://localhost:4200")).is_ok());
+
+        let wrong_path = format!("ws://{}/other", server.local_addr());
+        assert!(tungstenite::connect(wrong_path).is_err());
+    }
+}
@@ -1,224 +1,107 @@
-use crate::core::error::{AppError, ErrorCode, ErrorValue};
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::database::models::User;
 use crate::core::infrastructure::database::Database;
 use crate::core::infrastructure::di::get_container;
-use log::{error, info};
+use crate::core::presentation::rpc::{self, RpcContext};
+use log::info;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use webui_rs::webui;
 
-pub fn init_database(db: Arc<Database>) {
-    if let Err(e) = get_container().register_singleton(db.clone()) {
-        error!("Failed to register database in DI container: {}", e);
-        return;
-    }
-    info!("Database registered in DI container");
+#[derive(Debug, Deserialize)]
+pub struct CreateUserRequest {
+    pub name: String,
+    pub email: String,
+    #[serde(default = "default_role")]
+    pub role: String,
+    #[serde(default = "default_status")]
+    pub status: String,
 }
 
-fn get_db() -> Option<Arc<Database>> {
-    get_container().resolve_arc::<Database>().ok()
+#[derive(Debug, Deserialize)]
+pub struct UpdateUserRequest {
+    pub id: i64,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub role: Option<String>,
+    pub status: Option<String>,
 }
 
-/// Send a success response to the frontend
-fn send_success_response(window: webui::Window, event_name: &str, data: &serde_json::Value) {
-    let response = serde_json::json!({
-        "success": true,
-        "data": data,
-        "error": null
-    });
-    dispatch_event(window, event_name, &response);
+#[derive(Debug, Deserialize)]
+pub struct DeleteUserRequest {
+    pub id: i64,
 }
 
-/// Send an error response to the frontend using structured error values
-fn send_error_response(window: webui::Window, event_name: &str, err: &AppError) {
-    let error_value = err.to_value();
-    let response = serde_json::json!({
-        "success": false,
-        "data": null,
-        "error": error_value.to_response()
-    });
-    dispatch_event(window, event_name, &response);
+#[derive(Debug, Serialize)]
+pub struct CreateUserResponse {
+    pub id: i64,
+    pub message: String,
 }
 
-/// Helper to dispatch a custom event to the frontend
-fn dispatch_event(window: webui::Window, event_name: &str, detail: &serde_json::Value) {
-    let js = format!(
-        "window.dispatchEvent(new CustomEvent('{}', {{ detail: {} }}))",
-        event_name, detail
-    );
-    webui::Window::from_id(window.id).run_js(&js);
+#[derive(Debug, Serialize)]
+pub struct UserChangeResponse {
+    pub id: i64,
+    pub rows_affected: usize,
+    pub message: String,
 }
 
-/// Handle a database operation result and send appropriate response
-fn handle_db_result<T: serde::Serialize>(
-    window: webui::Window,
-    event_name: &str,
-    result: Result<T, AppError>,
-    success_message: Option<&str>,
-) {
-    match result {
-        Ok(data) => {
-            let response_data = if let Some(msg) = success_message {
-                serde_json::json!({
-                    "message": msg,
-                    "payload": data
-                })
-            } else {
-                serde_json::to_value(data).unwrap_or(serde_json::Value::Null)
-            };
-            send_success_response(window, event_name, &response_data);
-        }
-        Err(e) => {
-            error!("Database operation failed: {}", e);
-            send_error_response(window, event_name, &e);
-        }
-    }
+fn default_role() -> String {
+    "User".to_string()
 }
 
-pub fn setup_db_handlers(window: &mut webui::Window) {
-    window.bind("get_users", |event| {
-        info!("get_users called from frontend");
-        info!("[Communication] Frontend → Backend (get_users): JSON/FFI call received");
-        let window = event.get_window();
-
-        let Some(db) = get_db() else {
-            let err = AppError::DependencyInjection(
-                ErrorValue::new(ErrorCode::InternalError, "Database not initialized")
-                    .with_cause("DI container missing database instance")
-            );
-            send_error_response(window, "db_response", &err);
-            return;
-        };
-
-        handle_db_result(
-            window,
-            "db_response",
-            db.get_all_users(),
-            Some("Users retrieved successfully"),
-        );
-    });
-
-    window.bind("create_user", |event| {
-        info!("create_user called from frontend");
-
-        let element_name = unsafe {
-            std::ffi::CStr::from_ptr(event.element)
-                .to_string_lossy()
-                .into_owned()
-        };
-
-        let window = event.get_window();
-
-        let parts: Vec<&str> = element_name.split(':').collect();
-        let name = if parts.len() > 1 { parts[1] } else { "" };
-        let email = if parts.len() > 2 { parts[2] } else { "" };
-        let role = if parts.len() > 3 { parts[3] } else { "User" };
-        let status = if parts.len() > 4 { parts[4] } else { "Active" };
-
-        let Some(db) = get_db() else {
-            let err = AppError::DependencyInjection(
-                ErrorValue::new(ErrorCode::InternalError, "Database not initialized")
-                    .with_cause("DI container missing database instance")
-            );
-            send_error_response(window, "user_create_response", &err);
-            return;
-        };
-
-        handle_db_result(
-            window,
-            "user_create_response",
-            db.insert_user(name, email, role, status),
-            Some(&format!("User '{}' created successfully", name)),
-        );
-    });
-
-    window.bind("update_user", |event| {
-        info!("update_user called from frontend");
-
-        let element_name = unsafe {
-            std::ffi::CStr::from_ptr(event.element)
-                .to_string_lossy()
-                .into_owned()
-        };
-
-        let window = event.get_window();
-
-        let parts: Vec<&str> = element_name.split(':').collect();
-        let id: i64 = if parts.len() > 1 {
-            parts[1].parse().unwrap_or(0)
-        } else {
-            0
-        };
-        let name = if parts.len() > 2 {
-            Some(parts[2].to_string())
-        } else {
-            None
-        };
-        let email = if parts.len() > 3 {
-            Some(parts[3].to_string())
-        } else {
-            None
-        };
-        let role = if parts.len() > 4 {
-            Some(parts[4].to_string())
-        } else {
-            None
-        };
-        let status = if parts.len() > 5 {
-            Some(parts[5].to_string())
-        } else {
-            None
-        };
-
-        let Some(db) = get_db() else {
-            let err = AppError::DependencyInjection(
-                ErrorValue::new(ErrorCode::InternalError, "Database not initialized")
-                    .with_cause("DI container missing database instance")
-            );
-            send_error_response(window, "user_update_response", &err);
-            return;
-        };
-
-        handle_db_result(
-            window,
-            "user_update_response",
-            db.update_user(id, name, email, role, status),
-            Some(&format!("User ID {} updated successfully", id)),
-        );
-    });
+fn default_status() -> String {
+    "Active".to_string()
+}
 
-    window.bind("delete_user", |event| {
-        info!("delete_user called from frontend");
+fn get_db() -> AppResult<Arc<Database>> {
+    get_container().resolve_arc::<Database>().map_err(|_| {
+        AppError::DependencyInjection(
+            ErrorValue::new(ErrorCode::InternalError, "Database not initialized")
+                .with_cause("DI container missing database instance"),
+        )
+    })
+}
 
-        let element_name = unsafe {
-            std::ffi::CStr::from_ptr(event.element)
-                .to_string_lossy()
-                .into_owned()
-        };
+fn get_users(_ctx: &RpcContext, _req: ()) -> AppResult<Vec<User>> {
+    info!("get_users called from frontend");
+    get_db()?.get_all_users()
+}
 
-        let window = event.get_window();
+fn create_user(_ctx: &RpcContext, req: CreateUserRequest) -> AppResult<CreateUserResponse> {
+    info!("create_user called from frontend");
+    let id = get_db()?.insert_user(&req.name, &req.email, &req.role, &req.status)?;
+    Ok(CreateUserResponse {
+        id,
+        message: format!("User '{}' created successfully", req.name),
+    })
+}
 
-        let parts: Vec<&str> = element_name.split(':').collect();
-        let id: i64 = if parts.len() > 1 {
-            parts[1].parse().unwrap_or(0)
-        } else {
-            0
-        };
+fn update_user(_ctx: &RpcContext, req: UpdateUserRequest) -> AppResult<UserChangeResponse> {
+    info!("update_user called from frontend");
+    let rows_affected =
+        get_db()?.update_user(req.id, req.name, req.email, req.role, req.status)?;
+    Ok(UserChangeResponse {
+        id: req.id,
+        rows_affected,
+        message: format!("User ID {} updated successfully", req.id),
+    })
+}
 
-        let Some(db) = get_db() else {
-            let err = AppError::DependencyInjection(
-                ErrorValue::new(ErrorCode::InternalError, "Database not initialized")
-                    .with_cause("DI container missing database instance")
-            );
-            send_error_response(window, "user_delete_response", &err);
-            return;
-        };
+fn delete_user(_ctx: &RpcContext, req: DeleteUserRequest) -> AppResult<UserChangeResponse> {
+    info!("delete_user called from frontend");
+    let rows_affected = get_db()?.delete_user(req.id)?;
+    Ok(UserChangeResponse {
+        id: req.id,
+        rows_affected,
+        message: format!("User ID {} deleted successfully", req.id),
+    })
+}
 
-        handle_db_result(
-            window,
-            "user_delete_response",
-            db.delete_user(id),
-            Some(&format!("User ID {} deleted successfully", id)),
-        );
-    });
+pub fn setup_db_handlers(window: &mut webui::Window) {
+    rpc::bind(window, "get_users", get_users);
+    rpc::bind(window, "create_user", create_user);
+    rpc::bind(window, "update_user", update_user);
+    rpc::bind(window, "delete_user", delete_user);
 
     info!("Database handlers set up successfully");
 }
@@ -0,0 +1,40 @@
+use crate::core::error::{AppResult, ErrorValue};
+use crate::core::infrastructure::di::ServiceInfo;
+use crate::core::presentation::rpc::{self, RpcContext};
+use log::info;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct DiValidationResponse {
+    pub valid: bool,
+    /// The graph error, with `missing` and `cycles` in its context
+    pub error: Option<ErrorValue>,
+}
+
+/// Registrations visible from the calling window's scope, the root's included
+fn list_services(ctx: &RpcContext, _req: ()) -> AppResult<Vec<ServiceInfo>> {
+    match ctx.window_scope() {
+        Some(scope) => scope.describe(),
+        None => ctx.scope.describe(),
+    }
+}
+
+fn validate_services(ctx: &RpcContext, _req: ()) -> AppResult<DiValidationResponse> {
+    Ok(match ctx.scope.validate() {
+        Ok(()) => DiValidationResponse {
+            valid: true,
+            error: None,
+        },
+        Err(e) => DiValidationResponse {
+            valid: false,
+            error: Some(e.to_value().clone()),
+        },
+    })
+}
+
+pub fn setup_di_handlers(window: &mut webui_rs::webui::Window) {
+    rpc::bind(window, "di:services", list_services);
+    rpc::bind(window, "di:validate", validate_services);
+
+    info!("DI handlers initialized");
+}
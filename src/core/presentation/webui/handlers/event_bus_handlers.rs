@@ -1,18 +1,70 @@
-use crate::core::infrastructure::event_bus::{EventData, get_global_event_bus};
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::event_bus::{
+    get_global_event_bus, DeadLetter, EventBusStats, EventData, EventQuery, EventSchemaInfo,
+    ImportMode, TraceFormat,
+};
+use crate::core::presentation::rpc::{self, RpcContext};
+use crate::core::presentation::webui::subscriptions::{
+    get_window_subscriptions, subscribe_window, window_endpoint,
+};
 use log::info;
 use serde::{Deserialize, Serialize};
-use std::ffi::CStr;
-use webui_rs::webui::bindgen::webui_interface_get_string_at;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 5_000;
+const MAX_REQUEST_TIMEOUT_MS: u64 = 30_000;
+/// Directory every trace is exported to and imported from
+const TRACE_DIR: &str = "traces";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EventPublishRequest {
     pub event_type: String,
     pub data: serde_json::Value,
     pub source: Option<String>,
+    /// Endpoint to deliver to (`window:3`, a service name); broadcast when omitted
+    pub target: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventRequestRequest {
+    pub event_type: String,
+    pub data: serde_json::Value,
+    pub target: Option<String>,
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventReplyRequest {
+    pub correlation_id: String,
+    pub data: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventReplyResponse {
+    /// False when the request was already answered or timed out
+    pub delivered: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventSchemaRequest {
+    pub event_type: String,
+    /// JSON schema the event's payloads must match
+    pub schema: serde_json::Value,
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+pub struct EventPublishResponse {
+    pub success: bool,
+    pub event_type: String,
+    /// Backend listeners the event was dispatched to
+    pub listeners: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct EventHistoryRequest {
+    /// Exact type or topic pattern such as `user.*` or `#`
     pub event_type: Option<String>,
     pub limit: Option<usize>,
 }
@@ -23,118 +75,315 @@ pub struct EventHistoryResponse {
     pub count: usize,
 }
 
-fn read_event_payload(event: &webui_rs::webui::Event) -> Option<String> {
-    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
-    if ptr.is_null() {
-        return None;
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventQueryResponse {
+    pub events: Vec<EventData>,
+    pub count: usize,
+    /// True when results come from the durable event store rather than memory
+    pub persistent: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct EventExportRequest {
+    /// File name inside `traces/`; defaults to `event-trace-<timestamp>.<ext>`
+    pub path: Option<String>,
+    /// `jsonl` or `cbor`; taken from the path's extension when omitted
+    pub format: Option<String>,
+    /// Gzip the file; true for `.gz` paths when omitted
+    pub compress: Option<bool>,
+    #[serde(flatten)]
+    pub query: EventQuery,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventImportRequest {
+    /// File name inside `traces/`
+    pub path: String,
+    pub format: Option<String>,
+    /// Publish the events again instead of only adding them to the history
+    #[serde(default)]
+    pub replay: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventExportResponse {
+    pub path: String,
+    pub count: usize,
+    pub format: TraceFormat,
+    pub compressed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventImportResponse {
+    pub path: String,
+    pub count: usize,
+    pub replayed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventSubscribeRequest {
+    /// Exact type or topic pattern such as `user.*` or `#`
+    pub event_type: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct EventUnsubscribeRequest {
+    /// Pattern to drop; all of the window's subscriptions when omitted
+    pub event_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventSubscriptionsResponse {
+    pub window_id: usize,
+    /// Patterns the window is subscribed to after the call
+    pub event_types: Vec<String>,
+    /// Subscriptions removed by an unsubscribe call
+    pub removed: usize,
+}
+
+/// Pushes need a WebUI window; REST and WebSocket calls carry window id 0
+fn require_window(ctx: &RpcContext) -> AppResult<usize> {
+    if ctx.window_id == 0 {
+        return Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::ValidationFailed, "Event subscriptions require a WebUI window")
+                .with_context("method", ctx.method.clone())
+                .with_context("hint", "WebSocket clients subscribe to the events push channel"),
+        ));
     }
-    Some(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
+    Ok(ctx.window_id)
 }
 
-fn send_response(window: webui_rs::webui::Window, response: &str) {
-    let js = format!(
-        "window.dispatchEvent(new CustomEvent('event_response', {{ detail: {} }}))",
-        response
-    );
-    let _ = window.run_js(&js);
+fn publish_event(_ctx: &RpcContext, req: EventPublishRequest) -> AppResult<EventPublishResponse> {
+    log::info!("[Communication] Frontend → Backend (event:publish): JSON payload received");
+
+    let mut frontend_event = EventData::new(req.event_type.clone(), req.data)
+        .with_source(req.source.unwrap_or_else(|| "frontend".to_string()));
+    frontend_event.target = req.target;
+
+    let listeners = get_global_event_bus().publish(frontend_event)?;
+
+    Ok(EventPublishResponse {
+        success: true,
+        event_type: req.event_type,
+        listeners,
+    })
 }
 
-pub fn setup_event_bus_handlers(window: &mut webui_rs::webui::Window) {
-    window.bind("event:publish", move |event| {
-        let data = match read_event_payload(&event) {
-            Some(payload) => payload,
-            None => {
-                log::error!("event:publish missing payload");
-                return;
-            }
-        };
-
-        log::info!("[Communication] Frontend → Backend (event:publish): JSON payload received");
-
-        match serde_json::from_str::<EventPublishRequest>(&data) {
-            Ok(req) => {
-                let frontend_event = EventData::new(req.event_type.clone(), req.data)
-                    .with_source(req.source.unwrap_or_else(|| "frontend".to_string()));
-
-                get_global_event_bus().emit_with_source(
-                    &frontend_event.event_type,
-                    frontend_event.payload,
-                    frontend_event.source.as_deref().unwrap_or("frontend"),
-                );
-
-                let response = serde_json::json!({
-                    "success": true,
-                    "event_type": req.event_type,
-                });
-
-                if let Ok(json) = serde_json::to_string(&response) {
-                    log::info!("[Communication] Backend → Frontend: JSON response sent");
-                    send_response(webui_rs::webui::Window::from_id(event.window), &json);
-                }
-            }
-            Err(e) => {
-                log::error!("Failed to parse event publish request: {}", e);
-            }
-        }
+fn event_history(
+    _ctx: &RpcContext,
+    req: Option<EventHistoryRequest>,
+) -> AppResult<EventHistoryResponse> {
+    let req = req.unwrap_or(EventHistoryRequest {
+        event_type: None,
+        limit: Some(50),
     });
 
-    window.bind("event:history", move |event| {
-        let data = match read_event_payload(&event) {
-            Some(payload) => payload,
-            None => {
-                log::error!("event:history missing payload");
-                return;
-            }
-        };
-
-        let req: EventHistoryRequest = serde_json::from_str(&data).unwrap_or(EventHistoryRequest {
-            event_type: None,
-            limit: Some(50),
-        });
-
-        let history = match get_global_event_bus().get_history(req.event_type.as_deref(), req.limit) {
-            Ok(h) => h,
-            Err(e) => {
-                log::error!("Failed to get event history: {}", e);
-                return;
-            }
-        };
-
-        let events: Vec<serde_json::Value> = history
-            .iter()
-            .map(|e| {
-                serde_json::json!({
-                    "event_type": e.event_type,
-                    "payload": e.payload,
-                    "timestamp": e.timestamp,
-                    "source": e.source
-                })
+    let history = get_global_event_bus().get_history(req.event_type.as_deref(), req.limit)?;
+
+    let events: Vec<serde_json::Value> = history
+        .iter()
+        .map(|e| {
+            serde_json::json!({
+                "event_type": e.event_type,
+                "payload": e.payload,
+                "timestamp": e.timestamp,
+                "source": e.source
             })
-            .collect();
+        })
+        .collect();
 
-        let response = EventHistoryResponse {
-            events,
-            count: history.len(),
-        };
+    Ok(EventHistoryResponse {
+        count: events.len(),
+        events,
+    })
+}
 
-        if let Ok(json) = serde_json::to_string(&response) {
-            send_response(webui_rs::webui::Window::from_id(event.window), &json);
-        }
-    });
+fn query_events(_ctx: &RpcContext, req: Option<EventQuery>) -> AppResult<EventQueryResponse> {
+    let bus = get_global_event_bus();
+    let events = bus.query(&req.unwrap_or_default())?;
 
-    window.bind("event:stats", move |event| {
-        let stats = get_global_event_bus().get_stats();
+    Ok(EventQueryResponse {
+        count: events.len(),
+        events,
+        persistent: bus.store().is_some(),
+    })
+}
 
-        if let Ok(json) = serde_json::to_string(&stats) {
-            send_response(webui_rs::webui::Window::from_id(event.window), &json);
-        }
-    });
+fn trace_format(name: Option<&str>) -> AppResult<Option<TraceFormat>> {
+    name.map(|name| {
+        TraceFormat::from_name(name).ok_or_else(|| {
+            AppError::Validation(
+                ErrorValue::new(ErrorCode::InvalidFieldValue, format!("Unknown trace format: {}", name))
+                    .with_field("format")
+                    .with_context("expected", "jsonl, cbor"),
+            )
+        })
+    })
+    .transpose()
+}
 
-    window.bind("event:clear_history", move |_event| {
-        if let Err(e) = get_global_event_bus().clear_history() {
-            log::error!("Failed to clear event history: {}", e);
-        }
+/// A bare file name inside `TRACE_DIR`; anything else could reach the user's files
+fn trace_path(name: &str) -> AppResult<PathBuf> {
+    let mut components = Path::new(name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(file)), None) => Ok(Path::new(TRACE_DIR).join(file)),
+        _ => Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::ValidationFailed, "Trace path must be a file name")
+                .with_field("path")
+                .with_details(format!("Traces are read from and written to the {} directory", TRACE_DIR))
+                .with_context("path", name),
+        )),
+    }
+}
+
+fn export_events(_ctx: &RpcContext, req: Option<EventExportRequest>) -> AppResult<EventExportResponse> {
+    let req = req.unwrap_or_default();
+    let requested = req.path.as_deref().map(trace_path).transpose()?;
+    let from_path = requested.as_deref().and_then(TraceFormat::from_path);
+    let format = trace_format(req.format.as_deref())?
+        .or(from_path.map(|(format, _)| format))
+        .unwrap_or_default();
+    let compress = req
+        .compress
+        .unwrap_or_else(|| from_path.is_some_and(|(_, compressed)| compressed));
+    let path = requested.unwrap_or_else(|| {
+        let name = format!(
+            "event-trace-{}.{}{}",
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            format.extension(),
+            if compress { ".gz" } else { "" }
+        );
+        Path::new(TRACE_DIR).join(name)
     });
 
+    let count = get_global_event_bus().export_trace(&path, &req.query, format, compress)?;
+    info!("Exported {} events to {}", count, path.display());
+
+    Ok(EventExportResponse {
+        path: path.display().to_string(),
+        count,
+        format,
+        compressed: compress,
+    })
+}
+
+fn import_events(_ctx: &RpcContext, req: EventImportRequest) -> AppResult<EventImportResponse> {
+    let format = trace_format(req.format.as_deref())?;
+    let mode = if req.replay { ImportMode::Replay } else { ImportMode::Load };
+    let path = trace_path(&req.path)?;
+
+    let count = get_global_event_bus().import_trace(&path, format, mode)?;
+    info!("Imported {} events from {} ({:?})", count, path.display(), mode);
+
+    Ok(EventImportResponse {
+        path: path.display().to_string(),
+        count,
+        replayed: req.replay,
+    })
+}
+
+fn subscribe_events(
+    ctx: &RpcContext,
+    req: EventSubscribeRequest,
+) -> AppResult<EventSubscriptionsResponse> {
+    let window_id = require_window(ctx)?;
+    let event_types = subscribe_window(window_id, &req.event_type)?;
+
+    Ok(EventSubscriptionsResponse {
+        window_id,
+        event_types,
+        removed: 0,
+    })
+}
+
+fn unsubscribe_events(
+    ctx: &RpcContext,
+    req: Option<EventUnsubscribeRequest>,
+) -> AppResult<EventSubscriptionsResponse> {
+    let window_id = require_window(ctx)?;
+    let req = req.unwrap_or_default();
+    let subscriptions = get_window_subscriptions();
+    let removed = subscriptions.unsubscribe(window_id, req.event_type.as_deref())?;
+
+    Ok(EventSubscriptionsResponse {
+        window_id,
+        event_types: subscriptions.topics(window_id),
+        removed,
+    })
+}
+
+/// Publish a request from the calling window and wait for its reply
+fn request_event(ctx: &RpcContext, req: EventRequestRequest) -> AppResult<EventData> {
+    let timeout_ms = req.timeout_ms.unwrap_or(DEFAULT_REQUEST_TIMEOUT_MS);
+    if timeout_ms == 0 || timeout_ms > MAX_REQUEST_TIMEOUT_MS {
+        return Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::InvalidFieldValue, "Request timeout out of range")
+                .with_field("timeout_ms")
+                .with_context("max", MAX_REQUEST_TIMEOUT_MS.to_string()),
+        ));
+    }
+    let source = match ctx.window_id {
+        0 => "frontend".to_string(),
+        window_id => window_endpoint(window_id),
+    };
+    let mut event = EventData::new(req.event_type, req.data).with_source(source);
+    event.target = req.target;
+
+    get_global_event_bus().request(event, Duration::from_millis(timeout_ms))
+}
+
+fn reply_event(ctx: &RpcContext, req: EventReplyRequest) -> AppResult<EventReplyResponse> {
+    let bus = get_global_event_bus();
+    let delivered = match ctx.window_id {
+        0 => bus.reply(&req.correlation_id, req.data)?,
+        window_id => bus.reply_from(&window_endpoint(window_id), &req.correlation_id, req.data)?,
+    };
+    Ok(EventReplyResponse { delivered })
+}
+
+fn event_stats(_ctx: &RpcContext, _req: ()) -> AppResult<EventBusStats> {
+    Ok(get_global_event_bus().get_stats())
+}
+
+fn dead_letters(_ctx: &RpcContext, _req: ()) -> AppResult<Vec<DeadLetter>> {
+    Ok(get_global_event_bus().dead_letters())
+}
+
+fn clear_dead_letters(_ctx: &RpcContext, _req: ()) -> AppResult<usize> {
+    Ok(get_global_event_bus().clear_dead_letters())
+}
+
+fn event_schemas(_ctx: &RpcContext, _req: ()) -> AppResult<Vec<EventSchemaInfo>> {
+    Ok(get_global_event_bus().schemas())
+}
+
+fn register_schema(_ctx: &RpcContext, req: EventSchemaRequest) -> AppResult<Vec<EventSchemaInfo>> {
+    let bus = get_global_event_bus();
+    bus.register_schema(&req.event_type, req.schema, req.description)?;
+    info!("Registered payload schema for {}", req.event_type);
+    Ok(bus.schemas())
+}
+
+fn clear_history(_ctx: &RpcContext, _req: ()) -> AppResult<()> {
+    get_global_event_bus().clear_history()
+}
+
+pub fn setup_event_bus_handlers(window: &mut webui_rs::webui::Window) {
+    rpc::bind(window, "event:publish", publish_event);
+    rpc::bind(window, "event:history", event_history);
+    rpc::bind(window, "event:query", query_events);
+    rpc::bind(window, "event:export", export_events);
+    rpc::bind(window, "event:import", import_events);
+    rpc::bind(window, "event:request", request_event);
+    rpc::bind(window, "event:reply", reply_event);
+    rpc::bind(window, "event:subscribe", subscribe_events);
+    rpc::bind(window, "event:unsubscribe", unsubscribe_events);
+    rpc::bind(window, "event:stats", event_stats);
+    rpc::bind(window, "event:clear_history", clear_history);
+    rpc::bind(window, "event:dead_letters", dead_letters);
+    rpc::bind(window, "event:clear_dead_letters", clear_dead_letters);
+    rpc::bind(window, "event:schemas", event_schemas);
+    rpc::bind(window, "event:register_schema", register_schema);
+
     info!("Event bus handlers initialized");
 }
@@ -1,7 +1,7 @@
+use crate::core::error::AppResult;
+use crate::core::presentation::rpc::{self, RpcContext};
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
-use std::ffi::CStr;
-use webui_rs::webui::bindgen::webui_interface_get_string_at;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct FrontendLogEntry {
@@ -17,57 +17,40 @@ pub struct FrontendLogEntry {
     pub frontend_timestamp: String,
 }
 
-fn read_event_payload(event: &webui_rs::webui::Event) -> Option<String> {
-    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
-    if ptr.is_null() {
-        return None;
-    }
-    Some(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
-}
+fn log_message(_ctx: &RpcContext, entry: FrontendLogEntry) -> AppResult<()> {
+    let _target = format!("frontend::{}", entry.category);
+    let msg = format!("Session {}: {}", entry.session_id, entry.message);
 
-pub fn setup_logging_handlers(window: &mut webui_rs::webui::Window) {
-    window.bind("log_message", |event| {
-        let data = match read_event_payload(&event) {
-            Some(payload) => payload,
-            None => {
-                error!("log_message missing payload");
-                return;
-            }
-        };
+    match entry.level.to_uppercase().as_str() {
+        "ERROR" => {
+            error!("{}", msg);
+            debug!("Frontend metadata: {:?}", entry.meta);
+        }
+        "WARN" => {
+            warn!("{}", msg);
+        }
+        "DEBUG" => {
+            debug!("{}", msg);
+        }
+        "TRACE" => {
+            debug!("TRACE {}", msg);
+        }
+        _ => {
+            info!("{}", msg);
+        }
+    }
 
-        match serde_json::from_str::<FrontendLogEntry>(&data) {
-            Ok(entry) => {
-                let _target = format!("frontend::{}", entry.category);
-                let msg = format!("Session {}: {}", entry.session_id, entry.message);
+    Ok(())
+}
 
-                match entry.level.to_uppercase().as_str() {
-                    "ERROR" => {
-                        error!("{}", msg);
-                        debug!("Frontend metadata: {:?}", entry.meta);
-                    }
-                    "WARN" => {
-                        warn!("{}", msg);
-                    }
-                    "DEBUG" => {
-                        debug!("{}", msg);
-                    }
-                    "TRACE" => {
-                        debug!("TRACE {}", msg);
-                    }
-                    _ => {
-                        info!("{}", msg);
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Failed to parse frontend log entry: {}", e);
-            }
-        }
-    });
+fn get_backend_logs(_ctx: &RpcContext, _req: ()) -> AppResult<()> {
+    info!("Frontend requested backend logs");
+    Ok(())
+}
 
-    window.bind("get_backend_logs", |_event| {
-        info!("Frontend requested backend logs");
-    });
+pub fn setup_logging_handlers(window: &mut webui_rs::webui::Window) {
+    rpc::bind_notification(window, "log_message", log_message);
+    rpc::bind(window, "get_backend_logs", get_backend_logs);
 
     info!("Logging handlers initialized");
 }
@@ -1,6 +1,9 @@
 pub mod ui_handlers;
 pub mod db_handlers;
+pub mod di_handlers;
+pub mod settings_handlers;
 pub mod sysinfo_handlers;
 pub mod logging_handlers;
 pub mod event_bus_handlers;
+pub mod serialization_handlers;
 pub mod window_state_handler;
\ No newline at end of file
@@ -0,0 +1,193 @@
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::database::Database;
+use crate::core::infrastructure::di::get_container;
+use crate::core::infrastructure::event_bus::get_global_event_bus;
+use crate::core::presentation::rpc::{self, RpcContext};
+use crate::core::presentation::webui::handlers::sysinfo_handlers;
+use crate::utils::serialization::{
+    self, BenchmarkResult, FormatComparison, SerializationFormat, SerializationStats,
+};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct NegotiateRequest {
+    /// Formats the frontend can decode, e.g. `["json", "messagepack", "cbor"]`
+    pub formats: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NegotiateResponse {
+    /// Format used for this window from now on
+    pub format: String,
+    /// Format requested by `communication.serialization`
+    pub configured: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FormatInfo {
+    pub name: String,
+    pub mime_type: String,
+    pub description: String,
+    pub binary: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SerializationInfoResponse {
+    pub selected: String,
+    pub window_format: String,
+    pub formats: Vec<FormatInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SerializationStatsResponse {
+    pub selected: String,
+    /// Live counters for every format, including the ones not selected
+    pub formats: Vec<SerializationStats>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BenchmarkRequest {
+    /// Encode/decode rounds per payload and format
+    pub iterations: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BenchmarkResponse {
+    pub iterations: u32,
+    pub results: Vec<BenchmarkResult>,
+    pub comparison: Vec<FormatComparison>,
+}
+
+const DEFAULT_BENCHMARK_ITERATIONS: u32 = 100;
+const MAX_BENCHMARK_ITERATIONS: u32 = 10_000;
+
+/// Agree on the response format for the calling window
+///
+/// The configured format wins when the frontend supports it; otherwise the
+/// window stays on JSON. The reply itself is always sent in the previous format.
+fn negotiate(ctx: &RpcContext, req: NegotiateRequest) -> AppResult<NegotiateResponse> {
+    let configured = SerializationFormat::selected();
+    let supported = req
+        .formats
+        .iter()
+        .filter_map(|name| SerializationFormat::from_name(name))
+        .any(|format| format == configured);
+
+    let format = if supported {
+        configured
+    } else {
+        warn!(
+            "Frontend does not support {} (offered {:?}); window {} stays on JSON",
+            configured, req.formats, ctx.window_id
+        );
+        SerializationFormat::Json
+    };
+
+    rpc::set_window_format(ctx.window_id, format);
+    info!("Window {} negotiated {} serialization", ctx.window_id, format);
+
+    Ok(NegotiateResponse {
+        format: format.name().to_string(),
+        configured: configured.name().to_string(),
+    })
+}
+
+fn serialization_info(ctx: &RpcContext, _req: ()) -> AppResult<SerializationInfoResponse> {
+    let formats = SerializationFormat::available_formats()
+        .iter()
+        .map(|format| FormatInfo {
+            name: format.name().to_string(),
+            mime_type: format.mime_type().to_string(),
+            description: format.description().to_string(),
+            binary: format.is_binary(),
+        })
+        .collect();
+
+    Ok(SerializationInfoResponse {
+        selected: SerializationFormat::selected().name().to_string(),
+        window_format: rpc::window_format(ctx.window_id).name().to_string(),
+        formats,
+    })
+}
+
+fn serialization_stats(_ctx: &RpcContext, _req: ()) -> AppResult<SerializationStatsResponse> {
+    Ok(SerializationStatsResponse {
+        selected: SerializationFormat::selected().name().to_string(),
+        formats: serialization::get_all_stats(),
+    })
+}
+
+fn reset_serialization_stats(ctx: &RpcContext, _req: ()) -> AppResult<SerializationStatsResponse> {
+    serialization::reset_stats();
+    info!("Serialization stats reset");
+    serialization_stats(ctx, ())
+}
+
+/// Real payloads the frontend receives: users, system info and event history
+fn benchmark_payloads() -> AppResult<Vec<(String, serde_json::Value)>> {
+    let mut payloads = Vec::new();
+
+    match get_container().resolve_arc::<Database>() {
+        Ok(db) => payloads.push(("users".to_string(), serde_json::to_value(db.get_all_users()?)?)),
+        Err(_) => warn!("Database not available; benchmarking without the users payload"),
+    }
+
+    payloads.push((
+        "sysinfo".to_string(),
+        serde_json::json!({
+            "system": sysinfo_handlers::get_system_info(),
+            "memory": sysinfo_handlers::get_memory_info(),
+            "process": sysinfo_handlers::get_process_info(),
+            "network": sysinfo_handlers::get_network_info(),
+        }),
+    ));
+
+    let history = get_global_event_bus().get_history(None, Some(100))?;
+    payloads.push(("event_history".to_string(), serde_json::to_value(history)?));
+
+    Ok(payloads)
+}
+
+fn run_benchmark(_ctx: &RpcContext, req: Option<BenchmarkRequest>) -> AppResult<BenchmarkResponse> {
+    let iterations = req
+        .unwrap_or_default()
+        .iterations
+        .unwrap_or(DEFAULT_BENCHMARK_ITERATIONS);
+    if iterations == 0 || iterations > MAX_BENCHMARK_ITERATIONS {
+        return Err(AppError::Validation(
+            ErrorValue::new(ErrorCode::InvalidFieldValue, "iterations out of range")
+                .with_field("iterations")
+                .with_context("max", MAX_BENCHMARK_ITERATIONS.to_string()),
+        ));
+    }
+
+    let payloads = benchmark_payloads()?;
+    let results = serialization::run_benchmark(&payloads, iterations).map_err(|e| {
+        AppError::Serialization(
+            ErrorValue::new(ErrorCode::SerializationFailed, "Serialization benchmark failed")
+                .with_cause(e),
+        )
+    })?;
+    info!(
+        "Serialization benchmark: {} payloads x {} iterations",
+        payloads.len(),
+        iterations
+    );
+
+    Ok(BenchmarkResponse {
+        iterations,
+        comparison: serialization::get_format_comparison(&results),
+        results,
+    })
+}
+
+pub fn setup_serialization_handlers(window: &mut webui_rs::webui::Window) {
+    rpc::bind(window, "serialization:negotiate", negotiate);
+    rpc::bind(window, "serialization:info", serialization_info);
+    rpc::bind(window, "serialization:stats", serialization_stats);
+    rpc::bind(window, "serialization:reset_stats", reset_serialization_stats);
+    rpc::bind(window, "serialization:benchmark", run_benchmark);
+
+    info!("Serialization handlers initialized");
+}
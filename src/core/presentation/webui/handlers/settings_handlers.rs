@@ -0,0 +1,40 @@
+use crate::core::domain::entities::AppConfig as Settings;
+use crate::core::domain::traits::ConfigRepository;
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::presentation::rpc::{self, RpcContext};
+use log::info;
+use std::sync::Arc;
+
+fn repository(ctx: &RpcContext) -> AppResult<Arc<dyn ConfigRepository>> {
+    ctx.scope.resolve_trait::<dyn ConfigRepository>()
+}
+
+/// Keep the structured error when the repository failed with one
+fn repository_error(e: anyhow::Error) -> AppError {
+    e.downcast::<AppError>().unwrap_or_else(|e| {
+        AppError::Configuration(
+            ErrorValue::new(ErrorCode::InternalError, "Config repository failed").with_cause(e.to_string()),
+        )
+    })
+}
+
+/// The settings as saved in the config file
+fn get_settings(ctx: &RpcContext, _req: ()) -> AppResult<Settings> {
+    repository(ctx)?.load_config().map_err(repository_error)
+}
+
+/// Save the settings and return them as written; the config watcher applies
+/// the change to the running app
+fn update_settings(ctx: &RpcContext, settings: Settings) -> AppResult<Settings> {
+    let repository = repository(ctx)?;
+    repository.save_config(&settings).map_err(repository_error)?;
+    info!("Settings saved");
+    repository.load_config().map_err(repository_error)
+}
+
+pub fn setup_settings_handlers(window: &mut webui_rs::webui::Window) {
+    rpc::bind(window, "settings:get", get_settings);
+    rpc::bind(window, "settings:update", update_settings);
+
+    info!("Settings handlers initialized");
+}
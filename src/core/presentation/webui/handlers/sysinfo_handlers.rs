@@ -1,5 +1,11 @@
+use crate::core::error::AppResult;
+use crate::core::infrastructure::config::AppConfig;
+use crate::core::infrastructure::di::get_container;
+use crate::core::infrastructure::lifecycle::get_service_manager;
+use crate::core::presentation::rpc::{self, RpcContext};
 use log::info;
 use std::process::Command;
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use webui_rs::webui;
 
@@ -279,30 +285,54 @@ pub fn get_database_info() -> serde_json::Value {
 
 // ==================== Config Info ====================
 pub fn get_config_info() -> serde_json::Value {
-    let mut config = serde_json::Map::new();
-
-    config.insert("app_name".to_string(), serde_json::json!(
-        env!("CARGO_PKG_NAME").to_string()
-    ));
-    config.insert("version".to_string(), serde_json::json!(
-        env!("CARGO_PKG_VERSION").to_string()
-    ));
-    config.insert("log_level".to_string(), serde_json::json!(
-        std::env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string())
-    ));
-    config.insert("log_file".to_string(), serde_json::json!(
-        std::env::var("LOG_FILE").unwrap_or_else(|_| "./app.log".to_string())
-    ));
-    config.insert("database_path".to_string(), serde_json::json!(
-        std::env::var("DATABASE_PATH").unwrap_or_else(|_| "./app.db".to_string())
-    ));
-    config.insert("port".to_string(), serde_json::json!(0));
-    config.insert("debug_mode".to_string(), serde_json::json!(
+    let config = get_container()
+        .resolve_arc::<AppConfig>()
+        .unwrap_or_else(|_| Arc::new(AppConfig::default()));
+    let mut info = serde_json::Map::new();
+
+    info.insert("app_name".to_string(), serde_json::json!(config.get_app_name()));
+    info.insert("version".to_string(), serde_json::json!(config.get_version()));
+    info.insert("log_level".to_string(), serde_json::json!(config.get_log_level()));
+    info.insert("log_file".to_string(), serde_json::json!(config.get_log_file()));
+    info.insert("database_path".to_string(), serde_json::json!(config.get_db_path()));
+    info.insert("port".to_string(), serde_json::json!(config.get_http_port()));
+    info.insert("debug_mode".to_string(), serde_json::json!(
         std::env::var("DEBUG").is_ok()
     ));
-    config.insert("features".to_string(), serde_json::json!(Vec::<String>::new()));
 
-    serde_json::Value::Object(config)
+    let mut features = Vec::new();
+    if config.is_dark_mode() {
+        features.push("dark_mode");
+    }
+    if config.features.show_tray_icon.unwrap_or(false) {
+        features.push("show_tray_icon");
+    }
+    if config.is_event_store_enabled() {
+        features.push("event_store");
+    }
+    info.insert("features".to_string(), serde_json::json!(features));
+
+    // Where each effective value came from: default, file, env or cli
+    info.insert("config_file".to_string(), serde_json::json!(
+        config.provenance.file.as_ref().map(|path| path.display().to_string())
+    ));
+    info.insert("profile".to_string(), serde_json::json!(config.get_profile()));
+    info.insert("profiles".to_string(), serde_json::json!(config.provenance.profiles));
+    info.insert("values".to_string(), serde_json::json!(config.effective_values()));
+    info.insert("validation_policy".to_string(), serde_json::json!(config.validation_policy()));
+    info.insert("problems".to_string(), serde_json::json!(config.provenance.problems));
+    info.insert("pending_restart".to_string(), serde_json::json!(config.provenance.pending_restart));
+
+    serde_json::Value::Object(info)
+}
+
+// ==================== Services ====================
+pub fn get_services_info() -> serde_json::Value {
+    let services = get_service_manager();
+    serde_json::json!({
+        "healthy": services.is_healthy(),
+        "services": services.statuses(),
+    })
 }
 
 // ==================== Logs ====================
@@ -313,91 +343,56 @@ pub fn get_logs() -> serde_json::Value {
     })
 }
 
+// ==================== RPC Handlers ====================
+fn handle_get_system_info(_ctx: &RpcContext, _req: ()) -> AppResult<serde_json::Value> {
+    info!("get_system_info called from frontend");
+    Ok(get_system_info())
+}
+
+fn handle_get_memory_info(_ctx: &RpcContext, _req: ()) -> AppResult<serde_json::Value> {
+    info!("get_memory_info called from frontend");
+    Ok(get_memory_info())
+}
+
+fn handle_get_process_info(_ctx: &RpcContext, _req: ()) -> AppResult<serde_json::Value> {
+    info!("get_process_info called from frontend");
+    Ok(get_process_info())
+}
+
+fn handle_get_network_info(_ctx: &RpcContext, _req: ()) -> AppResult<serde_json::Value> {
+    info!("get_network_info called from frontend");
+    Ok(get_network_info())
+}
+
+fn handle_get_database_info(_ctx: &RpcContext, _req: ()) -> AppResult<serde_json::Value> {
+    info!("get_database_info called from frontend");
+    Ok(get_database_info())
+}
+
+fn handle_get_config_info(_ctx: &RpcContext, _req: ()) -> AppResult<serde_json::Value> {
+    info!("get_config_info called from frontend");
+    Ok(get_config_info())
+}
+
+fn handle_get_services_info(_ctx: &RpcContext, _req: ()) -> AppResult<serde_json::Value> {
+    Ok(get_services_info())
+}
+
+fn handle_get_logs(_ctx: &RpcContext, _req: ()) -> AppResult<serde_json::Value> {
+    info!("get_logs called from frontend");
+    Ok(get_logs())
+}
+
 // ==================== Setup Handlers ====================
 pub fn setup_sysinfo_handlers(window: &mut webui::Window) {
-    // System info
-    window.bind("get_system_info", |event| {
-        info!("get_system_info called from frontend");
-        let sysinfo = get_system_info();
-        let response = serde_json::json!({ "data": sysinfo });
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('get_system_info_response', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(event.window).run_js(&js);
-    });
-
-    // Memory info
-    window.bind("get_memory_info", |event| {
-        info!("get_memory_info called from frontend");
-        let mem_info = get_memory_info();
-        let response = serde_json::json!({ "data": mem_info });
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('get_memory_info_response', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(event.window).run_js(&js);
-    });
-
-    // Process info
-    window.bind("get_process_info", |event| {
-        info!("get_process_info called from frontend");
-        let proc_info = get_process_info();
-        let response = serde_json::json!({ "data": proc_info });
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('get_process_info_response', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(event.window).run_js(&js);
-    });
-
-    // Network info
-    window.bind("get_network_info", |event| {
-        info!("get_network_info called from frontend");
-        let net_info = get_network_info();
-        let response = serde_json::json!({ "data": net_info });
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('get_network_info_response', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(event.window).run_js(&js);
-    });
-
-    // Database info
-    window.bind("get_database_info", |event| {
-        info!("get_database_info called from frontend");
-        let db_info = get_database_info();
-        let response = serde_json::json!({ "data": db_info });
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('get_database_info_response', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(event.window).run_js(&js);
-    });
-
-    // Config info
-    window.bind("get_config_info", |event| {
-        info!("get_config_info called from frontend");
-        let config_info = get_config_info();
-        let response = serde_json::json!({ "data": config_info });
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('get_config_info_response', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(event.window).run_js(&js);
-    });
-
-    // Logs
-    window.bind("get_logs", |event| {
-        info!("get_logs called from frontend");
-        let logs = get_logs();
-        let response = serde_json::json!({ "data": logs });
-        let js = format!(
-            "window.dispatchEvent(new CustomEvent('get_logs_response', {{ detail: {} }}))",
-            response
-        );
-        webui::Window::from_id(event.window).run_js(&js);
-    });
+    rpc::bind(window, "get_system_info", handle_get_system_info);
+    rpc::bind(window, "get_memory_info", handle_get_memory_info);
+    rpc::bind(window, "get_process_info", handle_get_process_info);
+    rpc::bind(window, "get_network_info", handle_get_network_info);
+    rpc::bind(window, "get_database_info", handle_get_database_info);
+    rpc::bind(window, "get_config_info", handle_get_config_info);
+    rpc::bind(window, "get_services_info", handle_get_services_info);
+    rpc::bind(window, "get_logs", handle_get_logs);
 
     info!("System info handlers set up successfully");
-}
\ No newline at end of file
+}
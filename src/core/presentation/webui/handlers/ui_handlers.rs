@@ -1,45 +1,50 @@
+use crate::core::error::AppResult;
+use crate::core::presentation::rpc::{self, RpcContext};
 use log::{debug, info};
+use serde::Deserialize;
 use webui_rs::webui;
 
-pub fn setup_ui_handlers(window: &mut webui::Window) {
-    window.bind("open_folder", |_event| {
-        info!("Open folder button clicked!");
-    });
+#[derive(Debug, Default, Deserialize)]
+pub struct CounterRequest {
+    #[serde(default)]
+    pub value: Option<i64>,
+}
 
-    window.bind("organize_images", |_event| {
-        info!("Organize images button clicked!");
-    });
+fn open_folder(_ctx: &RpcContext, _req: ()) -> AppResult<()> {
+    info!("Open folder button clicked!");
+    Ok(())
 }
 
-pub fn setup_counter_handlers(window: &mut webui::Window) {
-    window.bind("increment_counter", |event| {
-        let element_name = unsafe {
-            std::ffi::CStr::from_ptr(event.element)
-                .to_string_lossy()
-                .into_owned()
-        };
+fn organize_images(_ctx: &RpcContext, _req: ()) -> AppResult<()> {
+    info!("Organize images button clicked!");
+    Ok(())
+}
+
+fn increment_counter(ctx: &RpcContext, req: Option<CounterRequest>) -> AppResult<()> {
+    let req = req.unwrap_or_default();
+    info!("Counter incremented in Rust backend - Value: {:?}", req.value);
+    debug!(
+        "Increment event details - method: {}, window: {}",
+        ctx.method, ctx.window_id
+    );
+    Ok(())
+}
 
-        info!(
-            "Counter incremented in Rust backend - Element: {}",
-            element_name
-        );
-        debug!(
-            "Increment event details - element: {}, window: {}",
-            element_name, event.window
-        );
-    });
+fn reset_counter(ctx: &RpcContext, _req: Option<CounterRequest>) -> AppResult<()> {
+    info!("Counter reset in Rust backend");
+    debug!(
+        "Reset event details - method: {}, window: {}",
+        ctx.method, ctx.window_id
+    );
+    Ok(())
+}
 
-    window.bind("reset_counter", |event| {
-        let element_name = unsafe {
-            std::ffi::CStr::from_ptr(event.element)
-                .to_string_lossy()
-                .into_owned()
-        };
+pub fn setup_ui_handlers(window: &mut webui::Window) {
+    rpc::bind_notification(window, "open_folder", open_folder);
+    rpc::bind_notification(window, "organize_images", organize_images);
+}
 
-        info!("Counter reset in Rust backend - Element: {}", element_name);
-        debug!(
-            "Reset event details - element: {}, window: {}",
-            element_name, event.window
-        );
-    });
+pub fn setup_counter_handlers(window: &mut webui::Window) {
+    rpc::bind_notification(window, "increment_counter", increment_counter);
+    rpc::bind_notification(window, "reset_counter", reset_counter);
 }
@@ -1,7 +1,8 @@
-use log::{debug, error, info};
+use crate::core::error::AppResult;
+use crate::core::infrastructure::event_bus::{get_global_event_bus, DispatchMode, EventData};
+use crate::core::presentation::rpc::{self, RpcContext};
+use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
-use std::ffi::CStr;
-use webui_rs::webui::bindgen::webui_interface_get_string_at;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -22,47 +23,57 @@ pub struct WindowStateEvent {
     pub timestamp: String,
 }
 
-fn read_event_payload(event: &webui_rs::webui::Event) -> Option<String> {
-    let ptr = unsafe { webui_interface_get_string_at(event.window, event.event_number, 0) };
-    if ptr.is_null() {
-        return None;
+/// Event type frontends may publish through `event:publish` instead of calling the binding
+pub const WINDOW_STATE_EVENT: &str = "window.state_changed";
+
+fn window_state_change(_ctx: &RpcContext, event_data: WindowStateEvent) -> AppResult<()> {
+    record_window_state(&event_data);
+    Ok(())
+}
+
+fn on_window_state_event(event: &EventData) {
+    match serde_json::from_value::<WindowStateEvent>(event.payload.clone()) {
+        Ok(event_data) => record_window_state(&event_data),
+        Err(e) => warn!("Ignoring malformed {} event: {}", WINDOW_STATE_EVENT, e),
     }
-    Some(unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() })
 }
 
-pub fn setup_window_state_handlers(window: &mut webui_rs::webui::Window) {
-    window.bind("window_state_change", |event| {
-        let data = match read_event_payload(&event) {
-            Some(payload) => payload,
-            None => {
-                error!("window_state_change missing payload");
-                return;
-            }
-        };
+fn record_window_state(event_data: &WindowStateEvent) {
+    let state_msg = match event_data.state {
+        WindowState::Focused => "focused (became active)",
+        WindowState::Blurred => "blurred (lost focus)",
+        WindowState::Minimized => "minimized",
+        WindowState::Maximized => "maximized",
+        WindowState::Restored => "restored",
+        WindowState::Closed => "closed",
+    };
+
+    info!(
+        "Window State Change | ID: {} | Title: '{}' | State: {} | Time: {}",
+        event_data.window_id, event_data.title, state_msg, event_data.timestamp
+    );
 
-        match serde_json::from_str::<WindowStateEvent>(&data) {
-            Ok(event_data) => {
-                let state_msg = match event_data.state {
-                    WindowState::Focused => "focused (became active)",
-                    WindowState::Blurred => "blurred (lost focus)",
-                    WindowState::Minimized => "minimized",
-                    WindowState::Maximized => "maximized",
-                    WindowState::Restored => "restored",
-                    WindowState::Closed => "closed",
-                };
+    debug!("Full window state event: {:?}", event_data);
+}
 
-                info!(
-                    "Window State Change | ID: {} | Title: '{}' | State: {} | Time: {}",
-                    event_data.window_id, event_data.title, state_msg, event_data.timestamp
-                );
+pub fn setup_window_state_handlers(window: &mut webui_rs::webui::Window) {
+    rpc::bind_notification(window, "window_state_change", window_state_change);
 
-                debug!("Full window state event: {:?}", event_data);
-            }
-            Err(e) => {
-                error!("Failed to parse window state event: {}", e);
-            }
-        }
-    });
+    let bus = get_global_event_bus();
+    if let Err(e) = bus.register_payload_type::<WindowStateEvent>(
+        WINDOW_STATE_EVENT,
+        Some("A window was focused, blurred, minimized, maximized, restored or closed".to_string()),
+    ) {
+        error!("Failed to register the {} schema: {}", WINDOW_STATE_EVENT, e);
+    }
+
+    if let Err(e) = bus.subscribe(
+        WINDOW_STATE_EVENT,
+        DispatchMode::Worker,
+        on_window_state_event,
+    ) {
+        error!("Failed to subscribe to {}: {}", WINDOW_STATE_EVENT, e);
+    }
 
     info!("Window state handlers initialized");
 }
@@ -1,3 +1,6 @@
 pub mod handlers;
+pub mod scopes;
+pub mod subscriptions;
+pub mod tray;
 
 pub use handlers::*;
\ No newline at end of file
@@ -0,0 +1,127 @@
+#![allow(dead_code)]
+// src/core/presentation/webui/scopes.rs
+// DI scopes per WebUI window, children of the root container
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use log::debug;
+use webui_rs::webui;
+use webui_rs::webui::bindgen::webui_is_shown;
+
+use crate::core::infrastructure::di::{root_container, Container};
+
+/// WebUI event type sent when a window's page goes away
+const WEBUI_EVENT_DISCONNECTED: usize = 0;
+
+/// One scope per window; closed windows' scopes are dropped by `sweep`
+pub struct WindowScopes {
+    root: Arc<Container>,
+    scopes: Mutex<HashMap<usize, Arc<Container>>>,
+    is_alive: fn(usize) -> bool,
+}
+
+impl WindowScopes {
+    pub fn new(root: Arc<Container>, is_alive: fn(usize) -> bool) -> Self {
+        Self {
+            root,
+            scopes: Mutex::new(HashMap::new()),
+            is_alive,
+        }
+    }
+
+    /// The window's scope, created on first use
+    pub fn scope(&self, window_id: usize) -> Arc<Container> {
+        let Ok(mut scopes) = self.scopes.lock() else {
+            return self.root.create_scope();
+        };
+        Arc::clone(scopes.entry(window_id).or_insert_with(|| {
+            debug!("Creating DI scope for window {}", window_id);
+            self.root.create_scope()
+        }))
+    }
+
+    /// Drop a window's scope; services still held elsewhere live until released
+    pub fn drop_scope(&self, window_id: usize) -> bool {
+        self.scopes
+            .lock()
+            .map(|mut scopes| scopes.remove(&window_id).is_some())
+            .unwrap_or(false)
+    }
+
+    /// Drop the scopes of closed windows; returns how many were dropped
+    pub fn sweep(&self) -> usize {
+        let Ok(mut scopes) = self.scopes.lock() else {
+            return 0;
+        };
+        let before = scopes.len();
+        let is_alive = self.is_alive;
+        scopes.retain(|id, _| is_alive(*id));
+        before - scopes.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.scopes.lock().map(|scopes| scopes.len()).unwrap_or(0)
+    }
+}
+
+fn webui_window_alive(window_id: usize) -> bool {
+    unsafe { webui_is_shown(window_id) }
+}
+
+static WINDOW_SCOPES: OnceLock<WindowScopes> = OnceLock::new();
+
+pub fn get_window_scopes() -> &'static WindowScopes {
+    WINDOW_SCOPES.get_or_init(|| WindowScopes::new(Arc::clone(root_container()), webui_window_alive))
+}
+
+/// Bound to every event of a window (element `""`): drops the scopes of
+/// windows that closed
+pub fn handle_window_event(event: webui::Event) {
+    if event.event_type == WEBUI_EVENT_DISCONNECTED {
+        let dropped = get_window_scopes().sweep();
+        if dropped > 0 {
+            debug!("Dropped {} DI scope(s) of closed windows", dropped);
+        }
+    }
+}
+
+/// Scope of an RPC call: a child of the window's scope, or of the root for
+/// REST and WebSocket calls (window id 0)
+pub fn request_scope(window_id: usize) -> Arc<Container> {
+    match window_id {
+        0 => root_container().create_scope(),
+        window_id => get_window_scopes().scope(window_id).create_scope(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn odd_windows_alive(window_id: usize) -> bool {
+        window_id % 2 == 1
+    }
+
+    #[test]
+    fn test_window_scopes() {
+        let root = Arc::new(Container::new());
+        root.register(String::from("root")).unwrap();
+        let scopes = WindowScopes::new(Arc::clone(&root), odd_windows_alive);
+
+        let first = scopes.scope(1);
+        first.register(42u32).unwrap();
+        assert!(Arc::ptr_eq(&first, &scopes.scope(1)));
+        assert_eq!(scopes.scope(1).resolve::<u32>().unwrap(), 42);
+        assert_eq!(scopes.scope(3).resolve::<String>().unwrap(), "root");
+        assert!(scopes.scope(3).resolve::<u32>().is_err());
+
+        // Window 2 is closed: looking up other windows keeps its scope until a sweep
+        scopes.scope(2);
+        scopes.scope(5);
+        assert_eq!(scopes.len(), 4);
+        assert_eq!(scopes.sweep(), 1);
+        assert!(scopes.drop_scope(3));
+        assert_eq!(scopes.len(), 2);
+    }
+}
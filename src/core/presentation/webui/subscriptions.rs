@@ -0,0 +1,301 @@
+#![allow(dead_code)]
+// src/core/presentation/webui/subscriptions.rs
+// Per-window event bus subscriptions pushed to the frontend as they are published
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, RwLock, Weak};
+
+use log::{debug, info};
+use webui_rs::webui::bindgen::webui_is_shown;
+
+use crate::core::error::{AppError, AppResult, ErrorCode, ErrorValue};
+use crate::core::infrastructure::event_bus::{
+    get_global_event_bus, DispatchMode, EventBus, EventData, QueueOptions, Subscription,
+};
+use crate::core::presentation::rpc;
+
+/// Window event carrying pushed bus events: `{ subscription, event }`
+pub const PUSH_EVENT_NAME: &str = "event:message";
+
+/// Bus endpoint of a window; events targeted at it reach only that window
+pub fn window_endpoint(window_id: usize) -> String {
+    format!("window:{}", window_id)
+}
+
+/// Delivery side of window subscriptions
+pub trait WindowSink: Send + Sync {
+    /// False once the window is closed; its subscriptions are then dropped
+    fn is_alive(&self, window_id: usize) -> bool;
+    fn push(&self, window_id: usize, pattern: &str, event: &EventData);
+}
+
+/// Pushes into WebUI windows through the RPC transport
+struct WebUiSink;
+
+impl WindowSink for WebUiSink {
+    fn is_alive(&self, window_id: usize) -> bool {
+        unsafe { webui_is_shown(window_id) }
+    }
+
+    fn push(&self, window_id: usize, pattern: &str, event: &EventData) {
+        let data = serde_json::json!({ "subscription": pattern, "event": event });
+        rpc::push_to_window(window_id, PUSH_EVENT_NAME, &data);
+    }
+}
+
+/// Bus subscriptions of each window, keyed by topic pattern
+type Windows = HashMap<usize, HashMap<String, Subscription>>;
+type WindowTable = Mutex<Windows>;
+
+pub struct WindowSubscriptions {
+    windows: Arc<WindowTable>,
+    sink: Arc<dyn WindowSink>,
+    queue: RwLock<QueueOptions>,
+}
+
+impl WindowSubscriptions {
+    pub fn new(sink: Arc<dyn WindowSink>) -> Self {
+        Self {
+            windows: Arc::new(Mutex::new(HashMap::new())),
+            sink,
+            queue: RwLock::new(QueueOptions::default()),
+        }
+    }
+
+    /// Queue size and overflow policy for subscriptions made from now on
+    pub fn set_queue_options(&self, options: QueueOptions) {
+        if let Ok(mut queue) = self.queue.write() {
+            *queue = options;
+        }
+    }
+
+    fn lock(&self) -> AppResult<MutexGuard<'_, Windows>> {
+        self.windows.lock().map_err(|e| {
+            AppError::LockPoisoned(
+                ErrorValue::new(ErrorCode::LockPoisoned, "Failed to acquire window subscription lock")
+                    .with_cause(e.to_string()),
+            )
+        })
+    }
+
+    /// Subscribe a window to a topic pattern; subscribing twice is a no-op
+    ///
+    /// Returns the window's patterns after the change.
+    pub fn subscribe(&self, bus: &EventBus, window_id: usize, pattern: &str) -> AppResult<Vec<String>> {
+        self.sweep();
+        let mut windows = self.lock()?;
+        let topics = windows.entry(window_id).or_default();
+        if !topics.contains_key(pattern) {
+            let sink = Arc::clone(&self.sink);
+            let table = Arc::downgrade(&self.windows);
+            let key = pattern.to_string();
+            let endpoint = window_endpoint(window_id);
+            let options = self.queue.read().map(|queue| *queue).unwrap_or_default();
+            // Each subscription drains its own queue, so a slow window never
+            // holds up the publisher or other windows
+            let subscription = bus.subscribe_endpoint(
+                &endpoint,
+                pattern,
+                DispatchMode::Queued(options),
+                move |event: &EventData| {
+                    if sink.is_alive(window_id) {
+                        sink.push(window_id, &key, event);
+                    } else {
+                        drop_window(&table, window_id);
+                    }
+                },
+            )?;
+            topics.insert(pattern.to_string(), subscription);
+            debug!("Window {} subscribed to {}", window_id, pattern);
+        }
+        Ok(sorted_keys(topics))
+    }
+
+    /// Remove one pattern, or every pattern of the window when `pattern` is `None`
+    ///
+    /// Returns the number of subscriptions removed.
+    pub fn unsubscribe(&self, window_id: usize, pattern: Option<&str>) -> AppResult<usize> {
+        let mut windows = self.lock()?;
+        let Some(topics) = windows.get_mut(&window_id) else {
+            return Ok(0);
+        };
+        let removed: Vec<Subscription> = match pattern {
+            Some(pattern) => topics.remove(pattern).into_iter().collect(),
+            None => topics.drain().map(|(_, s)| s).collect(),
+        };
+        if topics.is_empty() {
+            windows.remove(&window_id);
+        }
+        drop(windows);
+
+        let count = removed.len();
+        for subscription in removed {
+            subscription.unsubscribe();
+        }
+        Ok(count)
+    }
+
+    /// Patterns a window is subscribed to, sorted
+    pub fn topics(&self, window_id: usize) -> Vec<String> {
+        self.windows
+            .lock()
+            .ok()
+            .and_then(|windows| windows.get(&window_id).map(sorted_keys))
+            .unwrap_or_default()
+    }
+
+    pub fn window_count(&self) -> usize {
+        self.windows.lock().map(|windows| windows.len()).unwrap_or(0)
+    }
+
+    /// Drop the subscriptions of every window that is no longer shown
+    pub fn sweep(&self) -> usize {
+        let window_ids: Vec<usize> = match self.windows.lock() {
+            Ok(windows) => windows.keys().copied().collect(),
+            Err(_) => return 0,
+        };
+        window_ids
+            .into_iter()
+            .filter(|id| !self.sink.is_alive(*id))
+            .map(|id| drop_window(&Arc::downgrade(&self.windows), id))
+            .sum()
+    }
+}
+
+fn sorted_keys(topics: &HashMap<String, Subscription>) -> Vec<String> {
+    let mut keys: Vec<String> = topics.keys().cloned().collect();
+    keys.sort();
+    keys
+}
+
+/// Unsubscribe everything a closed window registered
+fn drop_window(table: &Weak<WindowTable>, window_id: usize) -> usize {
+    let Some(table) = table.upgrade() else {
+        return 0;
+    };
+    let removed = match table.lock() {
+        Ok(mut windows) => windows.remove(&window_id).unwrap_or_default(),
+        Err(_) => return 0,
+    };
+    let count = removed.len();
+    for (_, subscription) in removed {
+        subscription.unsubscribe();
+    }
+    if count > 0 {
+        info!("Dropped {} event subscriptions of closed window {}", count, window_id);
+    }
+    count
+}
+
+static WINDOW_SUBSCRIPTIONS: OnceLock<WindowSubscriptions> = OnceLock::new();
+
+/// Subscriptions of WebUI windows on the global event bus
+pub fn get_window_subscriptions() -> &'static WindowSubscriptions {
+    WINDOW_SUBSCRIPTIONS.get_or_init(|| WindowSubscriptions::new(Arc::new(WebUiSink)))
+}
+
+/// Subscribe a WebUI window on the global event bus
+pub fn subscribe_window(window_id: usize, pattern: &str) -> AppResult<Vec<String>> {
+    get_window_subscriptions().subscribe(get_global_event_bus(), window_id, pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    struct TestSink {
+        alive: AtomicBool,
+        pushed: Mutex<mpsc::Sender<(usize, String, String)>>,
+    }
+
+    impl WindowSink for TestSink {
+        fn is_alive(&self, _window_id: usize) -> bool {
+            self.alive.load(Ordering::SeqCst)
+        }
+
+        fn push(&self, window_id: usize, pattern: &str, event: &EventData) {
+            let _ = self.pushed.lock().unwrap().send((
+                window_id,
+                pattern.to_string(),
+                event.event_type.clone(),
+            ));
+        }
+    }
+
+    fn setup() -> (Arc<TestSink>, WindowSubscriptions, mpsc::Receiver<(usize, String, String)>) {
+        let (sender, receiver) = mpsc::channel();
+        let sink = Arc::new(TestSink {
+            alive: AtomicBool::new(true),
+            pushed: Mutex::new(sender),
+        });
+        let subscriptions = WindowSubscriptions::new(Arc::clone(&sink) as Arc<dyn WindowSink>);
+        (sink, subscriptions, receiver)
+    }
+
+    #[test]
+    fn test_push_matching_events_per_window() {
+        let bus = EventBus::new(10);
+        let (_sink, subscriptions, pushed) = setup();
+
+        subscriptions.subscribe(&bus, 1, "user.*").unwrap();
+        assert_eq!(subscriptions.subscribe(&bus, 1, "user.*").unwrap(), vec!["user.*"]);
+        subscriptions.subscribe(&bus, 2, "order.created").unwrap();
+        assert_eq!(bus.total_listeners(), 2);
+
+        bus.emit("user.created", serde_json::json!({}));
+        bus.emit("order.created", serde_json::json!({}));
+        // Targeted at window 1, which has no matching topic
+        bus.publish(EventData::new("order.created", serde_json::json!({})).with_target(window_endpoint(1)))
+            .unwrap();
+        bus.publish(EventData::new("user.deleted", serde_json::json!({})).with_target(window_endpoint(1)))
+            .unwrap();
+        // Windows drain their own queues, so only per-window order is fixed
+        let mut received: Vec<_> = (0..3)
+            .map(|_| pushed.recv_timeout(Duration::from_secs(2)).unwrap())
+            .collect();
+        received.sort();
+        assert_eq!(
+            received,
+            vec![
+                (1, "user.*".into(), "user.created".into()),
+                (1, "user.*".into(), "user.deleted".into()),
+                (2, "order.created".into(), "order.created".into()),
+            ]
+        );
+        assert!(pushed.recv_timeout(Duration::from_millis(50)).is_err());
+
+        assert_eq!(subscriptions.unsubscribe(1, Some("user.*")).unwrap(), 1);
+        assert!(subscriptions.topics(1).is_empty());
+        assert_eq!(bus.total_listeners(), 1);
+        assert!(subscriptions.subscribe(&bus, 1, "user*").is_err());
+    }
+
+    #[test]
+    fn test_closed_windows_are_dropped() {
+        let bus = EventBus::new(10);
+        let (sink, subscriptions, pushed) = setup();
+        subscriptions.subscribe(&bus, 1, "#").unwrap();
+        subscriptions.subscribe(&bus, 1, "user.created").unwrap();
+
+        sink.alive.store(false, Ordering::SeqCst);
+        bus.emit("ping", serde_json::json!({}));
+        // Dropping happens on the worker; wait for the listener to go away
+        for _ in 0..200 {
+            if subscriptions.window_count() == 0 {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(subscriptions.window_count(), 0);
+        assert_eq!(bus.total_listeners(), 0);
+        assert!(pushed.try_recv().is_err());
+
+        sink.alive.store(true, Ordering::SeqCst);
+        subscriptions.subscribe(&bus, 3, "a").unwrap();
+        sink.alive.store(false, Ordering::SeqCst);
+        assert_eq!(subscriptions.sweep(), 1);
+    }
+}
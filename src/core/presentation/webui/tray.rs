@@ -0,0 +1,16 @@
+// src/core/presentation/webui/tray.rs
+// Tray icon visibility, following `features.show_tray_icon` at startup and on reload
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use log::info;
+
+static VISIBLE: AtomicBool = AtomicBool::new(false);
+
+/// Show or hide the tray icon; WebUI has no tray API, so this records whether
+/// the icon should show for a platform tray to follow
+pub fn set_visible(visible: bool) {
+    if VISIBLE.swap(visible, Ordering::Relaxed) != visible {
+        info!("Tray icon {}", if visible { "shown" } else { "hidden" });
+    }
+}
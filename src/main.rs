@@ -9,12 +9,24 @@ use webui_rs::webui::bindgen::webui_set_port;
 // MVVM: Core - Domain, Application, Infrastructure, Presentation
 mod core;
 use core::{
-    infrastructure::{config::AppConfig, database::Database, logging, di},
+    domain::traits::ConfigRepository,
+    infrastructure::{
+        config::{
+            self, describe_problem, AppConfig, ConfigChangedEvent, ConfigWatcher, FileConfigRepository,
+            CONFIG_CHANGED_EVENT,
+        },
+        database::{self, Database},
+        di,
+        event_bus::{get_global_event_bus, EventStore, OverflowPolicy, QueueOptions, RetentionPolicy},
+        lifecycle::{self, get_service_manager, OwnedService},
+        logging,
+    },
     presentation,
 };
 
 // Shared utilities
 mod utils;
+use utils::serialization::SerializationFormat;
 
 include!(concat!(env!("OUT_DIR"), "/embedded_frontend.rs"));
 
@@ -54,6 +66,12 @@ fn main() {
     // Initialize panic handler FIRST before anything else
     init_panic_handler();
 
+    // `config convert ...` is a one-shot command, not an app start
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("config") {
+        std::process::exit(config::run_command(&args[1..]));
+    }
+
     info!("Starting application - panic handler initialized");
     // Initialize dependency injection container
     if let Err(e) = di::init_container() {
@@ -68,17 +86,29 @@ fn main() {
     let config = match AppConfig::load() {
         Ok(config) => {
             println!("Configuration loaded successfully!");
+            if let Some(file) = &config.provenance.file {
+                println!("Loaded configuration from: {}", file.display());
+            }
             println!(
                 "Application: {} v{}",
                 config.get_app_name(),
                 config.get_version()
             );
+            if let Some(profile) = config.get_profile() {
+                println!("Profile: {}", profile);
+            }
             config
         }
         Err(e) => {
+            // validation.on_invalid = "fail": refuse to start with a bad config
             eprintln!("Failed to load configuration: {}", e);
-            eprintln!("Using default configuration");
-            AppConfig::default()
+            if let Some(details) = &e.to_value().details {
+                for line in details.lines() {
+                    eprintln!("  - {}", line);
+                }
+            }
+            eprintln!("Fix the settings above, or set validation.on_invalid = \"degraded\" to start with defaults");
+            std::process::exit(1);
         }
     };
 
@@ -104,11 +134,30 @@ fn main() {
         config.get_app_name(),
         config.get_version()
     );
+    info!("Profile: {}", config.get_profile().unwrap_or("(none)"));
     info!("=============================================");
 
+    for problem in &config.provenance.problems {
+        warn!("Invalid configuration, using the default instead: {}", describe_problem(problem));
+    }
+
+    // Ctrl+C / SIGTERM closes the windows so the shutdown below still runs
+    if let Err(e) = lifecycle::install_signal_handler(webui::exit) {
+        warn!("Shutdown on signals unavailable: {}", e);
+    }
+
     // Get communication settings from config
     let transport = config.get_transport();
-    let serialization = config.get_serialization();
+    let serialization_format = SerializationFormat::from_name(config.get_serialization())
+        .unwrap_or_else(|| {
+            warn!(
+                "Unknown serialization format '{}', falling back to JSON",
+                config.get_serialization()
+            );
+            SerializationFormat::Json
+        });
+    serialization_format.select();
+    let serialization = serialization_format.name();
     
     // Display backend-frontend communication configuration
     info!("═══════════════════════════════════════════════════════");
@@ -128,16 +177,17 @@ fn main() {
     info!("  └──────────────────┴────────────────────────────────┘");
     info!("");
     info!("  SERIALIZATION FORMAT:");
-    info!("  ┌──────────────┬────────┬────────┬─────────────────────┐");
-    info!("  │ Format       │ Size   │ Speed  │ Description         │");
-    info!("  ├──────────────┼────────┼────────┼─────────────────────┤");
+    info!("  ┌──────────────┬─────────────────────┐");
+    info!("  │ Format       │ Description         │");
+    info!("  ├──────────────┼─────────────────────┤");
     let json_active = if serialization == "json" { "✓ [ACTIVE]" } else { "   " };
-    info!("  │ JSON         │ 1.0x   │ 1.0x   │ Human readable{}    │", json_active);
+    info!("  │ JSON         │ Human readable{}    │", json_active);
     let msgpack_active = if serialization == "messagepack" { "✓ [ACTIVE]" } else { "   " };
-    info!("  │ MessagePack  │ ~0.7x  │ ~1.5x  │ Binary, compact{}   │", msgpack_active);
+    info!("  │ MessagePack  │ Binary, compact{}   │", msgpack_active);
     let cbor_active = if serialization == "cbor" { "✓ [ACTIVE]" } else { "   " };
-    info!("  │ CBOR         │ ~0.6x  │ ~1.6x  │ RFC 7049{}          │", cbor_active);
-    info!("  └──────────────┴────────┴────────┴─────────────────────┘");
+    info!("  │ CBOR         │ RFC 7049{}          │", cbor_active);
+    info!("  └──────────────┴─────────────────────┘");
+    info!("  Sizes and speeds on this machine: serialization:benchmark (DevTools → Serialization)");
     info!("");
     info!("  SELECTED: {} + {}", 
         match transport {
@@ -163,12 +213,16 @@ fn main() {
                 serialization.to_uppercase());
         },
         "http_rest" => {
-            info!("    Frontend JS ──[HTTP/JSON]──> REST API ──> Rust Backend");
-            info!("    Rust Backend ─[HTTP/JSON]──> REST API ──> Frontend JS");
+            info!("    Frontend JS ──[HTTP/{}]──> REST API ──> Rust Backend",
+                serialization.to_uppercase());
+            info!("    Rust Backend ─[HTTP/{}]──> REST API ──> Frontend JS",
+                serialization.to_uppercase());
         },
         "websocket" => {
-            info!("    Frontend JS ──[WS/JSON]──> WebSocket Server ──> Rust Backend");
-            info!("    Rust Backend ─[WS/JSON]──> WebSocket Server ──> Frontend JS");
+            info!("    Frontend JS ──[WS/{}]──> WebSocket Server ──> Rust Backend",
+                serialization.to_uppercase());
+            info!("    Rust Backend ─[WS/{}]──> WebSocket Server ──> Frontend JS",
+                serialization.to_uppercase());
         },
         _ => {}
     }
@@ -176,41 +230,67 @@ fn main() {
 
     info!("Application starting...");
 
-    // Get database path from config
-    let db_path = config.get_db_path();
-    info!("Database path: {}", db_path);
-
-    // Initialize SQLite database
-    let db = match Database::new(db_path) {
-        Ok(db) => {
-            info!("Database initialized successfully");
-            if let Err(e) = db.init() {
-                eprintln!("Failed to initialize database schema: {}", e);
-                return;
-            }
-            if config.should_create_sample_data() {
-                if let Err(e) = db.insert_sample_data() {
-                    eprintln!("Failed to insert sample data: {}", e);
-                    return;
-                }
-                info!("Sample data created (if not exists)");
-            }
-            Arc::new(db)
+    // Wire services declaratively; each is built when first resolved
+    if let Err(e) = database::register(container) {
+        eprintln!("Failed to register database in DI container: {}", e);
+        return;
+    }
+
+    // Settings are saved to the file that was loaded, or created in the working directory
+    let settings_file = config
+        .provenance
+        .file
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("app.config.toml"));
+    if let Err(e) = container
+        .register_trait::<dyn ConfigRepository>(Arc::new(FileConfigRepository::new(settings_file.clone())))
+    {
+        error!("Failed to register config repository: {}", e);
+    }
+
+    // Catch missing services and cycles before anything is built
+    if let Err(e) = container.validate() {
+        eprintln!("Invalid DI wiring: {}", e);
+        if let Some(details) = &e.to_value().details {
+            eprintln!("  {}", details);
         }
+        return;
+    }
+
+    // Build the database now so a broken setup stops startup
+    let db = match container.resolve_arc::<Database>() {
+        Ok(db) => db,
         Err(e) => {
             eprintln!("Failed to initialize database: {}", e);
             return;
         }
     };
+    let services = get_service_manager();
+    if let Err(e) = services.register(db.clone()) {
+        error!("Failed to register database service: {}", e);
+    }
 
-    // Register database in the container
-    if let Err(e) = container.register_singleton(Arc::clone(&db)) {
-        eprintln!("Failed to register database in DI container: {}", e);
-        return;
+    // Persist events to the database when enabled
+    if config.is_event_store_enabled() {
+        let retention = RetentionPolicy::from_config(
+            config.get_event_retention_days(),
+            config.get_max_stored_events(),
+        );
+        match EventStore::open(Arc::clone(&db), retention) {
+            Ok(store) => get_global_event_bus().attach_store(Arc::new(store)),
+            Err(e) => error!("Failed to open event store, events stay in memory: {}", e),
+        }
     }
 
-    // Initialize database handlers with the database instance
-    presentation::db_handlers::init_database(Arc::clone(&db));
+    let overflow = OverflowPolicy::from_name(config.get_event_overflow()).unwrap_or_else(|| {
+        warn!("Unknown events.overflow '{}', using drop_oldest", config.get_event_overflow());
+        OverflowPolicy::DropOldest
+    });
+    presentation::webui::subscriptions::get_window_subscriptions().set_queue_options(QueueOptions {
+        capacity: config.get_event_queue_capacity(),
+        overflow,
+    });
+    presentation::webui::tray::set_visible(config.features.show_tray_icon.unwrap_or(false));
 
     // Demo code - only runs with --features demo flag
     #[cfg(feature = "demo")]
@@ -241,10 +321,86 @@ fn main() {
     presentation::ui_handlers::setup_ui_handlers(&mut my_window);
     presentation::ui_handlers::setup_counter_handlers(&mut my_window);
     presentation::db_handlers::setup_db_handlers(&mut my_window);
+    presentation::di_handlers::setup_di_handlers(&mut my_window);
+    presentation::settings_handlers::setup_settings_handlers(&mut my_window);
     presentation::sysinfo_handlers::setup_sysinfo_handlers(&mut my_window);
     presentation::logging_handlers::setup_logging_handlers(&mut my_window);
     presentation::event_bus_handlers::setup_event_bus_handlers(&mut my_window);
+    presentation::serialization_handlers::setup_serialization_handlers(&mut my_window);
     presentation::window_state_handler::setup_window_state_handlers(&mut my_window);
+    // Every window event, so closed windows' DI scopes are dropped
+    my_window.bind("", presentation::webui::scopes::handle_window_event);
+
+    // Serve the same RPC methods over REST when the http_rest transport is selected
+    let http_server = if transport == "http_rest" {
+        let addr = format!("127.0.0.1:{}", config.get_http_port());
+        match presentation::http::HttpServer::start(&addr) {
+            Ok(server) => Some(Arc::new(OwnedService::new("http", server).depends_on("database"))),
+            Err(e) => {
+                error!("Failed to start HTTP REST server: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // WebSocket transport: RPC calls plus server push of events, logs and metrics
+    let ws_server = if transport == "websocket" {
+        let addr = format!("127.0.0.1:{}", config.get_http_port());
+        match presentation::websocket::WebSocketServer::start(&addr) {
+            Ok(server) => Some(Arc::new(OwnedService::new("websocket", server).depends_on("database"))),
+            Err(e) => {
+                error!("Failed to start WebSocket server: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Transport servers stop before the database they call into
+    for server in [
+        http_server.clone().map(|s| s as Arc<dyn lifecycle::Service>),
+        ws_server.clone().map(|s| s as Arc<dyn lifecycle::Service>),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if let Err(e) = services.register(server) {
+            error!("Failed to register transport service: {}", e);
+        }
+    }
+    // Reload the config file when it changes, including when settings:update
+    // creates it; live settings apply at once
+    let watcher = ConfigWatcher::new(settings_file, Arc::clone(di::root_container()));
+    let queue_listener = watcher.on_reload(|config, changes| {
+        if changes.iter().any(|c| c.key == "features.show_tray_icon") {
+            presentation::webui::tray::set_visible(config.features.show_tray_icon.unwrap_or(false));
+        }
+        if changes.iter().any(|c| c.key.starts_with("events.")) {
+            let overflow = OverflowPolicy::from_name(config.get_event_overflow()).unwrap_or_default();
+            presentation::webui::subscriptions::get_window_subscriptions().set_queue_options(QueueOptions {
+                capacity: config.get_event_queue_capacity(),
+                overflow,
+            });
+        }
+    });
+    if let Err(e) = queue_listener.and_then(|()| services.register(Arc::new(watcher))) {
+        error!("Failed to register config watcher: {}", e);
+    }
+    if let Err(e) = get_global_event_bus().register_payload_type::<ConfigChangedEvent>(
+        CONFIG_CHANGED_EVENT,
+        Some("The config file was reloaded; lists each changed setting and those needing a restart".to_string()),
+    ) {
+        error!("Failed to register the {} schema: {}", CONFIG_CHANGED_EVENT, e);
+    }
+
+    if let Err(e) = services.start_all() {
+        error!("Failed to start services: {}", e);
+        services.shutdown();
+        return;
+    }
 
     // Get window settings from config
     let window_title = config.get_window_title();
@@ -256,6 +412,7 @@ fn main() {
         None => {
             error!("Could not locate frontend dist/index.html");
             error!("Run `./run.sh --build-frontend` and ensure dist/index.html exists.");
+            services.shutdown();
             return;
         }
     };
@@ -283,14 +440,41 @@ fn main() {
         }
     }
 
+    // Announce the configured serialization format; the frontend answers with serialization:negotiate
+    let js = format!(
+        "window.__WEBUI_SERIALIZATION = '{}'; window.dispatchEvent(new CustomEvent('webui:serialization', {{ detail: {{ format: '{}' }} }}));",
+        serialization, serialization
+    );
+    my_window.run_js(js);
+
+    // Announce the REST base URL so the frontend can switch to fetch()
+    if let Some(base_url) = http_server.as_ref().and_then(|s| s.with(|server| server.base_url())) {
+        let js = format!(
+            "window.__API_BASE_URL = '{}'; window.dispatchEvent(new CustomEvent('webui:api', {{ detail: {{ baseUrl: '{}' }} }}));",
+            base_url, base_url
+        );
+        my_window.run_js(js);
+    }
+
+    // Announce the WebSocket URL so the frontend can connect
+    if let Some(url) = ws_server.as_ref().and_then(|s| s.with(|server| server.url())) {
+        let js = format!(
+            "window.__WS_URL = '{}'; window.dispatchEvent(new CustomEvent('webui:ws', {{ detail: {{ url: '{}' }} }}));",
+            url, url
+        );
+        my_window.run_js(js);
+    }
+
     info!("Application started successfully, waiting for events...");
     info!("=============================================");
 
-    // Wait until all windows are closed
+    // Wait until all windows are closed, or a termination signal closes them
     webui::wait();
 
     info!("Application shutting down...");
+    services.shutdown();
     info!("=============================================");
+    log::logger().flush();
 }
 
 fn resolve_frontend_dist() -> Option<(PathBuf, PathBuf)> {
@@ -0,0 +1,117 @@
+// src/utils/serialization/benchmark.rs
+// Measure the supported formats against real application payloads
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::{decode_bytes, encode_bytes, stats, SerializationFormat};
+
+/// Result for one payload in one format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub payload: String,
+    pub format: String,
+    pub bytes: usize,
+    pub avg_serialization_time_us: f64,
+    pub avg_deserialization_time_us: f64,
+    /// Encoded size relative to JSON for the same payload
+    pub size_ratio: f64,
+    /// JSON round-trip time divided by this format's round-trip time
+    pub speed_ratio: f64,
+}
+
+/// Encode and decode every payload `iterations` times in each format
+///
+/// Timings are kept out of the live `SerializationStats`; the size of each
+/// format relative to JSON, over all payloads, becomes their `compression_ratio`.
+pub fn run_benchmark(
+    payloads: &[(String, serde_json::Value)],
+    iterations: u32,
+) -> Result<Vec<BenchmarkResult>, String> {
+    let iterations = iterations.max(1);
+    let mut results = Vec::new();
+
+    for (name, value) in payloads {
+        let mut measured = Vec::new();
+        for format in SerializationFormat::available_formats() {
+            measured.push((*format, measure(value, *format, iterations)?));
+        }
+
+        let (json_bytes, json_round_trip) = measured
+            .iter()
+            .find(|(format, _)| *format == SerializationFormat::Json)
+            .map(|(_, m)| (m.bytes, m.serialize + m.deserialize))
+            .unwrap_or_default();
+
+        for (format, m) in measured {
+            let round_trip = m.serialize + m.deserialize;
+            results.push(BenchmarkResult {
+                payload: name.clone(),
+                format: format.to_string(),
+                bytes: m.bytes,
+                avg_serialization_time_us: per_iteration_us(m.serialize, iterations),
+                avg_deserialization_time_us: per_iteration_us(m.deserialize, iterations),
+                size_ratio: ratio(m.bytes as f64, json_bytes as f64),
+                speed_ratio: ratio(json_round_trip.as_secs_f64(), round_trip.as_secs_f64()),
+            });
+        }
+    }
+
+    let total_bytes = |format: SerializationFormat| -> usize {
+        results
+            .iter()
+            .filter(|r| r.format == format.to_string())
+            .map(|r| r.bytes)
+            .sum()
+    };
+    let json_bytes = total_bytes(SerializationFormat::Json);
+    for format in SerializationFormat::available_formats() {
+        stats::record_benchmark_size_ratio(*format, ratio(total_bytes(*format) as f64, json_bytes as f64));
+    }
+
+    Ok(results)
+}
+
+struct Measurement {
+    bytes: usize,
+    serialize: Duration,
+    deserialize: Duration,
+}
+
+fn measure(
+    value: &serde_json::Value,
+    format: SerializationFormat,
+    iterations: u32,
+) -> Result<Measurement, String> {
+    let mut bytes = Vec::new();
+    let started = Instant::now();
+    for _ in 0..iterations {
+        bytes = encode_bytes(value, format)?;
+    }
+    let serialize = started.elapsed();
+
+    let started = Instant::now();
+    for _ in 0..iterations {
+        decode_bytes::<serde_json::Value>(&bytes, format)?;
+    }
+    let deserialize = started.elapsed();
+
+    Ok(Measurement {
+        bytes: bytes.len(),
+        serialize,
+        deserialize,
+    })
+}
+
+fn per_iteration_us(total: Duration, iterations: u32) -> f64 {
+    total.as_secs_f64() * 1_000_000.0 / iterations as f64
+}
+
+fn ratio(value: f64, baseline: f64) -> f64 {
+    if baseline == 0.0 || value == 0.0 {
+        1.0
+    } else {
+        value / baseline
+    }
+}
@@ -3,12 +3,27 @@
 // Serialization utilities for backend-frontend communication
 // Supports multiple formats: JSON, MessagePack, CBOR
 
+mod benchmark;
+mod stats;
+
+pub use benchmark::{run_benchmark, BenchmarkResult};
+pub use stats::{get_all_stats, reset_stats};
+
 use base64::{engine::general_purpose::STANDARD, Engine};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::sync::{OnceLock, RwLock};
+use std::time::Instant;
+
+/// Format chosen from `communication.serialization` at startup
+static SELECTED_FORMAT: OnceLock<RwLock<SerializationFormat>> = OnceLock::new();
+
+fn selected_format() -> &'static RwLock<SerializationFormat> {
+    SELECTED_FORMAT.get_or_init(|| RwLock::new(SerializationFormat::Json))
+}
 
 /// Supported serialization formats
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SerializationFormat {
     Json,
     MessagePack,
@@ -35,10 +50,63 @@ impl SerializationFormat {
         ]
     }
 
-    /// Get the currently selected format
+    /// Get the currently selected format (JSON until `select` is called)
     pub fn selected() -> SerializationFormat {
-        // Default to JSON for web compatibility
-        SerializationFormat::Json
+        selected_format()
+            .read()
+            .map(|format| *format)
+            .unwrap_or(SerializationFormat::Json)
+    }
+
+    /// Make this the format used by every transport
+    pub fn select(self) {
+        if let Ok(mut format) = selected_format().write() {
+            *format = self;
+        }
+    }
+
+    /// Parse a config value (`json`, `messagepack`/`msgpack`, `cbor`)
+    pub fn from_name(name: &str) -> Option<SerializationFormat> {
+        match name.trim().to_lowercase().as_str() {
+            "json" => Some(SerializationFormat::Json),
+            "messagepack" | "msgpack" => Some(SerializationFormat::MessagePack),
+            "cbor" => Some(SerializationFormat::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Name used in config files and on the wire
+    pub fn name(&self) -> &'static str {
+        match self {
+            SerializationFormat::Json => "json",
+            SerializationFormat::MessagePack => "messagepack",
+            SerializationFormat::Cbor => "cbor",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            SerializationFormat::Json => "application/json",
+            SerializationFormat::MessagePack => "application/msgpack",
+            SerializationFormat::Cbor => "application/cbor",
+        }
+    }
+
+    /// Match a Content-Type / Accept value, ignoring parameters such as charset
+    pub fn from_mime_type(mime: &str) -> Option<SerializationFormat> {
+        let essence = mime.split(';').next().unwrap_or_default().trim().to_lowercase();
+        match essence.as_str() {
+            "application/json" => Some(SerializationFormat::Json),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(SerializationFormat::MessagePack)
+            }
+            "application/cbor" => Some(SerializationFormat::Cbor),
+            _ => None,
+        }
+    }
+
+    pub fn is_binary(&self) -> bool {
+        !matches!(self, SerializationFormat::Json)
     }
 
     /// Get format description
@@ -124,21 +192,75 @@ impl Default for SerializationStats {
     }
 }
 
+/// Serialize data to raw bytes in the specified format
+///
+/// Recorded in the live stats for the format.
+pub fn to_bytes<T: Serialize>(value: &T, format: SerializationFormat) -> Result<Vec<u8>, String> {
+    let started = Instant::now();
+    let bytes = encode_bytes(value, format)?;
+    stats::record_serialization(format, bytes.len(), started.elapsed());
+    Ok(bytes)
+}
+
+/// Deserialize data from raw bytes in the specified format
+///
+/// Recorded in the live stats for the format.
+pub fn from_bytes<T: for<'de> Deserialize<'de>>(
+    data: &[u8],
+    format: SerializationFormat,
+) -> Result<T, String> {
+    let started = Instant::now();
+    let value = decode_bytes(data, format)?;
+    stats::record_deserialization(format, data.len(), started.elapsed());
+    Ok(value)
+}
+
+/// Count a received payload that is passed on undecoded, such as a JSON request
+/// body the handler parses itself
+pub fn record_received(bytes: usize, format: SerializationFormat) {
+    stats::record_received(format, bytes);
+}
+
+fn encode_bytes<T: Serialize>(value: &T, format: SerializationFormat) -> Result<Vec<u8>, String> {
+    match format {
+        SerializationFormat::Json => {
+            serde_json::to_vec(value).map_err(|e| format!("JSON serialize error: {}", e))
+        }
+        // Named fields so structs decode as maps, not positional arrays, on the frontend
+        SerializationFormat::MessagePack => rmp_serde::to_vec_named(value)
+            .map_err(|e| format!("MessagePack serialize error: {}", e)),
+        SerializationFormat::Cbor => {
+            serde_cbor::to_vec(value).map_err(|e| format!("CBOR serialize error: {}", e))
+        }
+    }
+}
+
+fn decode_bytes<T: for<'de> Deserialize<'de>>(
+    data: &[u8],
+    format: SerializationFormat,
+) -> Result<T, String> {
+    match format {
+        SerializationFormat::Json => {
+            serde_json::from_slice(data).map_err(|e| format!("JSON deserialize error: {}", e))
+        }
+        SerializationFormat::MessagePack => rmp_serde::from_slice(data)
+            .map_err(|e| format!("MessagePack deserialize error: {}", e)),
+        SerializationFormat::Cbor => {
+            serde_cbor::from_slice(data).map_err(|e| format!("CBOR deserialize error: {}", e))
+        }
+    }
+}
+
 /// Serialize data to the specified format
+///
+/// Binary formats are base64 encoded for text-only transports.
 pub fn serialize<T: Serialize>(value: &T, format: SerializationFormat) -> Result<String, String> {
     match format {
         SerializationFormat::Json => {
             serde_json::to_string(value).map_err(|e| format!("JSON serialize error: {}", e))
         }
-        SerializationFormat::MessagePack => {
-            let bytes = rmp_serde::to_vec(value)
-                .map_err(|e| format!("MessagePack serialize error: {}", e))?;
-            Ok(base64_encode(&bytes))
-        }
-        SerializationFormat::Cbor => {
-            let bytes =
-                serde_cbor::to_vec(value).map_err(|e| format!("CBOR serialize error: {}", e))?;
-            Ok(base64_encode(&bytes))
+        SerializationFormat::MessagePack | SerializationFormat::Cbor => {
+            Ok(base64_encode(&to_bytes(value, format)?))
         }
     }
 }
@@ -152,16 +274,10 @@ pub fn deserialize<T: for<'de> Deserialize<'de>>(
         SerializationFormat::Json => {
             serde_json::from_str(data).map_err(|e| format!("JSON deserialize error: {}", e))
         }
-        SerializationFormat::MessagePack => {
+        SerializationFormat::MessagePack | SerializationFormat::Cbor => {
             let bytes = base64_decode(data)
-                .map_err(|e| format!("MessagePack base64 decode error: {}", e))?;
-            rmp_serde::from_slice(&bytes)
-                .map_err(|e| format!("MessagePack deserialize error: {}", e))
-        }
-        SerializationFormat::Cbor => {
-            let bytes =
-                base64_decode(data).map_err(|e| format!("CBOR base64 decode error: {}", e))?;
-            serde_cbor::from_slice(&bytes).map_err(|e| format!("CBOR deserialize error: {}", e))
+                .map_err(|e| format!("{} base64 decode error: {}", format, e))?;
+            from_bytes(&bytes, format)
         }
     }
 }
@@ -178,34 +294,77 @@ fn base64_decode(data: &str) -> Result<Vec<u8>, String> {
         .map_err(|e| format!("Base64 decode error: {}", e))
 }
 
-/// Get comparison table of all formats
-pub fn get_format_comparison() -> Vec<FormatComparison> {
-    vec![
-        FormatComparison {
-            format: "JSON".to_string(),
-            size_ratio: "1.0x (baseline)".to_string(),
-            speed_ratio: "1.0x (baseline)".to_string(),
-            readability: "✅ Human readable".to_string(),
-            browser_support: "✅ Universal".to_string(),
-            use_case: "Default, debugging, APIs".to_string(),
-        },
-        FormatComparison {
-            format: "MessagePack".to_string(),
-            size_ratio: "~0.7x (30% smaller)".to_string(),
-            speed_ratio: "~1.5x faster".to_string(),
-            readability: "❌ Binary".to_string(),
-            browser_support: "⚠️ Needs library".to_string(),
-            use_case: "Performance-critical".to_string(),
-        },
-        FormatComparison {
-            format: "CBOR".to_string(),
-            size_ratio: "~0.6x (40% smaller)".to_string(),
-            speed_ratio: "~1.6x faster".to_string(),
-            readability: "❌ Binary".to_string(),
-            browser_support: "⚠️ Limited".to_string(),
-            use_case: "Embedded, IoT".to_string(),
-        },
-    ]
+/// Summarize benchmark results into a comparison table of all formats
+///
+/// Ratios are totals over every benchmarked payload, relative to JSON.
+pub fn get_format_comparison(results: &[BenchmarkResult]) -> Vec<FormatComparison> {
+    let totals = |format: SerializationFormat| {
+        results
+            .iter()
+            .filter(|r| r.format == format.to_string())
+            .fold((0usize, 0f64), |(bytes, us), r| {
+                (
+                    bytes + r.bytes,
+                    us + r.avg_serialization_time_us + r.avg_deserialization_time_us,
+                )
+            })
+    };
+    let (json_bytes, json_us) = totals(SerializationFormat::Json);
+
+    SerializationFormat::available_formats()
+        .iter()
+        .map(|format| {
+            let (bytes, us) = totals(*format);
+            let (readability, browser_support, use_case) = match format {
+                SerializationFormat::Json => ("✅ Human readable", "✅ Universal", "Default, debugging, APIs"),
+                SerializationFormat::MessagePack => ("❌ Binary", "⚠️ Needs library", "Performance-critical"),
+                SerializationFormat::Cbor => ("❌ Binary", "⚠️ Limited", "Embedded, IoT"),
+            };
+            let (size_ratio, speed_ratio) = if *format == SerializationFormat::Json {
+                ("1.0x (baseline)".to_string(), "1.0x (baseline)".to_string())
+            } else {
+                (
+                    describe_size_ratio(bytes as f64, json_bytes as f64),
+                    describe_speed_ratio(json_us, us),
+                )
+            };
+            FormatComparison {
+                format: format.to_string(),
+                size_ratio,
+                speed_ratio,
+                readability: readability.to_string(),
+                browser_support: browser_support.to_string(),
+                use_case: use_case.to_string(),
+            }
+        })
+        .collect()
+}
+
+fn describe_size_ratio(bytes: f64, json_bytes: f64) -> String {
+    if bytes == 0.0 || json_bytes == 0.0 {
+        return "n/a".to_string();
+    }
+    let ratio = bytes / json_bytes;
+    let percent = ((1.0 - ratio) * 100.0).round();
+    if percent > 0.0 {
+        format!("{:.2}x ({}% smaller)", ratio, percent)
+    } else if percent < 0.0 {
+        format!("{:.2}x ({}% larger)", ratio, -percent)
+    } else {
+        format!("{:.2}x (same size)", ratio)
+    }
+}
+
+fn describe_speed_ratio(json_us: f64, us: f64) -> String {
+    if us == 0.0 || json_us == 0.0 {
+        return "n/a".to_string();
+    }
+    let ratio = json_us / us;
+    if ratio >= 1.0 {
+        format!("{:.2}x faster", ratio)
+    } else {
+        format!("{:.2}x slower", 1.0 / ratio)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -262,4 +421,60 @@ mod tests {
         let deserialized: TestData = deserialize(&serialized, SerializationFormat::Cbor).unwrap();
         assert_eq!(data, deserialized);
     }
+
+    #[test]
+    fn test_raw_bytes_round_trip() {
+        let value = serde_json::json!({"name": "test", "tags": ["a", "b"], "n": -3, "ok": true});
+        for format in SerializationFormat::available_formats() {
+            let bytes = to_bytes(&value, *format).unwrap();
+            let back: serde_json::Value = from_bytes(&bytes, *format).unwrap();
+            assert_eq!(back, value, "round trip through {}", format);
+        }
+    }
+
+    #[test]
+    fn test_stats_record_traffic() {
+        let value = serde_json::json!({"users": [{"id": 1, "name": "Ada"}, {"id": 2, "name": "Grace"}]});
+        let before = stats::get_stats(SerializationFormat::MessagePack);
+
+        let bytes = to_bytes(&value, SerializationFormat::MessagePack).unwrap();
+        let _: serde_json::Value = from_bytes(&bytes, SerializationFormat::MessagePack).unwrap();
+
+        let after = stats::get_stats(SerializationFormat::MessagePack);
+        assert!(after.total_serializations > before.total_serializations);
+        assert!(after.total_deserializations > before.total_deserializations);
+        assert!(after.total_bytes_sent >= before.total_bytes_sent + bytes.len() as u64);
+    }
+
+    #[test]
+    fn test_benchmark_and_comparison() {
+        let payloads = vec![(
+            "users".to_string(),
+            serde_json::json!([{"id": 1, "name": "Ada", "email": "ada@example.com", "active": true}]),
+        )];
+        let results = run_benchmark(&payloads, 3).unwrap();
+        assert_eq!(results.len(), SerializationFormat::available_formats().len());
+
+        let json = results.iter().find(|r| r.format == "JSON").unwrap();
+        assert_eq!(json.size_ratio, 1.0);
+        let msgpack = results.iter().find(|r| r.format == "MessagePack").unwrap();
+        assert!(msgpack.bytes < json.bytes);
+        assert!(stats::get_stats(SerializationFormat::MessagePack).compression_ratio < 1.0);
+
+        let comparison = get_format_comparison(&results);
+        assert_eq!(comparison[0].size_ratio, "1.0x (baseline)");
+        assert!(comparison[1].size_ratio.contains("smaller"));
+    }
+
+    #[test]
+    fn test_format_names() {
+        assert_eq!(SerializationFormat::from_name("msgpack"), Some(SerializationFormat::MessagePack));
+        assert_eq!(SerializationFormat::from_name("CBOR"), Some(SerializationFormat::Cbor));
+        assert_eq!(SerializationFormat::from_name("xml"), None);
+        assert_eq!(
+            SerializationFormat::from_mime_type("application/json; charset=utf-8"),
+            Some(SerializationFormat::Json)
+        );
+        assert!(SerializationFormat::Cbor.is_binary());
+    }
 }
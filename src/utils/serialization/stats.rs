@@ -0,0 +1,119 @@
+// src/utils/serialization/stats.rs
+// Live per-format counters for every message encoded or decoded by the bridge
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use super::{SerializationFormat, SerializationStats};
+
+#[derive(Default)]
+struct FormatCounters {
+    serializations: AtomicU64,
+    deserializations: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    /// Decodes that were timed; JSON request payloads are parsed by the handler
+    timed_deserializations: AtomicU64,
+    serialize_nanos: AtomicU64,
+    deserialize_nanos: AtomicU64,
+    /// Size relative to JSON from the last benchmark, as `f64` bits; 0 until one runs
+    benchmark_size_ratio: AtomicU64,
+}
+
+static COUNTERS: OnceLock<[FormatCounters; 3]> = OnceLock::new();
+
+fn counters(format: SerializationFormat) -> &'static FormatCounters {
+    let all = COUNTERS.get_or_init(Default::default);
+    match format {
+        SerializationFormat::Json => &all[0],
+        SerializationFormat::MessagePack => &all[1],
+        SerializationFormat::Cbor => &all[2],
+    }
+}
+
+pub(super) fn record_serialization(format: SerializationFormat, bytes: usize, elapsed: Duration) {
+    let c = counters(format);
+    c.serializations.fetch_add(1, Ordering::Relaxed);
+    c.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+    c.serialize_nanos
+        .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+}
+
+pub(super) fn record_deserialization(format: SerializationFormat, bytes: usize, elapsed: Duration) {
+    record_received(format, bytes);
+    let c = counters(format);
+    c.timed_deserializations.fetch_add(1, Ordering::Relaxed);
+    c.deserialize_nanos
+        .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+}
+
+/// Count a received message whose decoding happens elsewhere
+pub(super) fn record_received(format: SerializationFormat, bytes: usize) {
+    let c = counters(format);
+    c.deserializations.fetch_add(1, Ordering::Relaxed);
+    c.bytes_received.fetch_add(bytes as u64, Ordering::Relaxed);
+}
+
+pub(super) fn record_benchmark_size_ratio(format: SerializationFormat, ratio: f64) {
+    counters(format)
+        .benchmark_size_ratio
+        .store(ratio.to_bits(), Ordering::Relaxed);
+}
+
+/// Snapshot of the counters for one format
+pub fn get_stats(format: SerializationFormat) -> SerializationStats {
+    let c = counters(format);
+    let serializations = c.serializations.load(Ordering::Relaxed);
+    let deserializations = c.deserializations.load(Ordering::Relaxed);
+    let bytes_sent = c.bytes_sent.load(Ordering::Relaxed);
+    let size_ratio = f64::from_bits(c.benchmark_size_ratio.load(Ordering::Relaxed));
+
+    SerializationStats {
+        format: format.to_string(),
+        total_serializations: serializations,
+        total_deserializations: deserializations,
+        total_bytes_sent: bytes_sent,
+        total_bytes_received: c.bytes_received.load(Ordering::Relaxed),
+        avg_serialization_time_us: average_us(c.serialize_nanos.load(Ordering::Relaxed), serializations),
+        avg_deserialization_time_us: average_us(
+            c.deserialize_nanos.load(Ordering::Relaxed),
+            c.timed_deserializations.load(Ordering::Relaxed),
+        ),
+        compression_ratio: if size_ratio > 0.0 { size_ratio } else { 1.0 },
+    }
+}
+
+/// Snapshot of the counters for every format
+pub fn get_all_stats() -> Vec<SerializationStats> {
+    SerializationFormat::available_formats()
+        .iter()
+        .map(|format| get_stats(*format))
+        .collect()
+}
+
+/// Zero the traffic counters; the benchmark size ratio is kept
+pub fn reset_stats() {
+    for format in SerializationFormat::available_formats() {
+        let c = counters(*format);
+        for counter in [
+            &c.serializations,
+            &c.deserializations,
+            &c.bytes_sent,
+            &c.bytes_received,
+            &c.timed_deserializations,
+            &c.serialize_nanos,
+            &c.deserialize_nanos,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+fn average_us(total_nanos: u64, count: u64) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        total_nanos as f64 / count as f64 / 1000.0
+    }
+}